@@ -0,0 +1,206 @@
+// 成就：订阅玩法事件判定解锁，解锁时在屏幕上方弹出提示，主菜单里可以查看全部成就。
+// 解锁记录和累计数据保存在用户数据目录下的 achievements.txt，每行一个 `键 = 值`
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use ggez::{
+    graphics::{self, Color, Rect, Text, TextFragment},
+    nalgebra as na, Context, GameResult,
+};
+
+use crate::draw_centered_text;
+use crate::events::{EventSubscriber, GameEvent};
+use crate::storage;
+use crate::ItemType;
+
+const ACHIEVEMENTS_FILE: &str = "achievements.txt";
+const CAREER_GOLD_GOAL: i64 = 10_000;
+const TOAST_SECONDS: f32 = 3.0;
+
+// 成就定义
+#[derive(Debug, Clone, Copy)]
+pub struct Achievement {
+    pub id: &'static str, // 存盘用的键
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+pub const ACHIEVEMENTS: [Achievement; 5] = [
+    Achievement {
+        id: "first_diamond",
+        name: "First Diamond",
+        description: "Reel in your first diamond",
+    },
+    Achievement {
+        id: "career_gold",
+        name: "Gold Baron",
+        description: "Bank $10000 worth of gold over your career",
+    },
+    Achievement {
+        id: "perfect_level",
+        name: "Clean Sweep",
+        description: "Clear every item from a level",
+    },
+    Achievement {
+        id: "demolition",
+        name: "Demolition",
+        description: "Set off an explosion",
+    },
+    Achievement {
+        id: "lucky_bag",
+        name: "Lucky Bag",
+        description: "Open a mystery bag",
+    },
+];
+
+// 解锁提示
+#[derive(Debug, Clone)]
+struct Toast {
+    achievement: Achievement,
+    life: f32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Achievements {
+    unlocked: BTreeSet<String>,
+    career_gold: i64, // 历史累计拉回的黄金价值
+    toasts: Vec<Toast>,
+    unsaved: bool,
+}
+
+impl Achievements {
+    fn path() -> PathBuf {
+        storage::user_data_dir().join(ACHIEVEMENTS_FILE)
+    }
+
+    // 读取解锁记录，文件不存在或某行损坏时跳过
+    pub fn load() -> Self {
+        let text = fs::read_to_string(Self::path()).unwrap_or_default();
+        let mut achievements = Achievements::default();
+        for (key, value) in text.lines().filter_map(|line| line.split_once('=')) {
+            match (key.trim(), value.trim()) {
+                ("career_gold", value) => achievements.career_gold = value.parse().unwrap_or(0),
+                (id, "1") => {
+                    achievements.unlocked.insert(id.to_string());
+                }
+                _ => (),
+            }
+        }
+        achievements
+    }
+
+    pub fn save(&mut self) -> io::Result<()> {
+        fs::create_dir_all(storage::user_data_dir())?;
+        let mut out = fs::File::create(Self::path())?;
+        writeln!(out, "career_gold = {}", self.career_gold)?;
+        for id in &self.unlocked {
+            writeln!(out, "{} = 1", id)?;
+        }
+        self.unsaved = false;
+        Ok(())
+    }
+
+    // 有新解锁或累计数据变化、还没写盘
+    pub fn unsaved(&self) -> bool {
+        self.unsaved
+    }
+
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.contains(id)
+    }
+
+    pub fn unlocked_count(&self) -> usize {
+        ACHIEVEMENTS.iter().filter(|a| self.is_unlocked(a.id)).count()
+    }
+
+    fn unlock(&mut self, id: &str) {
+        if self.is_unlocked(id) {
+            return;
+        }
+        if let Some(achievement) = ACHIEVEMENTS.iter().find(|a| a.id == id) {
+            self.unlocked.insert(id.to_string());
+            self.toasts.push(Toast {
+                achievement: *achievement,
+                life: TOAST_SECONDS,
+            });
+            self.unsaved = true;
+        }
+    }
+
+    // 提示按真实时间淡出，结算画面停下来时也会消失
+    pub fn update(&mut self, dt: f32) {
+        for toast in self.toasts.iter_mut() {
+            toast.life -= dt;
+        }
+        self.toasts.retain(|toast| toast.life > 0.0);
+    }
+
+    // 屏幕上方依次叠放的解锁提示
+    pub fn draw_toasts(&self, ctx: &mut Context, screen_w: f32) -> GameResult {
+        for (i, toast) in self.toasts.iter().enumerate() {
+            let alpha = (toast.life / 0.5).min(1.0);
+            let panel = Rect::new(screen_w / 2.0 - 160.0, 120.0 + i as f32 * 60.0, 320.0, 52.0);
+            graphics::rectangle(
+                ctx,
+                graphics::DrawParam::default().dest(panel.point()),
+                &panel,
+                Color::new(0.1, 0.1, 0.1, 0.85 * alpha),
+            )?;
+            let title = Text::new(TextFragment::new(format!("Achievement unlocked: {}", toast.achievement.name))
+                .color(Color::new(1.0, 0.84, 0.0, alpha))
+                .font_size(18));
+            graphics::draw(ctx, &title, graphics::DrawParam::default().dest(na::Point2::new(panel.x + 10.0, panel.y + 6.0)))?;
+            let description = Text::new(TextFragment::new(toast.achievement.description)
+                .color(Color::new(1.0, 1.0, 1.0, alpha))
+                .font_size(14));
+            graphics::draw(ctx, &description, graphics::DrawParam::default().dest(na::Point2::new(panel.x + 10.0, panel.y + 30.0)))?;
+        }
+        Ok(())
+    }
+
+    // 成就列表界面
+    pub fn draw_list(&self, ctx: &mut Context, screen_w: f32, screen_h: f32) -> GameResult {
+        let title = format!("Achievements  {}/{}", self.unlocked_count(), ACHIEVEMENTS.len());
+        draw_centered_text(ctx, screen_w, &title, 36.0, Color::new(1.0, 0.84, 0.0, 1.0), 80.0)?;
+        for (i, achievement) in ACHIEVEMENTS.iter().enumerate() {
+            let y = 150.0 + i as f32 * 60.0;
+            let (mark, color) = if self.is_unlocked(achievement.id) {
+                ("[x]", Color::new(1.0, 0.84, 0.0, 1.0))
+            } else {
+                ("[ ]", Color::new(0.6, 0.6, 0.6, 1.0))
+            };
+            let name = Text::new(TextFragment::new(format!("{} {}", mark, achievement.name))
+                .color(color)
+                .font_size(24));
+            graphics::draw(ctx, &name, graphics::DrawParam::default().dest(na::Point2::new(screen_w / 2.0 - 220.0, y)))?;
+            let description = Text::new(TextFragment::new(achievement.description)
+                .color(Color::new(0.8, 0.8, 0.8, 1.0))
+                .font_size(16));
+            graphics::draw(ctx, &description, graphics::DrawParam::default().dest(na::Point2::new(screen_w / 2.0 - 180.0, y + 28.0)))?;
+        }
+        let progress = format!("Career gold: ${} / ${}", self.career_gold.min(CAREER_GOLD_GOAL), CAREER_GOLD_GOAL);
+        draw_centered_text(ctx, screen_w, &progress, 18.0, Color::WHITE, screen_h - 90.0)?;
+        draw_centered_text(ctx, screen_w, "Esc: back", 18.0, Color::new(0.7, 0.7, 0.7, 1.0), screen_h - 50.0)
+    }
+}
+
+impl EventSubscriber for Achievements {
+    fn on_event(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::ItemBanked { player: 0, item_type: ItemType::Diamond, .. } => self.unlock("first_diamond"),
+            GameEvent::ItemBanked { player: 0, item_type: ItemType::Gold(_), value, .. } => {
+                self.career_gold += *value as i64;
+                self.unsaved = true;
+                if self.career_gold >= CAREER_GOLD_GOAL {
+                    self.unlock("career_gold");
+                }
+            }
+            GameEvent::LevelCompleted { skipped, .. } if skipped.is_empty() => self.unlock("perfect_level"),
+            GameEvent::ExplosionTriggered { .. } => self.unlock("demolition"),
+            GameEvent::MysteryBagOpened { .. } => self.unlock("lucky_bag"),
+            _ => (),
+        }
+    }
+}
@@ -0,0 +1,206 @@
+// 电脑对手：每帧先看一眼场面，挑性价比最高的物品（价值 ÷ 重量，离得远的打折扣），
+// 等摆钩转到对准目标时出钩，摆幅够不着就先开矿车过去。
+// 技能等级决定反应快慢、瞄准误差和估价的准头；AI 和键盘一样实现 InputSource，对战模式把它当作玩家二的输入源
+use ggez::nalgebra as na;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::input::{InputAction, InputSource};
+
+const REACH_MARGIN: f32 = 0.95; // 只瞄准绳长以内这个比例的物品，留点余量
+const ARC_MARGIN: f32 = 0.9; // 目标角度超出摆幅这个比例就开车过去
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Skill {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Skill {
+    pub fn from_name(name: &str) -> Option<Skill> {
+        match name {
+            "easy" => Some(Skill::Easy),
+            "normal" => Some(Skill::Normal),
+            "hard" => Some(Skill::Hard),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Skill::Easy => "Easy",
+            Skill::Normal => "Normal",
+            Skill::Hard => "Hard",
+        }
+    }
+
+    // 主菜单里左右切换
+    pub fn harder(self) -> Skill {
+        match self {
+            Skill::Easy => Skill::Normal,
+            _ => Skill::Hard,
+        }
+    }
+
+    pub fn easier(self) -> Skill {
+        match self {
+            Skill::Hard => Skill::Normal,
+            _ => Skill::Easy,
+        }
+    }
+
+    // 钩子收回后要愣多少帧才开始瞄准
+    fn reaction_ticks(self) -> u64 {
+        match self {
+            Skill::Easy => 50,
+            Skill::Normal => 25,
+            Skill::Hard => 8,
+        }
+    }
+
+    // 出钩角度的最大误差（弧度）
+    fn aim_error(self) -> f32 {
+        match self {
+            Skill::Easy => 0.12,
+            Skill::Normal => 0.05,
+            Skill::Hard => 0.01,
+        }
+    }
+
+    // 估价的随机偏差比例，估错了就会去抓不值当的东西
+    fn misjudge(self) -> f32 {
+        match self {
+            Skill::Easy => 0.6,
+            Skill::Normal => 0.25,
+            Skill::Hard => 0.0,
+        }
+    }
+}
+
+// 场上一件可以抓的物品
+#[derive(Debug, Clone, Copy)]
+pub struct Target {
+    pub index: usize, // 在物品列表里的下标
+    pub position: na::Point2<f32>,
+    pub value: i32,
+    pub weight: f32,
+}
+
+// AI 每帧看到的场面
+#[derive(Debug, Clone, Default)]
+pub struct Board {
+    pub targets: Vec<Target>,
+    pub origin: na::Point2<f32>, // 自己矿车的位置
+    pub angle: f32,              // 自己钩子当前的摆动角度
+    pub arc: f32,                // 摆幅（弧度）
+    pub reach: f32,              // 绳长
+    pub ready: bool,             // 钩子空闲可以出
+}
+
+pub struct AiInput {
+    skill: Skill,
+    rng: StdRng,
+    board: Board,
+    target: Option<usize>,
+    error: f32,              // 这次瞄准的误差
+    ready_since: Option<u64>, // 钩子从哪一帧开始空闲
+    driving: bool,
+}
+
+impl AiInput {
+    pub fn new(skill: Skill, seed: u64) -> Self {
+        AiInput {
+            skill,
+            rng: StdRng::seed_from_u64(seed),
+            board: Board::default(),
+            target: None,
+            error: 0.0,
+            ready_since: None,
+            driving: false,
+        }
+    }
+
+    // 挑性价比最高的物品；绳长内没有值钱的就挑全场最好的，开车过去
+    fn pick(&mut self) -> Option<usize> {
+        let reach = self.board.reach * REACH_MARGIN;
+        let origin = self.board.origin;
+        let misjudge = self.skill.misjudge();
+        let mut best: Option<(usize, f32, bool)> = None;
+        // 比绳子还深的物品开到哪里都够不着
+        let reachable = |target: &&Target| target.value > 0 && target.position.y - origin.y <= reach;
+        for target in self.board.targets.iter().filter(reachable) {
+            let distance = na::distance(&origin, &target.position);
+            let guess = 1.0 + self.rng.gen_range(-misjudge..=misjudge);
+            let score = target.value as f32 * guess / target.weight.max(0.1) / (1.0 + distance / reach);
+            let in_reach = distance <= reach;
+            let better = match best {
+                None => true,
+                Some((_, best_score, best_in_reach)) => (in_reach, score) > (best_in_reach, best_score),
+            };
+            if better {
+                best = Some((target.index, score, in_reach));
+            }
+        }
+        best.map(|(index, _, _)| index)
+    }
+}
+
+impl InputSource for AiInput {
+    fn observe(&mut self, board: Board) {
+        self.board = board;
+    }
+
+    fn poll(&mut self, tick: u64) -> Vec<InputAction> {
+        let mut actions = Vec::new();
+        if !self.board.ready {
+            self.ready_since = None;
+            self.target = None;
+            if std::mem::take(&mut self.driving) {
+                actions.push(InputAction::Coast);
+            }
+            return actions;
+        }
+        let since = *self.ready_since.get_or_insert(tick);
+        if tick < since + self.skill.reaction_ticks() {
+            return actions;
+        }
+
+        // 目标被别人抓走了就重新挑
+        let current = self.board.targets.iter().find(|target| Some(target.index) == self.target).copied();
+        let target = match current {
+            Some(target) => target,
+            None => match self.pick() {
+                Some(index) => {
+                    self.target = Some(index);
+                    let error = self.skill.aim_error();
+                    self.error = self.rng.gen_range(-error..=error);
+                    match self.board.targets.iter().find(|target| target.index == index) {
+                        Some(target) => *target,
+                        None => return actions,
+                    }
+                }
+                None => return actions,
+            },
+        };
+
+        let offset = target.position - self.board.origin;
+        let wanted = offset.y.atan2(offset.x) + self.error;
+        let out_of_arc = (wanted - std::f32::consts::FRAC_PI_2).abs() > self.board.arc * ARC_MARGIN;
+        let out_of_reach = offset.norm() > self.board.reach * REACH_MARGIN;
+        if out_of_arc || out_of_reach {
+            self.driving = true;
+            actions.push(if offset.x < 0.0 { InputAction::MoveLeft } else { InputAction::MoveRight });
+            return actions;
+        }
+        if std::mem::take(&mut self.driving) {
+            actions.push(InputAction::Coast);
+        }
+        // 摆钩每帧转过的角度有限，误差容许一点
+        if (self.board.angle - wanted).abs() < self.skill.aim_error().max(0.02) {
+            actions.push(InputAction::Throw { angle: self.board.angle, power: 1.0 });
+            self.target = None;
+            self.ready_since = None;
+        }
+        actions
+    }
+}
@@ -0,0 +1,222 @@
+// 回放分析：出钩角度、漏掉价值热力图、每件物品耗时、进度条跳转
+use ggez::{
+    graphics::{self, Color, Rect, Text, TextFragment},
+    nalgebra as na, Context, GameResult,
+};
+
+use crate::config::GameConfig;
+use crate::replay::{Replay, ReplayPlayer};
+use crate::{GameState, HookState, ItemType, Scene, TICKS_PER_SECOND};
+
+// 热力图网格
+const HEATMAP_COLS: usize = 16;
+const HEATMAP_ROWS: usize = 12;
+
+// 进度条（距屏幕左右和底部的边距）
+const SCRUBBER_X: f32 = 20.0;
+const SCRUBBER_BOTTOM: f32 = 20.0;
+const SCRUBBER_HEIGHT: f32 = 8.0;
+
+// 单次出钩记录
+#[derive(Debug, Clone)]
+pub struct ThrowRecord {
+    pub tick: u64,
+    pub origin: na::Point2<f32>,
+    pub angle: f32,
+    pub grabbed: Option<(ItemType, i32)>,
+    pub duration: u64, // 从出钩到收回的帧数
+}
+
+// 整局回放的分析结果
+#[derive(Debug, Clone)]
+pub struct ReplayAnalysis {
+    pub throws: Vec<ThrowRecord>,
+    pub missed_value: [[i32; HEATMAP_COLS]; HEATMAP_ROWS],
+    pub duration: u64,
+    screen_width: f32,
+    screen_height: f32,
+    hook_length: f32,
+}
+
+impl ReplayAnalysis {
+    // 离屏模拟整局回放，收集分析数据
+    pub fn build(replay: &Replay, config: &GameConfig) -> Self {
+        let (screen_width, screen_height) = (config.screen_width, config.screen_height);
+        let mut state = GameState::headless(config.clone());
+        state.level = replay.header.level;
+        state.start_round(replay.header.seed, Box::new(ReplayPlayer::new(replay.clone())));
+        state.scene = Scene::Playing;
+
+        let mut throws: Vec<ThrowRecord> = Vec::new();
+        let mut was_idle = true;
+        while !state.game_over {
+            state.update(1.0 / TICKS_PER_SECOND as f32);
+
+            // 只统计主钩的出钩
+            let hook = &state.hooks[0];
+            let idle = hook.state == HookState::Idle;
+            if was_idle && !idle {
+                // 新的一次出钩
+                throws.push(ThrowRecord {
+                    tick: state.tick,
+                    origin: state.miner.position,
+                    angle: hook.angle,
+                    grabbed: None,
+                    duration: 0,
+                });
+            }
+            if let Some(throw) = throws.last_mut() {
+                if !idle {
+                    throw.duration = state.tick - throw.tick;
+                    if let Some(item) = hook.attached_item.and_then(|i| state.items.get(i)) {
+                        throw.grabbed = Some((item.item_type, item.value(&state.value_context())));
+                    }
+                }
+            }
+            was_idle = idle;
+        }
+
+        // 统计结束时仍留在矿里的物品价值
+        let mut missed_value = [[0; HEATMAP_COLS]; HEATMAP_ROWS];
+        for item in state.items.iter().filter(|item| !item.collected) {
+            let col = ((item.position.x / screen_width) * HEATMAP_COLS as f32) as usize;
+            let row = ((item.position.y / config.mine_depth) * HEATMAP_ROWS as f32) as usize;
+            missed_value[row.min(HEATMAP_ROWS - 1)][col.min(HEATMAP_COLS - 1)] += item.value(&state.value_context());
+        }
+
+        ReplayAnalysis {
+            throws,
+            missed_value,
+            duration: replay.header.duration,
+            screen_width,
+            screen_height,
+            hook_length: config.hook_length,
+        }
+    }
+
+    fn scrubber_y(&self) -> f32 {
+        self.screen_height - SCRUBBER_BOTTOM
+    }
+
+    fn scrubber_width(&self) -> f32 {
+        self.screen_width - SCRUBBER_X * 2.0
+    }
+
+    // 点击位置落在进度条上时返回对应帧号
+    pub fn scrubber_tick(&self, x: f32, y: f32) -> Option<u64> {
+        let hit = Rect::new(SCRUBBER_X, self.scrubber_y() - 10.0, self.scrubber_width(), SCRUBBER_HEIGHT + 20.0);
+        if !hit.contains(na::Point2::new(x, y)) {
+            return None;
+        }
+        let ratio = ((x - SCRUBBER_X) / self.scrubber_width()).max(0.0).min(1.0);
+        Some((ratio * self.duration as f32) as u64)
+    }
+
+    // 绘制分析层的屏幕部分；出钩线在世界坐标里，由 draw_throws 在镜头变换内单独绘制
+    pub fn draw(&self, ctx: &mut Context, current_tick: u64) -> GameResult {
+        self.draw_heatmap(ctx)?;
+        self.draw_item_times(ctx)?;
+        self.draw_scrubber(ctx, current_tick)
+    }
+
+    // 漏掉的价值热力图
+    fn draw_heatmap(&self, ctx: &mut Context) -> GameResult {
+        let max_value = self.missed_value.iter().flatten().copied().max().unwrap_or(0);
+        if max_value == 0 {
+            return Ok(());
+        }
+
+        let cell_w = self.screen_width / HEATMAP_COLS as f32;
+        let cell_h = self.screen_height / HEATMAP_ROWS as f32;
+        for (row, cells) in self.missed_value.iter().enumerate() {
+            for (col, &value) in cells.iter().enumerate() {
+                if value == 0 {
+                    continue;
+                }
+                let heat = value as f32 / max_value as f32;
+                let cell = Rect::new(col as f32 * cell_w, row as f32 * cell_h, cell_w, cell_h);
+                graphics::rectangle(
+                    ctx,
+                    graphics::DrawParam::default().dest(cell.point()),
+                    &cell,
+                    Color::new(1.0, 0.2, 0.0, 0.15 + heat * 0.45), // 越红漏掉的越多
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    // 出钩角度：已发生的为实线颜色，未发生的淡显
+    pub fn draw_throws(&self, ctx: &mut Context, current_tick: u64) -> GameResult {
+        for throw in &self.throws {
+            let end = na::Point2::new(
+                throw.origin.x + throw.angle.cos() * self.hook_length,
+                throw.origin.y + throw.angle.sin() * self.hook_length,
+            );
+            let alpha = if throw.tick <= current_tick { 0.9 } else { 0.25 };
+            let color = match throw.grabbed {
+                Some(_) => Color::new(0.2, 1.0, 0.2, alpha), // 抓到东西
+                None => Color::new(1.0, 1.0, 1.0, alpha),    // 空钩
+            };
+            graphics::line(ctx, graphics::DrawParam::default(), &[throw.origin, end], 1.0, color)?;
+        }
+        Ok(())
+    }
+
+    // 每件物品耗时列表
+    fn draw_item_times(&self, ctx: &mut Context) -> GameResult {
+        let mut y = 50.0;
+        for throw in self.throws.iter().filter(|t| t.grabbed.is_some()) {
+            let (item_type, value) = throw.grabbed.unwrap();
+            let seconds = throw.duration as f32 / TICKS_PER_SECOND as f32;
+            let line = format!("{} +{} ({:.1}s)", item_type.name(), value, seconds);
+            let text = Text::new(TextFragment::new(line)
+                .color(Color::WHITE)
+                .font_size(14));
+            graphics::draw(ctx, &text, graphics::DrawParam::default().dest(na::Point2::new(10.0, y)))?;
+            y += 16.0;
+            if y > self.scrubber_y() - 30.0 {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    // 底部进度条
+    fn draw_scrubber(&self, ctx: &mut Context, current_tick: u64) -> GameResult {
+        let (bar_y, bar_width) = (self.scrubber_y(), self.scrubber_width());
+        let bar = Rect::new(SCRUBBER_X, bar_y, bar_width, SCRUBBER_HEIGHT);
+        graphics::rectangle(
+            ctx,
+            graphics::DrawParam::default().dest(bar.point()),
+            &bar,
+            Color::new(0.3, 0.3, 0.3, 0.8),
+        )?;
+
+        // 出钩位置刻度
+        for throw in &self.throws {
+            let x = SCRUBBER_X + bar_width * throw.tick as f32 / self.duration.max(1) as f32;
+            let mark = Rect::new(x, bar_y, 1.0, SCRUBBER_HEIGHT);
+            graphics::rectangle(
+                ctx,
+                graphics::DrawParam::default().dest(mark.point()),
+                &mark,
+                Color::new(0.8, 0.8, 0.8, 0.8),
+            )?;
+        }
+
+        let progress = current_tick as f32 / self.duration.max(1) as f32;
+        let cursor = Rect::new(
+            SCRUBBER_X + bar_width * progress.min(1.0) - 2.0,
+            bar_y - 4.0,
+            4.0,
+            SCRUBBER_HEIGHT + 8.0,
+        );
+        graphics::rectangle(
+            ctx,
+            graphics::DrawParam::default().dest(cursor.point()),
+            &cursor,
+            Color::new(1.0, 0.84, 0.0, 1.0),
+        )
+    }
+}
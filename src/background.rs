@@ -0,0 +1,123 @@
+// 静态背景缓存：把地层、装饰和静止物品预先画到离屏画布上，每帧只需贴一张图。
+// 画布覆盖整个矿洞的深度，用世界坐标绘制，由镜头决定显示哪一段。
+// 下落、漂动的物品不进缓存，由主循环每帧单独绘制；哪些物品在动发生变化时才重绘
+use ggez::{
+    conf::NumSamples,
+    graphics::{self, Canvas, DrawParam, Rect},
+    Context, GameResult,
+};
+
+use crate::events::{EventSubscriber, GameEvent};
+use crate::worlds::World;
+use crate::Item;
+
+const SKY_HEIGHT: f32 = 80.0;
+const PEBBLE_SPACING: f32 = 15.0; // 平均每隔多少像素深度一颗碎石
+const BAND_COUNT: usize = 4; // 地层数，颜色由世界主题决定
+
+pub struct BackgroundCache {
+    canvas: Option<Canvas>,
+    dirty: bool,
+    baked: Vec<bool>, // 上次重绘时各物品是否在动
+    world: World,
+    width: f32,
+    height: f32,
+}
+
+impl BackgroundCache {
+    pub fn new(width: f32, height: f32) -> Self {
+        BackgroundCache {
+            canvas: None,
+            dirty: true,
+            baked: Vec::new(),
+            world: World::default(),
+            width,
+            height,
+        }
+    }
+
+    // 场景内容改变（新关卡、物品被抓走）时标记需要重绘
+    pub fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    // 换了世界主题（新关卡）时重绘
+    pub fn set_world(&mut self, world: World) {
+        self.world = world;
+        self.invalidate();
+    }
+
+    // 缓存贴图占用的显存（RGBA8）
+    pub fn cached_bytes(&self) -> usize {
+        match self.canvas {
+            Some(_) => self.width as usize * self.height as usize * 4,
+            None => 0,
+        }
+    }
+
+    // 贴出缓存的背景，必要时先重绘
+    pub fn draw(&mut self, ctx: &mut Context, items: &[Item]) -> GameResult {
+        let restless = !items.iter().map(Item::moving).eq(self.baked.iter().copied());
+        if self.dirty || restless || self.canvas.is_none() {
+            self.render(ctx, items)?;
+            self.baked = items.iter().map(Item::moving).collect();
+            self.dirty = false;
+        }
+        if let Some(canvas) = &self.canvas {
+            graphics::draw(ctx, canvas, DrawParam::default())?;
+        }
+        Ok(())
+    }
+
+    // 重绘离屏画布
+    fn render(&mut self, ctx: &mut Context, items: &[Item]) -> GameResult {
+        if self.canvas.is_none() {
+            self.canvas = Some(Canvas::new(ctx, self.width as u16, self.height as u16, NumSamples::One)?);
+        }
+        let theme = self.world.theme();
+        // 画布用自己的世界坐标，不受镜头平移影响
+        let screen = graphics::screen_coordinates(ctx);
+        graphics::set_canvas(ctx, self.canvas.as_ref());
+        graphics::set_screen_coordinates(ctx, Rect::new(0.0, 0.0, self.width, self.height))?;
+        graphics::push_transform(ctx, Some(DrawParam::default().to_matrix()));
+        graphics::apply_transformations(ctx)?;
+        graphics::clear(ctx, theme.sky);
+
+        // 地层：把地面以下的深度均分，越深颜色越暗
+        let band_height = (self.height - SKY_HEIGHT) / BAND_COUNT as f32;
+        for (i, color) in theme.bands.iter().enumerate() {
+            let band = Rect::new(0.0, SKY_HEIGHT + band_height * i as f32, self.width, band_height);
+            graphics::rectangle(ctx, DrawParam::default().dest(band.point()), &band, *color)?;
+        }
+
+        // 地面装饰：地表线和零散的碎石
+        let grass = Rect::new(0.0, SKY_HEIGHT - 4.0, self.width, 4.0);
+        graphics::rectangle(ctx, DrawParam::default().dest(grass.point()), &grass, theme.surface)?;
+        for i in 0..(self.height / PEBBLE_SPACING) as usize {
+            // 固定的伪随机位置，保证每次重绘一致
+            let x = (i * 197 % self.width as usize) as f32;
+            let y = SKY_HEIGHT + 20.0 + (i * 131 % (self.height as usize - 120)) as f32;
+            let pebble = Rect::new(x, y, 3.0, 3.0);
+            graphics::rectangle(ctx, DrawParam::default().dest(pebble.point()), &pebble, theme.pebble)?;
+        }
+
+        // 静止的物品
+        for item in items.iter().filter(|item| !item.collected && !item.moving()) {
+            item.draw(ctx)?;
+        }
+
+        graphics::pop_transform(ctx);
+        graphics::apply_transformations(ctx)?;
+        graphics::set_canvas(ctx, None);
+        graphics::set_screen_coordinates(ctx, screen)
+    }
+}
+
+impl EventSubscriber for BackgroundCache {
+    fn on_event(&mut self, event: &GameEvent) {
+        // 物品离开原位或被炸掉时，静态层需要重绘
+        if let GameEvent::ItemGrabbed { .. } | GameEvent::ItemDrilled { .. } | GameEvent::ExplosionTriggered { .. } = event {
+            self.invalidate();
+        }
+    }
+}
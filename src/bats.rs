@@ -0,0 +1,178 @@
+// 蝙蝠：平时在矿洞上半部各自绕圈盘旋，看到有钩子拖着东西往回收就扑过去，
+// 碰到挂着的物品就叼走，飞到矿洞另一侧接着盘旋；钩中叼着东西的蝙蝠能把物品抢回来。
+// 盘旋的位置按种子生成，之后的飞行只取决于钩子，回放时完全一致
+use ggez::{
+    graphics::{self, Color, Rect},
+    nalgebra as na, Context, GameResult,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::config::GameConfig;
+use crate::parallel;
+
+const BAT_SIZE: f32 = 18.0;
+const CIRCLE_RADIUS: f32 = 40.0; // 盘旋的半径
+const SWOOP_RANGE: f32 = 220.0; // 拖着的物品离蝙蝠多近时扑过去
+const REST_TICKS: u64 = 180; // 偷完或被抢回后多少帧内不再扑
+const DASH: f32 = 1.6; // 扑过去和逃走时的速度倍率
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Flight {
+    Circling, // 绕着盘旋中心转圈
+    Swooping, // 扑向最近的拖着物品的钩子
+    Fleeing,  // 叼着东西或被抢回后飞回盘旋中心
+}
+
+#[derive(Debug, Clone)]
+pub struct Bat {
+    pub position: na::Point2<f32>,
+    home: na::Point2<f32>, // 盘旋中心
+    flight: Flight,
+    phase: f32,
+    cargo: Option<usize>, // 叼着的物品在物品列表里的下标
+    rest: u64,
+}
+
+impl Bat {
+    fn rect(&self) -> Rect {
+        Rect::new(
+            self.position.x - BAT_SIZE / 2.0,
+            self.position.y - BAT_SIZE / 2.0,
+            BAT_SIZE,
+            BAT_SIZE,
+        )
+    }
+
+    // 朝 target 飞一步，到了返回 true
+    fn fly_to(&mut self, target: na::Point2<f32>, speed: f32) -> bool {
+        let offset = target - self.position;
+        let distance = offset.norm();
+        if distance <= speed {
+            self.position = target;
+            return true;
+        }
+        self.position += offset * (speed / distance);
+        false
+    }
+}
+
+// 本关的全部蝙蝠
+#[derive(Debug, Clone, Default)]
+pub struct Bats {
+    pub bats: Vec<Bat>,
+    speed: f32,
+    width: f32,
+}
+
+impl Bats {
+    pub fn roll(seed: u64, config: &GameConfig) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed.wrapping_add(0x62617473));
+        let bats = (0..config.profile().hazards(config.bat_count))
+            .map(|_| {
+                let x = rng.gen_range(CIRCLE_RADIUS * 2.0..config.screen_width - CIRCLE_RADIUS * 2.0);
+                let y = rng.gen_range(config.mine_depth * 0.2..config.mine_depth * 0.5);
+                let home = na::Point2::new(x, y);
+                Bat {
+                    position: home,
+                    home,
+                    flight: Flight::Circling,
+                    phase: rng.gen_range(0.0..std::f32::consts::TAU),
+                    cargo: None,
+                    rest: 0,
+                }
+            })
+            .collect();
+        Bats {
+            bats,
+            speed: config.bat_speed,
+            width: config.screen_width,
+        }
+    }
+
+    // 每帧飞行，loads 为正拖着物品收回的钩尖位置
+    pub fn update(&mut self, loads: &[na::Point2<f32>]) {
+        let speed = self.speed;
+        parallel::for_each_mut(&mut self.bats, |_, bat| {
+            bat.rest = bat.rest.saturating_sub(1);
+            bat.phase += 0.05;
+            let nearest = loads
+                .iter()
+                .copied()
+                .min_by(|a, b| na::distance(a, &bat.position).total_cmp(&na::distance(b, &bat.position)));
+            match bat.flight {
+                Flight::Circling => {
+                    let orbit = na::Vector2::new(bat.phase.cos(), bat.phase.sin() * 0.5) * CIRCLE_RADIUS;
+                    bat.fly_to(bat.home + orbit, speed);
+                    let spotted = nearest.map_or(false, |load| na::distance(&load, &bat.position) < SWOOP_RANGE);
+                    if bat.cargo.is_none() && bat.rest == 0 && spotted {
+                        bat.flight = Flight::Swooping;
+                    }
+                }
+                Flight::Swooping => match nearest {
+                    Some(load) => {
+                        bat.fly_to(load, speed * DASH);
+                    }
+                    None => bat.flight = Flight::Circling,
+                },
+                Flight::Fleeing => {
+                    if bat.fly_to(bat.home, speed * DASH) {
+                        bat.flight = Flight::Circling;
+                    }
+                }
+            }
+        });
+    }
+
+    // 扑过来的蝙蝠碰到钩尖 point 上挂着的物品（下标 index、半径 reach）时叼走，返回 true
+    pub fn steal(&mut self, point: na::Point2<f32>, index: usize, reach: f32) -> bool {
+        let width = self.width;
+        let bat = match self
+            .bats
+            .iter_mut()
+            .find(|bat| bat.flight == Flight::Swooping && na::distance(&bat.position, &point) < reach + BAT_SIZE / 2.0)
+        {
+            Some(bat) => bat,
+            None => return false,
+        };
+        bat.cargo = Some(index);
+        bat.flight = Flight::Fleeing;
+        bat.rest = REST_TICKS;
+        // 逃到矿洞另一侧
+        bat.home.x = (width - bat.position.x).clamp(CIRCLE_RADIUS * 2.0, width - CIRCLE_RADIUS * 2.0);
+        true
+    }
+
+    // 钩子碰到叼着东西的蝙蝠时把物品抢回来，返回物品下标
+    pub fn catch(&mut self, point: na::Point2<f32>) -> Option<usize> {
+        let bat = self.bats.iter_mut().find(|bat| bat.cargo.is_some() && bat.rect().contains(point))?;
+        bat.flight = Flight::Fleeing;
+        bat.rest = REST_TICKS;
+        bat.cargo.take()
+    }
+
+    // 叼着的物品及其位置（吊在蝙蝠下方）
+    pub fn cargo(&self) -> impl Iterator<Item = (usize, na::Point2<f32>)> + '_ {
+        self.bats
+            .iter()
+            .filter_map(|bat| bat.cargo.map(|index| (index, na::Point2::new(bat.position.x, bat.position.y + BAT_SIZE))))
+    }
+
+    pub fn draw(&self, ctx: &mut Context) -> GameResult {
+        let fur = Color::new(0.25, 0.15, 0.3, 1.0);
+        for bat in &self.bats {
+            let body = bat.rect();
+            let core = Rect::new(body.x + body.w / 4.0, body.y + body.h / 4.0, body.w / 2.0, body.h / 2.0);
+            graphics::rectangle(ctx, graphics::DrawParam::default().dest(core.point()), &core, fur)?;
+            // 两只翅膀上下扇动
+            let flap = (bat.phase * 6.0).sin() * BAT_SIZE / 3.0;
+            for side in [-1.0, 1.0] {
+                let wing = [
+                    bat.position,
+                    na::Point2::new(bat.position.x + side * BAT_SIZE, bat.position.y - flap),
+                ];
+                graphics::line(ctx, graphics::DrawParam::default(), &wing, 3.0, fur)?;
+            }
+        }
+        Ok(())
+    }
+}
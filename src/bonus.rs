@@ -0,0 +1,43 @@
+// 秘密奖励关：经典闯关里达成隐藏条件（收走本关全部钻石，或者抓到金地鼠）并过关后，
+// 进入下一关之前插入一个奖励关：满地金袋，没有石头和危险物，时间很短。
+// 奖励关只凭关卡号和种子生成，回放文件头记下它是奖励关，重放时布局一致
+use crate::events::{EventSubscriber, GameEvent};
+use crate::{Item, ItemType};
+
+// 一关里隐藏条件的进度
+#[derive(Debug, Clone, Default)]
+pub struct Secrets {
+    diamonds: usize, // 开局时场上的钻石数
+    diamonds_banked: usize,
+    golden_mole: bool,
+}
+
+impl Secrets {
+    pub fn new(items: &[Item]) -> Self {
+        Secrets {
+            diamonds: items.iter().filter(|item| item.item_type == ItemType::Diamond).count(),
+            ..Secrets::default()
+        }
+    }
+
+    // 关卡结束时达成的隐藏条件，没有达成返回 None
+    pub fn found(&self) -> Option<&'static str> {
+        if self.golden_mole {
+            Some("You caught the golden mole")
+        } else if self.diamonds > 0 && self.diamonds_banked >= self.diamonds {
+            Some("You found every diamond")
+        } else {
+            None
+        }
+    }
+}
+
+impl EventSubscriber for Secrets {
+    fn on_event(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::ItemBanked { player: 0, item_type: ItemType::Diamond, .. } => self.diamonds_banked += 1,
+            GameEvent::ItemBanked { player: 0, item_type: ItemType::GoldenMole, .. } => self.golden_mole = true,
+            _ => (),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+// 首领关：每 5 关出现一只在矿洞里来回跑的金猪，要钩中好几次才能抓住，抓到后拖回来是一大笔钱。
+// 金猪不在物品列表里，每钩中一次掉一格血、钩子空手而回；最后一钩才把它变成物品拖走
+use ggez::{
+    graphics::{self, Color, Rect},
+    nalgebra as na, Context, GameResult,
+};
+
+use crate::config::GameConfig;
+
+const BOSS_EVERY: u32 = 5; // 每隔几关出现一次
+const PIG_WIDTH: f32 = 90.0;
+const PIG_HEIGHT: f32 = 60.0;
+const FLINCH_TICKS: u64 = 40; // 被钩中后闪烁并加速逃跑的帧数
+const FLINCH_SPEED: f32 = 2.5;
+
+pub fn is_boss_level(number: u32) -> bool {
+    number > 0 && number % BOSS_EVERY == 0
+}
+
+// 钩子碰到金猪的结果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BossHit {
+    Wounded { health: u32 }, // 还剩几格血
+    Captured,
+}
+
+#[derive(Debug, Clone)]
+pub struct Boss {
+    pub position: na::Point2<f32>,
+    velocity: f32, // 每帧水平移动的像素，负数向左
+    pub health: u32,
+    max_health: u32,
+    flinch: u64, // 剩余的受惊帧数
+}
+
+impl Boss {
+    // 金猪从矿洞中部出发，位置固定，回放时完全一致
+    pub fn spawn(config: &GameConfig) -> Self {
+        Boss {
+            position: na::Point2::new(config.screen_width / 2.0, config.mine_depth * 0.6),
+            velocity: config.boss_speed,
+            health: config.boss_health.max(1),
+            max_health: config.boss_health.max(1),
+            flinch: 0,
+        }
+    }
+
+    fn rect(&self) -> Rect {
+        Rect::new(
+            self.position.x - PIG_WIDTH / 2.0,
+            self.position.y - PIG_HEIGHT / 2.0,
+            PIG_WIDTH,
+            PIG_HEIGHT,
+        )
+    }
+
+    // 每帧来回跑，碰到屏幕边缘掉头；受惊时跑得更快
+    pub fn update(&mut self, screen_width: f32) {
+        let speed = if self.flinch > 0 { self.velocity * FLINCH_SPEED } else { self.velocity };
+        self.position.x += speed;
+        let half = PIG_WIDTH / 2.0;
+        if (self.position.x < half && self.velocity < 0.0) || (self.position.x > screen_width - half && self.velocity > 0.0) {
+            self.velocity = -self.velocity;
+        }
+        self.flinch = self.flinch.saturating_sub(1);
+    }
+
+    // 钩子碰到金猪：掉一格血，血掉光就被抓住
+    pub fn hit(&mut self, point: na::Point2<f32>) -> Option<BossHit> {
+        if self.flinch > 0 || !self.rect().contains(point) {
+            return None;
+        }
+        self.health -= 1;
+        if self.health == 0 {
+            return Some(BossHit::Captured);
+        }
+        self.flinch = FLINCH_TICKS;
+        Some(BossHit::Wounded { health: self.health })
+    }
+
+    // 金猪和头顶的血条，受惊时闪烁
+    pub fn draw(&self, ctx: &mut Context) -> GameResult {
+        let body = self.rect();
+        let color = if self.flinch > 0 && (self.flinch / 4) % 2 == 0 {
+            Color::WHITE
+        } else {
+            Color::new(1.0, 0.75, 0.1, 1.0)
+        };
+        graphics::rectangle(ctx, graphics::DrawParam::default().dest(body.point()), &body, color)?;
+
+        // 鼻子画在朝向的一侧
+        let snout_x = if self.velocity > 0.0 { body.x + body.w - 6.0 } else { body.x - 10.0 };
+        let snout = Rect::new(snout_x, self.position.y - 8.0, 16.0, 16.0);
+        graphics::rectangle(ctx, graphics::DrawParam::default().dest(snout.point()), &snout, Color::new(0.95, 0.6, 0.5, 1.0))?;
+
+        let frame = Rect::new(body.x, body.y - 12.0, body.w, 6.0);
+        graphics::rectangle(ctx, graphics::DrawParam::default().dest(frame.point()), &frame, Color::new(0.2, 0.2, 0.2, 0.8))?;
+        let fill = Rect::new(body.x, body.y - 12.0, body.w * self.health as f32 / self.max_health as f32, 6.0);
+        graphics::rectangle(ctx, graphics::DrawParam::default().dest(fill.point()), &fill, Color::new(0.9, 0.15, 0.15, 1.0))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+// 本关增益：商店道具、神秘袋等带来的临时效果，有的持续整关，有的按帧倒计时。
+// 玩法代码只查询某种增益是否生效，不关心它从哪来
+use ggez::{
+    graphics::{self, Color, Rect, Text, TextFragment},
+    nalgebra as na, Context, GameResult,
+};
+
+use crate::TICKS_PER_SECOND;
+
+const ICON_SIZE: f32 = 22.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BuffKind {
+    Strength, // 拖物品时无视重量加速收钩
+    Luck,     // 运气好：物品成色不会差，神秘袋开出好东西的机会更大
+    Magnet,   // 钩子伸出时吸引附近的物品
+    Double,   // 拉回的物品价值翻倍
+    RockBreaker, // 钩到的石头当场粉碎
+}
+
+impl BuffKind {
+    fn icon(self) -> (&'static str, Color) {
+        match self {
+            BuffKind::Strength => ("S", Color::new(0.9, 0.4, 0.1, 1.0)),
+            BuffKind::Luck => ("L", Color::new(0.2, 0.75, 0.3, 1.0)),
+            BuffKind::Magnet => ("M", Color::new(0.8, 0.15, 0.2, 1.0)),
+            BuffKind::Double => ("2", Color::new(0.95, 0.75, 0.05, 1.0)),
+            BuffKind::RockBreaker => ("R", Color::new(0.45, 0.55, 0.7, 1.0)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Buff {
+    pub kind: BuffKind,
+    pub remaining: Option<u64>, // 剩余帧数，None 为持续到本关结束
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Buffs {
+    pub active: Vec<Buff>,
+}
+
+impl Buffs {
+    // 加上一个增益；已有同类增益时取较长的持续时间
+    pub fn add(&mut self, kind: BuffKind, ticks: Option<u64>) {
+        match self.active.iter_mut().find(|buff| buff.kind == kind) {
+            Some(buff) => {
+                buff.remaining = match (buff.remaining, ticks) {
+                    (Some(a), Some(b)) => Some(a.max(b)),
+                    _ => None,
+                }
+            }
+            None => self.active.push(Buff { kind, remaining: ticks }),
+        }
+    }
+
+    pub fn has(&self, kind: BuffKind) -> bool {
+        self.active.iter().any(|buff| buff.kind == kind)
+    }
+
+    // 限时增益的剩余帧数
+    pub fn remaining(&self, kind: BuffKind) -> Option<u64> {
+        self.active.iter().find(|buff| buff.kind == kind).and_then(|buff| buff.remaining)
+    }
+
+    // 每帧倒计时，到期的增益移除
+    pub fn update(&mut self) {
+        for buff in self.active.iter_mut() {
+            if let Some(remaining) = buff.remaining.as_mut() {
+                *remaining = remaining.saturating_sub(1);
+            }
+        }
+        self.active.retain(|buff| buff.remaining != Some(0));
+    }
+
+    // HUD 上的增益图标，限时增益下方显示剩余秒数
+    pub fn draw(&self, ctx: &mut Context, x: f32, y: f32) -> GameResult {
+        for (i, buff) in self.active.iter().enumerate() {
+            let (letter, color) = buff.kind.icon();
+            let icon = Rect::new(x + i as f32 * (ICON_SIZE + 6.0), y, ICON_SIZE, ICON_SIZE);
+            graphics::rectangle(ctx, graphics::DrawParam::default().dest(icon.point()), &icon, color)?;
+            let text = Text::new(TextFragment::new(letter).color(Color::WHITE).font_size(16));
+            graphics::draw(ctx, &text, graphics::DrawParam::default().dest(na::Point2::new(icon.x + 6.0, icon.y + 3.0)))?;
+            if let Some(remaining) = buff.remaining {
+                let seconds = remaining.div_ceil(TICKS_PER_SECOND as u64);
+                let timer = Text::new(TextFragment::new(format!("{}s", seconds)).color(Color::WHITE).font_size(11));
+                graphics::draw(ctx, &timer, graphics::DrawParam::default().dest(na::Point2::new(icon.x, icon.y + ICON_SIZE + 2.0)))?;
+            }
+        }
+        Ok(())
+    }
+}
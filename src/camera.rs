@@ -0,0 +1,70 @@
+// 纵向镜头：矿洞比屏幕深时跟着钩子往下走，钩子收回后回到地面。
+// 矿洞里的东西都用世界坐标，绘制时套上镜头的平移；HUD 用屏幕坐标，不受镜头影响
+use ggez::{graphics, nalgebra as na, Context, GameResult};
+
+const FOLLOW_RATE: f32 = 0.12; // 每帧向目标位置靠近的比例，镜头移动更平滑
+const LEAD: f32 = 0.6; // 钩子保持在屏幕高度的这个位置
+const SHAKE_AMPLITUDE: f32 = 8.0; // 地震时画面晃动的最大像素
+
+#[derive(Debug, Clone, Default)]
+pub struct Camera {
+    pub y: f32, // 屏幕顶端对应的世界纵坐标
+    view_height: f32,
+    depth: f32,
+    shake: u64, // 画面还要晃动的帧数
+    shake_ticks: u64,
+}
+
+impl Camera {
+    pub fn new(view_height: f32, depth: f32) -> Self {
+        Camera {
+            y: 0.0,
+            view_height,
+            depth: depth.max(view_height),
+            shake: 0,
+            shake_ticks: 0,
+        }
+    }
+
+    // 跟随目标（钩子）的纵坐标，不越过矿洞的上下边界
+    pub fn follow(&mut self, target_y: f32) {
+        let desired = (target_y - self.view_height * LEAD).clamp(0.0, self.depth - self.view_height);
+        self.y += (desired - self.y) * FOLLOW_RATE;
+        self.shake = self.shake.saturating_sub(1);
+    }
+
+    // 开始晃动画面，持续 ticks 帧并逐渐减弱
+    pub fn shake(&mut self, ticks: u64) {
+        self.shake = ticks;
+        self.shake_ticks = ticks;
+    }
+
+    // 本帧晃动的偏移，只影响绘制
+    fn shake_offset(&self) -> na::Vector2<f32> {
+        if self.shake == 0 {
+            return na::Vector2::zeros();
+        }
+        let strength = SHAKE_AMPLITUDE * self.shake as f32 / self.shake_ticks as f32;
+        let t = self.shake as f32;
+        na::Vector2::new((t * 1.7).sin() * strength, (t * 2.3).cos() * strength * 0.5)
+    }
+
+    // 屏幕坐标（如鼠标位置）换算成世界坐标
+    pub fn to_world(&self, point: na::Point2<f32>) -> na::Point2<f32> {
+        na::Point2::new(point.x, point.y + self.y)
+    }
+
+    // 开始用世界坐标绘制
+    pub fn push(&self, ctx: &mut Context) -> GameResult {
+        let shake = self.shake_offset();
+        let offset = graphics::DrawParam::default().dest(na::Point2::new(shake.x, shake.y - self.y));
+        graphics::push_transform(ctx, Some(offset.to_matrix()));
+        graphics::apply_transformations(ctx)
+    }
+
+    // 回到屏幕坐标
+    pub fn pop(&self, ctx: &mut Context) -> GameResult {
+        graphics::pop_transform(ctx);
+        graphics::apply_transformations(ctx)
+    }
+}
@@ -0,0 +1,101 @@
+// 还债战役：连续闯关，每关得分存入钱包，每隔几关要从钱包里还一笔越来越高的欠款；
+// 还不上则本次战役结束，按时还清会推进一段剧情。还完最后一笔拿到地契即通关，
+// 可以开新周目：保留买下的升级重新开始，物品更差、危险更多，但得分有加成，欠款也相应提高
+use crate::config::GameConfig;
+use crate::prestige::Prestige;
+
+// 每次按时还款后的剧情
+const STORY_BEATS: [&str; 5] = [
+    "The landlord grunts and pockets the cash. \"Same time next month.\"",
+    "Word spreads of a miner who always pays. The bank raises your limit.",
+    "A stranger slips you a map of the deep seams.",
+    "The landlord's nephew wants the mine. Payments go up.",
+    "The deed is almost yours. One more payment...",
+];
+
+pub const NEW_GAME_PLUS_RECORD: &str = "debt_new_game_plus"; // 纪录文件里记最高周目的键
+
+// 一关结束时的结算结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum Settlement {
+    Continue,
+    PaymentMade { amount: i64, beat: &'static str },
+    PaymentMissed { amount: i64 },
+    DeedEarned { amount: i64 }, // 还清最后一笔，本周目通关
+}
+
+#[derive(Debug, Clone)]
+pub struct DebtCampaign {
+    pub payments_made: u32,
+    pub failed: bool,
+    pub completed: bool, // 已拿到地契
+    pub plus: u32,       // 第几个新周目，0 为初次
+    levels_per_payment: u32,
+    next_payment: i64,
+    payment_growth: f32,
+}
+
+impl DebtCampaign {
+    pub fn new(config: &GameConfig) -> Self {
+        DebtCampaign {
+            payments_made: 0,
+            failed: false,
+            completed: false,
+            plus: 0,
+            levels_per_payment: config.debt_levels_per_payment.max(1),
+            next_payment: config.debt_first_payment,
+            payment_growth: config.debt_payment_growth,
+        }
+    }
+
+    // 通关后开下一个周目，欠款按声望里的周目加成同比提高
+    pub fn new_game_plus(&self, config: &GameConfig) -> Self {
+        let plus = self.plus + 1;
+        let mut campaign = DebtCampaign::new(config);
+        campaign.plus = plus;
+        campaign.next_payment = (campaign.next_payment as f32 * Prestige::new_game_plus(plus).value_bonus()) as i64;
+        campaign
+    }
+
+    // 从崩溃快照恢复进行中的战役
+    pub fn restore(config: &GameConfig, payments_made: u32, plus: u32, next_payment: i64) -> Self {
+        DebtCampaign {
+            payments_made,
+            plus,
+            next_payment,
+            ..DebtCampaign::new(config)
+        }
+    }
+
+    pub fn next_payment(&self) -> i64 {
+        self.next_payment
+    }
+
+    // 距离下次还款还有几关（含当前关）
+    pub fn levels_until_due(&self, level: u32) -> u32 {
+        let n = self.levels_per_payment;
+        n - (level - 1) % n
+    }
+
+    // 一关结束（得分已存入钱包）：到期则从钱包还款
+    pub fn settle(&mut self, level: u32, wallet: &mut i64) -> Settlement {
+        if level % self.levels_per_payment != 0 {
+            return Settlement::Continue;
+        }
+
+        let amount = self.next_payment;
+        if *wallet < amount {
+            self.failed = true;
+            return Settlement::PaymentMissed { amount };
+        }
+        *wallet -= amount;
+        let beat = STORY_BEATS[(self.payments_made as usize).min(STORY_BEATS.len() - 1)];
+        self.payments_made += 1;
+        if self.payments_made as usize > STORY_BEATS.len() {
+            self.completed = true;
+            return Settlement::DeedEarned { amount };
+        }
+        self.next_payment = (amount as f32 * self.payment_growth) as i64;
+        Settlement::PaymentMade { amount, beat }
+    }
+}
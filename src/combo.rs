@@ -0,0 +1,100 @@
+// 连击：连续拉回黄金或钻石时连击数加一，结算价值按连击数递增加成；
+// 拉回石头、空钩收回或一段时间没有续上连击都会清零
+use ggez::{
+    graphics::{self, Color, Rect, Text, TextFragment},
+    nalgebra as na, Context, GameResult,
+};
+
+use crate::modifiers::ScoreModifier;
+use crate::ItemType;
+
+const MAX_MULTIPLIER: f32 = 3.0;
+const BAR_WIDTH: f32 = 120.0;
+
+#[derive(Debug, Clone, Default)]
+pub struct Combo {
+    pub count: u32,
+    remaining: u64, // 连击失效前剩余的帧数
+    window: u64,    // 每次续上连击后重新计时的帧数
+    step: f32,      // 每多一次连击增加的倍率
+}
+
+impl Combo {
+    pub fn new(window: u64, step: f32) -> Self {
+        Combo {
+            count: 0,
+            remaining: 0,
+            window,
+            step,
+        }
+    }
+
+    // 能续上连击的物品
+    fn extends(item_type: ItemType) -> bool {
+        matches!(item_type, ItemType::Gold(_) | ItemType::Diamond) || item_type.is_gem()
+    }
+
+    // 当前倍率：第一次 1 倍，之后每次加 step，封顶 3 倍
+    pub fn multiplier(&self) -> f32 {
+        (1.0 + self.step * self.count.saturating_sub(1) as f32).min(MAX_MULTIPLIER)
+    }
+
+    // 拉回物品：黄金钻石续上连击，石头和骷髅清零，其他物品不影响
+    pub fn on_catch(&mut self, item_type: ItemType) {
+        if Self::extends(item_type) {
+            self.count += 1;
+            self.remaining = self.window;
+        } else if matches!(item_type, ItemType::Rock | ItemType::Skull) {
+            self.reset();
+        }
+    }
+
+    // 空钩收回
+    pub fn on_miss(&mut self) {
+        self.reset();
+    }
+
+    fn reset(&mut self) {
+        self.count = 0;
+        self.remaining = 0;
+    }
+
+    // 每帧倒计时，到时连击清零
+    pub fn update(&mut self) {
+        if self.count == 0 {
+            return;
+        }
+        self.remaining = self.remaining.saturating_sub(1);
+        if self.remaining == 0 {
+            self.reset();
+        }
+    }
+
+    // 连击数和倍率，下方的进度条显示连击还剩多久失效
+    pub fn draw(&self, ctx: &mut Context, x: f32, y: f32) -> GameResult {
+        if self.count < 2 {
+            return Ok(());
+        }
+        let label = format!("Combo x{}  ({:.2}x)", self.count, self.multiplier());
+        let text = Text::new(TextFragment::new(label).color(Color::new(1.0, 0.55, 0.1, 1.0)).font_size(20));
+        graphics::draw(ctx, &text, graphics::DrawParam::default().dest(na::Point2::new(x, y)))?;
+
+        let frame = Rect::new(x, y + 24.0, BAR_WIDTH, 6.0);
+        graphics::rectangle(ctx, graphics::DrawParam::default().dest(frame.point()), &frame, Color::new(0.2, 0.2, 0.2, 0.8))?;
+        let fraction = self.remaining as f32 / self.window.max(1) as f32;
+        let bar = Rect::new(x, y + 24.0, BAR_WIDTH * fraction, 6.0);
+        graphics::rectangle(ctx, graphics::DrawParam::default().dest(bar.point()), &bar, Color::new(1.0, 0.55, 0.1, 1.0))?;
+        Ok(())
+    }
+}
+
+// 连击只加成黄金和钻石
+impl ScoreModifier for Combo {
+    fn apply(&self, item_type: ItemType, value: f32) -> f32 {
+        if Self::extends(item_type) {
+            value * self.multiplier()
+        } else {
+            value
+        }
+    }
+}
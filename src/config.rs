@@ -0,0 +1,426 @@
+// 游戏参数：屏幕尺寸、时长、速度、生成数量等，默认值可由配置文件覆盖
+//
+// 配置文件为每行一个 `键 = 值`，以 # 开头的行为注释，未写出的键使用默认值。
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::ai::Skill;
+use crate::difficulty::{Difficulty, Profile};
+use crate::worlds::World;
+
+pub const CONFIG_FILE: &str = "gold_miner.cfg";
+
+#[derive(Debug, Clone)]
+pub struct GameConfig {
+    pub screen_width: f32,
+    pub screen_height: f32,
+    pub miner_width: f32,
+    pub miner_height: f32,
+    pub miner_speed: f32,
+    pub carry_drag: f32, // 收钩时每单位物品重量让矿车满速降低的比例
+    pub hook_length: f32,
+    pub water_drag: f32, // 水下关卡水面以下钩子的速度倍率
+    pub max_wind: f32, // 每关随机的风力上限（每帧把钩子吹偏的像素），0 为无风
+    pub mine_depth: f32, // 矿洞的深度（世界坐标），比屏幕深时镜头跟着钩子往下走
+    pub depth_value_bonus: f32, // 矿底物品的价值加成，按深度线性增加
+    pub hook_speed: f32,
+    pub item_size: f32,
+    pub item_count: usize,
+    pub optional_objectives: bool, // 经典闯关每关随机给出附加目标
+    pub bonus_stages: bool, // 达成隐藏条件后插入秘密奖励关
+    pub bonus_stage_seconds: u64, // 奖励关的时长
+    pub ghost: bool, // 经典闯关显示本关最佳成绩的幽灵
+    pub tutorial: bool, // 第一次进入第一关时先打教学关
+    pub slot_machine: bool, // 关卡结算后可以去老虎机赌一把
+    pub puzzle_throws: u32, // 解谜模式每关能出几钩
+    pub vein_chance: f32, // 每生成一件物品时改为生成一条金银矿脉的概率
+    pub game_duration: Duration,
+    pub pendulum: bool,       // 经典摆钩：钩子自动左右摆动，按空格沿当前角度出钩；关闭时朝鼠标出钩
+    pub swing_speed: f32,     // 摆动速度（每秒弧度）
+    pub swing_arc: f32,       // 摆幅，偏离正下方的最大角度（度）
+    pub charge_throw: bool,   // 蓄力出钩（可选玩法）
+    pub charge_time: f32,     // 蓄满所需秒数
+    pub min_throw_power: f32, // 未蓄力时的出钩速度倍率
+    pub max_throw_power: f32, // 蓄满时的出钩速度倍率
+    pub stamina: bool,            // 硬核规则：出钩消耗体力
+    pub max_stamina: f32,
+    pub stamina_throw_cost: f32,  // 每次出钩消耗
+    pub stamina_regen: f32,       // 钩子空闲时每秒恢复
+    pub stamina_haul_drain: f32,  // 拖拽物品时每秒额外消耗（按物品重量加权）
+    pub cable_oil: bool,          // 深矿规则：钩索消耗缆绳油
+    pub max_cable_oil: f32,
+    pub cable_oil_per_pixel: f32, // 钩子每伸出一个像素消耗的油量
+    pub oil_can_refill: f32,      // 每个油桶补充的油量
+    pub oil_can_count: usize,     // 每局生成的油桶数量
+    pub winch_fuel: bool,         // 绞盘燃料规则：钩子来回都烧燃料，烧干后绞盘变慢
+    pub max_winch_fuel: f32,
+    pub winch_fuel_per_pixel: f32, // 钩子每移动一个像素消耗的燃料
+    pub dry_winch_factor: f32,    // 燃料烧干后绞盘的速度倍率
+    pub fuel_can_refill: f32,     // 每个油罐补充的燃料
+    pub fuel_can_count: usize,    // 每局生成的油罐数量
+    pub mystery_bag_count: usize, // 每局生成的神秘袋数量
+    pub tnt_count: usize,         // 每局生成的炸药桶数量
+    pub clock_chance: f32,        // 每局生成一个加时时钟的概率
+    pub chest_count: usize,       // 每局生成的宝箱数量，每个宝箱配一把钥匙
+    pub tnt_radius: f32,          // 炸药桶的爆炸半径
+    pub tnt_fuse_seconds: f32,    // 被波及的炸药桶引信烧多久才接着炸
+    pub mole_count: usize,        // 每局横穿矿洞的地鼠数量
+    pub mole_speed: f32,          // 地鼠每帧移动的像素
+    pub mole_diamond_chance: f32, // 地鼠叼着钻石的概率
+    pub golden_mole_interval: u32, // 每隔几关出现一只金地鼠，0 为关闭
+    pub combo_seconds: u64,       // 连击多久没续上就清零
+    pub combo_step: f32,          // 每多一次连击增加的倍率
+    pub magnet_radius: f32,       // 磁铁吸引物品的范围
+    pub magnet_pull: f32,         // 磁铁每帧把物品拉近的像素
+    pub saw_count: usize,         // 每关的锯片数量
+    pub saw_radius: f32,
+    pub conveyor_chance: f32,     // 一关出现传送带的概率
+    pub conveyor_speed: f32,      // 传送带每帧运送物品的像素
+    pub portal_chance: f32,       // 一关出现一对传送门的概率
+    pub saw_speed: f32,           // 锯片每帧移动的像素
+    pub bat_count: usize,         // 每关盘旋的蝙蝠数量，会叼走正在收回的钩子上的物品
+    pub bat_speed: f32,           // 蝙蝠每帧飞行的像素
+    pub rope_respawn_seconds: u64, // 绳子被割断后装好新钩子的秒数
+    pub dirt_density: f32,        // 矿洞里铺泥土的格子比例
+    pub dirt_hardness: u8,        // 泥土最多要凿几次才能挖穿
+    pub buried_chance: f32,       // 物品被埋在泥土里的概率
+    pub powerup_seconds: u64,     // 每隔几秒漂过一个道具，0 为不出现
+    pub powerup_speed: f32,       // 道具每帧漂过的像素
+    pub quake_seconds: u64,       // 平均每隔几秒发生一次地震，0 为不发生
+    pub rock_fall_seconds: u64,   // 平均每隔几秒掉一块落石，0 为不掉
+    pub miner_hearts: u32,        // 矿工的心，被落石砸中扣一颗
+    pub stun_seconds: u64,        // 被砸中后晕多久
+    pub boss_health: u32,         // 首领金猪要钩中几次才能抓住
+    pub boss_speed: f32,          // 金猪每帧移动的像素
+    pub difficulty: Difficulty,   // 难度，决定关卡时长、目标分和危险物的多少
+    pub director: bool,           // 自适应难度：按最近的表现在关卡之间微调难度
+    pub nudge: f32,               // 导演给本关的难度调整量（-1~1），开局时写入
+    pub new_game_plus: u32,       // 还债战役的新周目次数，开局时写入
+    pub world: Option<World>,     // 固定的世界主题，None 为按关卡轮换
+    pub drill_unlock_score: i64,  // 历史总分达到后解锁钻头
+    pub drill_depth: f32,
+    pub drill_speed: f32,
+    pub drill_cooldown_ticks: u64,
+    pub drill_value_factor: f32,  // 钻头带回的物品按此比例计分
+    pub starting_dynamite: u32,
+    pub dynamite_radius: f32,     // 抛掷炸药的爆炸半径
+    pub value_variance: f32,      // 物品价值随机浮动比例
+    pub appraiser: bool,          // 开局即拥有鉴定师升级
+    pub market_volatility: f32,   // 每关行情相对基准价的最大浮动比例，0 为关闭
+    pub debt_levels_per_payment: u32, // 还债战役：每隔几关还一次款
+    pub debt_first_payment: i64,
+    pub debt_payment_growth: f32,     // 每次还款后欠款的增长倍率
+    pub language: String,             // 界面语言，对应 lang/<语言>.txt
+    pub rubber_band: bool,            // 对战时给落后一方补偿（竞技对局应关闭）
+    pub rubber_band_strength: f32,    // 差距最大时每件物品升档的概率
+    pub rubber_band_full_deficit: f32, // 分差达到多少时补偿拉满
+    pub coop_friendly_fire: bool,      // 双人合作时炸药桶会炸断队友的绳子
+    pub ai_skill: Skill,               // 电脑对手的默认等级，主菜单里可以切换
+    pub spawn_value_margin: f32,       // 布局总价值至少是目标分的多少倍
+    pub telemetry: bool,               // 匿名遥测，默认关闭
+    pub telemetry_endpoint: String,
+    pub telemetry_batch_size: u32,     // 攒够几关上传一次
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig {
+            screen_width: 800.0,
+            screen_height: 600.0,
+            miner_width: 60.0,
+            miner_height: 40.0,
+            miner_speed: 5.0,
+            carry_drag: 0.2,
+            hook_length: 1600.0,
+            water_drag: 0.6,
+            max_wind: 0.15,
+            mine_depth: 1800.0,
+            depth_value_bonus: 1.0,
+            hook_speed: 5.0,
+            item_size: 30.0,
+            item_count: 20,
+            optional_objectives: true,
+            bonus_stages: true,
+            bonus_stage_seconds: 20,
+            ghost: true,
+            tutorial: true,
+            slot_machine: true,
+            puzzle_throws: 8,
+            vein_chance: 0.05,
+            game_duration: Duration::from_secs(60), // 1分钟游戏时间
+            pendulum: true,
+            swing_speed: 2.0,
+            swing_arc: 70.0,
+            charge_throw: false,
+            charge_time: 1.0,
+            min_throw_power: 0.5,
+            max_throw_power: 2.0,
+            stamina: false,
+            max_stamina: 100.0,
+            stamina_throw_cost: 20.0,
+            stamina_regen: 5.0,
+            stamina_haul_drain: 8.0,
+            cable_oil: false,
+            max_cable_oil: 1500.0,
+            cable_oil_per_pixel: 1.0,
+            oil_can_refill: 600.0,
+            oil_can_count: 2,
+            winch_fuel: false,
+            max_winch_fuel: 100.0,
+            winch_fuel_per_pixel: 0.01,
+            dry_winch_factor: 0.2,
+            fuel_can_refill: 50.0,
+            fuel_can_count: 2,
+            mystery_bag_count: 2,
+            tnt_count: 1,
+            clock_chance: 0.4,
+            chest_count: 1,
+            tnt_radius: 90.0,
+            tnt_fuse_seconds: 0.4,
+            mole_count: 3,
+            mole_speed: 1.2,
+            mole_diamond_chance: 0.3,
+            golden_mole_interval: 4,
+            combo_seconds: 8,
+            combo_step: 0.25,
+            magnet_radius: 80.0,
+            magnet_pull: 1.5,
+            saw_count: 2,
+            saw_radius: 16.0,
+            conveyor_chance: 0.3,
+            conveyor_speed: 0.6,
+            portal_chance: 0.25,
+            saw_speed: 1.0,
+            bat_count: 1,
+            bat_speed: 1.5,
+            rope_respawn_seconds: 2,
+            dirt_density: 0.3,
+            dirt_hardness: 2,
+            buried_chance: 0.25,
+            powerup_seconds: 20,
+            powerup_speed: 1.2,
+            quake_seconds: 50,
+            rock_fall_seconds: 8,
+            miner_hearts: 3,
+            stun_seconds: 1,
+            boss_health: 3,
+            boss_speed: 1.5,
+            difficulty: Difficulty::Normal,
+            director: false,
+            nudge: 0.0,
+            new_game_plus: 0,
+            world: None,
+            drill_unlock_score: 3000,
+            drill_depth: 250.0,
+            drill_speed: 3.0,
+            drill_cooldown_ticks: 600,
+            drill_value_factor: 0.5,
+            starting_dynamite: 2,
+            dynamite_radius: 60.0,
+            value_variance: 0.2,
+            appraiser: false,
+            market_volatility: 0.3,
+            debt_levels_per_payment: 3,
+            debt_first_payment: 1500,
+            debt_payment_growth: 1.5,
+            language: "en".to_string(),
+            rubber_band: true,
+            rubber_band_strength: 0.3,
+            rubber_band_full_deficit: 1000.0,
+            coop_friendly_fire: true,
+            ai_skill: Skill::Normal,
+            spawn_value_margin: 1.5,
+            telemetry: false,
+            telemetry_endpoint: "http://localhost:8080/telemetry".to_string(),
+            telemetry_batch_size: 5,
+        }
+    }
+}
+
+impl GameConfig {
+    // 本关生效的难度参数：所选难度再叠加导演的调整和新周目的加难
+    pub fn profile(&self) -> Profile {
+        self.difficulty.profile().nudged(self.nudge).new_game_plus(self.new_game_plus)
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        let mut config = GameConfig::default();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line.split_once('=').ok_or_else(|| invalid(number, "缺少 ="))?;
+            config.set(key.trim(), value.trim()).map_err(|msg| invalid(number, msg))?;
+        }
+        Ok(config)
+    }
+
+    // 有配置文件就读取，否则使用默认值；文件有误时一并返回提示，由调用方显示给玩家
+    pub fn load_or_default() -> (Self, Option<String>) {
+        match GameConfig::load(Path::new(CONFIG_FILE)) {
+            Ok(config) => (config, None),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => (GameConfig::default(), None),
+            Err(err) => {
+                let warning = format!("Invalid {}, using defaults: {}", CONFIG_FILE, err);
+                (GameConfig::default(), Some(warning))
+            }
+        }
+    }
+
+    // 把一个参数写回配置文件：已有这一行就替换，否则追加在末尾，其余行原样保留
+    pub fn save_setting(key: &str, value: &str) -> io::Result<()> {
+        let path = Path::new(CONFIG_FILE);
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err),
+        };
+        let setting = format!("{} = {}", key, value);
+        let mut found = false;
+        let mut lines: Vec<String> = text
+            .lines()
+            .map(|line| match line.split_once('=') {
+                Some((name, _)) if name.trim() == key && !line.trim_start().starts_with('#') => {
+                    found = true;
+                    setting.clone()
+                }
+                _ => line.to_string(),
+            })
+            .collect();
+        if !found {
+            lines.push(setting);
+        }
+        fs::write(path, lines.join("\n") + "\n")
+    }
+
+    // 按键名设置一个参数
+    fn set(&mut self, key: &str, value: &str) -> Result<(), &'static str> {
+        match key {
+            "screen_width" => self.screen_width = parse(value)?,
+            "screen_height" => self.screen_height = parse(value)?,
+            "miner_width" => self.miner_width = parse(value)?,
+            "miner_height" => self.miner_height = parse(value)?,
+            "miner_speed" => self.miner_speed = parse(value)?,
+            "carry_drag" => self.carry_drag = parse(value)?,
+            "hook_length" => self.hook_length = parse(value)?,
+            "water_drag" => self.water_drag = parse(value)?,
+            "max_wind" => self.max_wind = parse(value)?,
+            "mine_depth" => self.mine_depth = parse(value)?,
+            "depth_value_bonus" => self.depth_value_bonus = parse(value)?,
+            "hook_speed" => self.hook_speed = parse(value)?,
+            "item_size" => self.item_size = parse(value)?,
+            "item_count" => self.item_count = parse(value)?,
+            "optional_objectives" => self.optional_objectives = parse(value)?,
+            "bonus_stages" => self.bonus_stages = parse(value)?,
+            "bonus_stage_seconds" => self.bonus_stage_seconds = parse(value)?,
+            "ghost" => self.ghost = parse(value)?,
+            "tutorial" => self.tutorial = parse(value)?,
+            "slot_machine" => self.slot_machine = parse(value)?,
+            "puzzle_throws" => self.puzzle_throws = parse(value)?,
+            "vein_chance" => self.vein_chance = parse(value)?,
+            "game_duration" => self.game_duration = Duration::from_secs(parse(value)?),
+            "pendulum" => self.pendulum = parse(value)?,
+            "swing_speed" => self.swing_speed = parse(value)?,
+            "swing_arc" => self.swing_arc = parse(value)?,
+            "charge_throw" => self.charge_throw = parse(value)?,
+            "charge_time" => self.charge_time = parse(value)?,
+            "min_throw_power" => self.min_throw_power = parse(value)?,
+            "max_throw_power" => self.max_throw_power = parse(value)?,
+            "stamina" => self.stamina = parse(value)?,
+            "max_stamina" => self.max_stamina = parse(value)?,
+            "stamina_throw_cost" => self.stamina_throw_cost = parse(value)?,
+            "stamina_regen" => self.stamina_regen = parse(value)?,
+            "stamina_haul_drain" => self.stamina_haul_drain = parse(value)?,
+            "cable_oil" => self.cable_oil = parse(value)?,
+            "max_cable_oil" => self.max_cable_oil = parse(value)?,
+            "cable_oil_per_pixel" => self.cable_oil_per_pixel = parse(value)?,
+            "oil_can_refill" => self.oil_can_refill = parse(value)?,
+            "oil_can_count" => self.oil_can_count = parse(value)?,
+            "winch_fuel" => self.winch_fuel = parse(value)?,
+            "max_winch_fuel" => self.max_winch_fuel = parse(value)?,
+            "winch_fuel_per_pixel" => self.winch_fuel_per_pixel = parse(value)?,
+            "dry_winch_factor" => self.dry_winch_factor = parse(value)?,
+            "fuel_can_refill" => self.fuel_can_refill = parse(value)?,
+            "fuel_can_count" => self.fuel_can_count = parse(value)?,
+            "mystery_bag_count" => self.mystery_bag_count = parse(value)?,
+            "tnt_count" => self.tnt_count = parse(value)?,
+            "clock_chance" => self.clock_chance = parse(value)?,
+            "chest_count" => self.chest_count = parse(value)?,
+            "tnt_radius" => self.tnt_radius = parse(value)?,
+            "tnt_fuse_seconds" => self.tnt_fuse_seconds = parse(value)?,
+            "mole_count" => self.mole_count = parse(value)?,
+            "mole_speed" => self.mole_speed = parse(value)?,
+            "mole_diamond_chance" => self.mole_diamond_chance = parse(value)?,
+            "golden_mole_interval" => self.golden_mole_interval = parse(value)?,
+            "combo_seconds" => self.combo_seconds = parse(value)?,
+            "combo_step" => self.combo_step = parse(value)?,
+            "magnet_radius" => self.magnet_radius = parse(value)?,
+            "magnet_pull" => self.magnet_pull = parse(value)?,
+            "saw_count" => self.saw_count = parse(value)?,
+            "saw_radius" => self.saw_radius = parse(value)?,
+            "conveyor_chance" => self.conveyor_chance = parse(value)?,
+            "conveyor_speed" => self.conveyor_speed = parse(value)?,
+            "portal_chance" => self.portal_chance = parse(value)?,
+            "saw_speed" => self.saw_speed = parse(value)?,
+            "bat_count" => self.bat_count = parse(value)?,
+            "bat_speed" => self.bat_speed = parse(value)?,
+            "rope_respawn_seconds" => self.rope_respawn_seconds = parse(value)?,
+            "dirt_density" => self.dirt_density = parse(value)?,
+            "dirt_hardness" => self.dirt_hardness = parse(value)?,
+            "buried_chance" => self.buried_chance = parse(value)?,
+            "powerup_seconds" => self.powerup_seconds = parse(value)?,
+            "powerup_speed" => self.powerup_speed = parse(value)?,
+            "quake_seconds" => self.quake_seconds = parse(value)?,
+            "rock_fall_seconds" => self.rock_fall_seconds = parse(value)?,
+            "miner_hearts" => self.miner_hearts = parse(value)?,
+            "stun_seconds" => self.stun_seconds = parse(value)?,
+            "boss_health" => self.boss_health = parse(value)?,
+            "boss_speed" => self.boss_speed = parse(value)?,
+            "difficulty" => self.difficulty = Difficulty::from_name(value).ok_or("未知的难度")?,
+            "director" => self.director = parse(value)?,
+            "world" => {
+                self.world = match value {
+                    "auto" => None,
+                    name => Some(World::from_name(name).ok_or("未知的世界主题")?),
+                }
+            }
+            "drill_unlock_score" => self.drill_unlock_score = parse(value)?,
+            "drill_depth" => self.drill_depth = parse(value)?,
+            "drill_speed" => self.drill_speed = parse(value)?,
+            "drill_cooldown_ticks" => self.drill_cooldown_ticks = parse(value)?,
+            "drill_value_factor" => self.drill_value_factor = parse(value)?,
+            "starting_dynamite" => self.starting_dynamite = parse(value)?,
+            "dynamite_radius" => self.dynamite_radius = parse(value)?,
+            "value_variance" => self.value_variance = parse(value)?,
+            "appraiser" => self.appraiser = parse(value)?,
+            "market_volatility" => self.market_volatility = parse(value)?,
+            "debt_levels_per_payment" => self.debt_levels_per_payment = parse(value)?,
+            "debt_first_payment" => self.debt_first_payment = parse(value)?,
+            "debt_payment_growth" => self.debt_payment_growth = parse(value)?,
+            "language" => self.language = value.to_string(),
+            "rubber_band" => self.rubber_band = parse(value)?,
+            "rubber_band_strength" => self.rubber_band_strength = parse(value)?,
+            "rubber_band_full_deficit" => self.rubber_band_full_deficit = parse(value)?,
+            "coop_friendly_fire" => self.coop_friendly_fire = parse(value)?,
+            "ai_skill" => self.ai_skill = Skill::from_name(value).ok_or("未知的电脑对手等级")?,
+            "spawn_value_margin" => self.spawn_value_margin = parse(value)?,
+            "telemetry" => self.telemetry = parse(value)?,
+            "telemetry_endpoint" => self.telemetry_endpoint = value.to_string(),
+            "telemetry_batch_size" => self.telemetry_batch_size = parse(value)?,
+            _ => return Err("未知的配置项"),
+        }
+        Ok(())
+    }
+}
+
+fn parse<T: std::str::FromStr>(value: &str) -> Result<T, &'static str> {
+    value.parse().map_err(|_| "数值格式错误")
+}
+
+fn invalid(line: usize, msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("第 {} 行: {}", line + 1, msg))
+}
@@ -0,0 +1,128 @@
+// 合约：还债战役每关开始前从模板随机生成三份风险/回报不同的附加赌约，
+// 玩家可以选一份或都不接，关卡结束时由附加目标框架判定成败
+use ggez::{
+    graphics::{self, Color, Rect},
+    Context, GameResult,
+};
+use rand::Rng;
+
+use crate::objectives::{Objective, ObjectiveKind};
+use crate::{draw_centered_text, BagSize, ItemType};
+
+const OFFER_COUNT: usize = 3;
+
+// 合约完成后的回报
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Payout {
+    Money(i64),
+    ShopDiscount(f32), // 下一次商店的价格倍率
+    Dynamite(u32),
+}
+
+#[derive(Debug, Clone)]
+pub struct Contract {
+    pub objective: Objective,
+    pub payout: Payout,
+    pub penalty: i64, // 失败时要赔的钱
+}
+
+impl Contract {
+    pub fn terms(&self) -> String {
+        let payout = match self.payout {
+            Payout::Money(amount) => format!("${}", amount),
+            Payout::ShopDiscount(factor) => format!("{}% off next shop", ((1.0 - factor) * 100.0).round()),
+            Payout::Dynamite(count) => format!("{} dynamite", count),
+        };
+        if self.penalty > 0 {
+            format!("{} -> {}, fail: -${}", self.objective.description(), payout, self.penalty)
+        } else {
+            format!("{} -> {}", self.objective.description(), payout)
+        }
+    }
+}
+
+// 按模板生成一份合约，要求随关卡提高
+fn from_template(template: usize, level: u32, rng: &mut impl Rng) -> Contract {
+    let level = level as i64;
+    match template {
+        0 => Contract {
+            objective: Objective::new(ObjectiveKind::ReachScore { amount: (500 + 250 * level) as i32 }, 0),
+            payout: Payout::ShopDiscount(0.5),
+            penalty: 0,
+        },
+        1 => {
+            let count = rng.gen_range(1..=2) + (level as u32) / 3;
+            Contract {
+                objective: Objective::new(ObjectiveKind::Collect { item_type: ItemType::Diamond, count }, 0),
+                payout: Payout::Money(300 * count as i64),
+                penalty: 150,
+            }
+        }
+        2 => Contract {
+            objective: Objective::new(ObjectiveKind::Avoid { item_type: ItemType::Rock }, 0),
+            payout: Payout::Dynamite(2),
+            penalty: 100,
+        },
+        _ => {
+            let count = rng.gen_range(3..=5) + level as u32;
+            Contract {
+                objective: Objective::new(ObjectiveKind::Collect { item_type: ItemType::Gold(BagSize::Medium), count }, 0),
+                payout: Payout::Money(80 * count as i64),
+                penalty: 50 * level,
+            }
+        }
+    }
+}
+
+const TEMPLATE_COUNT: usize = 4;
+
+// 关卡开始前的合约选择
+#[derive(Debug, Clone)]
+pub struct ContractOffer {
+    pub contracts: Vec<Contract>,
+    pub selected: usize, // 等于 contracts.len() 表示不接
+}
+
+impl ContractOffer {
+    pub fn roll(level: u32, rng: &mut impl Rng) -> Self {
+        let templates = rand::seq::index::sample(rng, TEMPLATE_COUNT, OFFER_COUNT);
+        let contracts = templates.iter().map(|t| from_template(t, level, rng)).collect();
+        ContractOffer { contracts, selected: 0 }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1).min(self.contracts.len());
+    }
+
+    // 确认选择，None 表示不接合约
+    pub fn accept(self) -> Option<Contract> {
+        self.contracts.into_iter().nth(self.selected)
+    }
+
+    pub fn draw(&self, ctx: &mut Context, screen_w: f32, screen_h: f32) -> GameResult {
+        let panel = Rect::new(40.0, screen_h / 2.0 - 130.0, screen_w - 80.0, 260.0);
+        graphics::rectangle(
+            ctx,
+            graphics::DrawParam::default().dest(panel.point()),
+            &panel,
+            Color::new(0.05, 0.05, 0.1, 0.92),
+        )?;
+        let gold = Color::new(1.0, 0.84, 0.0, 1.0);
+        draw_centered_text(ctx, screen_w, "Pick a contract", 28.0, gold, panel.y + 15.0)?;
+
+        let lines = self
+            .contracts
+            .iter()
+            .map(Contract::terms)
+            .chain(std::iter::once("No contract".to_string()));
+        for (i, line) in lines.enumerate() {
+            let color = if i == self.selected { gold } else { Color::WHITE };
+            draw_centered_text(ctx, screen_w, &line, 18.0, color, panel.y + 70.0 + i as f32 * 36.0)?;
+        }
+        Ok(())
+    }
+}
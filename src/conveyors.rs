@@ -0,0 +1,132 @@
+// 传送带：部分关卡里横着几条传送带，放在上面的物品会被慢慢运走，出钩时要算好提前量。
+// 有的传送带首尾相接，物品走到头从另一头绕回来；有的走到头物品就掉下去。
+// 位置、方向和速度按种子生成，回放时完全一致
+use ggez::{
+    graphics::{self, Color, Rect},
+    nalgebra as na, Context, GameResult,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::config::GameConfig;
+use crate::parallel;
+use crate::Item;
+
+const BELT_HEIGHT: f32 = 8.0;
+const MIN_WIDTH: f32 = 220.0;
+const MAX_WIDTH: f32 = 420.0;
+const MAX_BELTS: usize = 2;
+const ON_BELT_GAP: f32 = 2.0; // 物品底部离带面不超过这个距离就算在带上
+const STRIPE_SPACING: f32 = 16.0; // 带面上表示方向的条纹间距
+
+#[derive(Debug, Clone)]
+pub struct Belt {
+    pub rect: Rect,
+    speed: f32, // 每帧移动的像素，负数向左
+    wrap: bool, // 首尾相接，走到头从另一头绕回来
+    phase: f32, // 条纹滚动的位置，只用于绘制
+}
+
+impl Belt {
+    // 物品是否放在这条带上
+    fn carries(&self, item: &Item) -> bool {
+        let bottom = item.position.y + item.size() / 2.0;
+        !item.collected
+            && !item.falling
+            && item.position.x >= self.rect.x
+            && item.position.x <= self.rect.x + self.rect.w
+            && (bottom - self.rect.y).abs() <= ON_BELT_GAP
+    }
+}
+
+// 本关的全部传送带
+#[derive(Debug, Clone, Default)]
+pub struct Conveyors {
+    pub belts: Vec<Belt>,
+}
+
+impl Conveyors {
+    // 按种子决定本关有没有传送带，并把压在带子附近的物品摆到带面上
+    pub fn roll(seed: u64, config: &GameConfig, items: &mut [Item]) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed.wrapping_add(0x62656c74));
+        if config.conveyor_chance <= 0.0 || rng.gen::<f32>() >= config.conveyor_chance {
+            return Conveyors::default();
+        }
+        let count = rng.gen_range(1..=MAX_BELTS);
+        let belts: Vec<Belt> = (0..count)
+            .map(|_| {
+                let width = rng.gen_range(MIN_WIDTH..MAX_WIDTH).min(config.screen_width);
+                let x = rng.gen_range(0.0..=config.screen_width - width);
+                let y = rng.gen_range(config.mine_depth * 0.35..config.mine_depth * 0.85);
+                let speed = config.conveyor_speed * rng.gen_range(0.8..1.2);
+                Belt {
+                    rect: Rect::new(x, y, width, BELT_HEIGHT),
+                    speed: if rng.gen_bool(0.5) { speed } else { -speed },
+                    wrap: rng.gen_bool(0.5),
+                    phase: 0.0,
+                }
+            })
+            .collect();
+
+        for item in items.iter_mut().filter(|item| !item.collected) {
+            let half = item.size() / 2.0;
+            if let Some(belt) = belts.iter().find(|belt| {
+                item.position.x >= belt.rect.x
+                    && item.position.x <= belt.rect.x + belt.rect.w
+                    && (item.position.y + half - belt.rect.y).abs() < item.size()
+            }) {
+                item.position.y = belt.rect.y - half;
+                item.resting_on = None;
+            }
+        }
+        Conveyors { belts }
+    }
+
+    // 每帧：带上的物品跟着走，走到头绕回来或掉下去。被运走的物品标记为 drifting
+    pub fn update(&mut self, items: &mut [Item]) {
+        for belt in self.belts.iter_mut() {
+            belt.phase = (belt.phase + belt.speed).rem_euclid(STRIPE_SPACING);
+            let (left, right) = (belt.rect.x, belt.rect.x + belt.rect.w);
+            let belt = &*belt;
+            parallel::for_each_mut(items, |_, item| {
+                if !belt.carries(item) {
+                    return;
+                }
+                item.position.x += belt.speed;
+                if item.position.x < left || item.position.x > right {
+                    if belt.wrap {
+                        item.position.x += if item.position.x < left { belt.rect.w } else { -belt.rect.w };
+                    } else {
+                        item.falling = true;
+                    }
+                }
+                item.drifting = true;
+            });
+        }
+    }
+
+    pub fn draw(&self, ctx: &mut Context) -> GameResult {
+        for belt in &self.belts {
+            graphics::rectangle(
+                ctx,
+                graphics::DrawParam::default().dest(belt.rect.point()),
+                &belt.rect,
+                Color::new(0.25, 0.25, 0.28, 1.0),
+            )?;
+            // 滚动的条纹表示传送方向
+            let mut x = belt.rect.x + belt.phase;
+            while x < belt.rect.x + belt.rect.w {
+                let top = na::Point2::new(x, belt.rect.y + 1.0);
+                let bottom = na::Point2::new(x, belt.rect.y + belt.rect.h - 1.0);
+                graphics::line(ctx, graphics::DrawParam::default(), &[top, bottom], 2.0, Color::new(0.5, 0.5, 0.55, 1.0))?;
+                x += STRIPE_SPACING;
+            }
+            // 两端的滚筒，首尾相接的带子滚筒为绿色
+            let roller_color = if belt.wrap { Color::new(0.3, 0.7, 0.3, 1.0) } else { Color::new(0.7, 0.55, 0.2, 1.0) };
+            for end in [belt.rect.x - 5.0, belt.rect.x + belt.rect.w - 5.0] {
+                let roller = Rect::new(end, belt.rect.y - 1.0, 10.0, belt.rect.h + 2.0);
+                graphics::rectangle(ctx, graphics::DrawParam::default().dest(roller.point()), &roller, roller_color)?;
+            }
+        }
+        Ok(())
+    }
+}
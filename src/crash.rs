@@ -0,0 +1,160 @@
+// 崩溃报告：panic 时把错误信息、调用栈和当前这局的快照写到用户数据目录，
+// 下次启动时询问玩家是否从快照继续。快照是截至崩溃前的回放（文件头里带着升级、剧情关卡和周目）
+// 加上回放管不到的整局进度（钱包、还债战役），继续时恢复进度再重新模拟到那一帧。
+// 报告写没写成记在一个提示文件里，下次启动时显示在主菜单上
+use std::backtrace::Backtrace;
+use std::fs;
+use std::io::{self, Write};
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::campaign::DebtCampaign;
+use crate::config::GameConfig;
+use crate::replay::Replay;
+use crate::storage;
+
+const CRASH_DIR: &str = "crashes";
+const RESUME_FILE: &str = "resume.gmr";
+const RUN_FILE: &str = "resume_run.txt";
+const NOTICE_FILE: &str = "notice.txt";
+
+// 回放之外的整局进度
+#[derive(Debug, Clone, Default)]
+pub struct RunState {
+    pub wallet: i64,
+    pub campaign: Option<DebtCampaign>,
+}
+
+impl RunState {
+    // 每行一个 `键 = 值`
+    fn save(&self, path: &Path) -> io::Result<()> {
+        let mut out = fs::File::create(path)?;
+        writeln!(out, "wallet = {}", self.wallet)?;
+        if let Some(campaign) = &self.campaign {
+            writeln!(out, "campaign_payments = {}", campaign.payments_made)?;
+            writeln!(out, "campaign_plus = {}", campaign.plus)?;
+            writeln!(out, "campaign_next_payment = {}", campaign.next_payment())?;
+        }
+        Ok(())
+    }
+
+    // 文件不存在或某行损坏时按没有进度处理
+    fn load(path: &Path, config: &GameConfig) -> Self {
+        let text = fs::read_to_string(path).unwrap_or_default();
+        let mut wallet = 0;
+        let mut campaign: (Option<u32>, Option<u32>, Option<i64>) = (None, None, None);
+        for (key, value) in text.lines().filter_map(|line| line.split_once('=')) {
+            let value = value.trim();
+            match key.trim() {
+                "wallet" => wallet = value.parse().unwrap_or(0),
+                "campaign_payments" => campaign.0 = value.parse().ok(),
+                "campaign_plus" => campaign.1 = value.parse().ok(),
+                "campaign_next_payment" => campaign.2 = value.parse().ok(),
+                _ => (),
+            }
+        }
+        let campaign = match campaign {
+            (Some(payments), Some(plus), Some(next_payment)) => {
+                Some(DebtCampaign::restore(config, payments, plus, next_payment))
+            }
+            _ => None,
+        };
+        RunState { wallet, campaign }
+    }
+}
+
+// 当前这局的快照，游戏循环定期更新
+#[derive(Debug, Clone)]
+pub struct RunSnapshot {
+    pub score: i32,
+    pub replay: Replay,
+    pub run: RunState,
+}
+
+static SNAPSHOT: Mutex<Option<RunSnapshot>> = Mutex::new(None);
+
+// 更新快照（回放的时长即当前帧）
+pub fn record_snapshot(tick: u64, score: i32, recording: &Replay, run: RunState) {
+    let mut replay = recording.clone();
+    replay.header.duration = tick;
+    if let Ok(mut snapshot) = SNAPSHOT.lock() {
+        *snapshot = Some(RunSnapshot { score, replay, run });
+    }
+}
+
+// 离开对局（回到菜单、结束）时清除快照
+pub fn clear_snapshot() {
+    if let Ok(mut snapshot) = SNAPSHOT.lock() {
+        *snapshot = None;
+    }
+}
+
+// 安装 panic 钩子，写完报告后仍交给默认钩子打印；报告的去向留给下次启动时提示
+pub fn install() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let notice = match write_report(&info.to_string()) {
+            Ok(path) => format!("Crash report saved to {}", path.display()),
+            Err(err) => format!("Failed to write crash report: {}", err),
+        };
+        let _ = storage::data_subdir(CRASH_DIR).and_then(|dir| fs::write(dir.join(NOTICE_FILE), notice));
+        default_hook(info);
+    }));
+}
+
+// 上次崩溃留下的提示，读过即删
+pub fn take_notice() -> Option<String> {
+    let path = storage::user_data_dir().join(CRASH_DIR).join(NOTICE_FILE);
+    let notice = fs::read_to_string(&path).ok()?;
+    let _ = fs::remove_file(path);
+    Some(notice.trim().to_string())
+}
+
+fn write_report(message: &str) -> io::Result<PathBuf> {
+    let dir = storage::data_subdir(CRASH_DIR)?;
+    let date = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let path = dir.join(format!("crash_{}.txt", date));
+    let mut out = fs::File::create(&path)?;
+    writeln!(out, "Gold Miner crash report")?;
+    writeln!(out, "date {}", date)?;
+    writeln!(out, "version {}", env!("CARGO_PKG_VERSION"))?;
+    writeln!(out, "panic {}", message)?;
+
+    // panic 可能发生在持锁期间，锁中毒时仍然读取快照
+    let snapshot = match SNAPSHOT.lock() {
+        Ok(snapshot) => snapshot.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    };
+    if let Some(snapshot) = snapshot {
+        let header = &snapshot.replay.header;
+        writeln!(out, "seed {}", header.seed)?;
+        writeln!(out, "level {}", header.level)?;
+        writeln!(out, "tick {}", header.duration)?;
+        writeln!(out, "score {}", snapshot.score)?;
+        writeln!(out, "inputs {}", snapshot.replay.inputs.len())?;
+        snapshot.replay.save(&dir.join(RESUME_FILE))?;
+        snapshot.run.save(&dir.join(RUN_FILE))?;
+    }
+
+    writeln!(out, "\nbacktrace:\n{}", Backtrace::force_capture())?;
+    Ok(path)
+}
+
+// 上次崩溃留下的快照
+pub fn pending_resume(config: &GameConfig) -> Option<(Replay, RunState)> {
+    let dir = storage::user_data_dir().join(CRASH_DIR);
+    let replay = Replay::load(&dir.join(RESUME_FILE)).ok()?;
+    Some((replay, RunState::load(&dir.join(RUN_FILE), config)))
+}
+
+// 玩家处理过快照（继续或放弃）后删除
+pub fn discard_resume() {
+    let dir = storage::user_data_dir().join(CRASH_DIR);
+    let _ = fs::remove_file(dir.join(RESUME_FILE));
+    let _ = fs::remove_file(dir.join(RUN_FILE));
+}
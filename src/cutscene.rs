@@ -0,0 +1,176 @@
+// 过场动画：按脚本依次播放定时的图文幻灯片，可配背景音乐，随时可跳过
+//
+// 脚本为纯文本：
+//   music <音频路径>           可选，整段过场循环播放
+//   slide <秒数> [图片路径]    开始一张新幻灯片
+//   text <文字>                当前幻灯片的文字，可写多行
+use ggez::{
+    audio::{self, SoundSource},
+    graphics::{self, Color, Image},
+    nalgebra as na, Context, GameResult,
+};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::{draw_centered_text, TICKS_PER_SECOND};
+
+pub const CUTSCENE_DIR: &str = "cutscenes";
+
+#[derive(Debug, Clone)]
+pub struct Slide {
+    pub duration_ticks: u64,
+    pub image: Option<String>,
+    pub lines: Vec<String>,
+}
+
+pub struct Cutscene {
+    slides: Vec<Slide>,
+    music: Option<String>,
+    index: usize,
+    tick: u64,
+    images: HashMap<String, Image>,
+    source: Option<audio::Source>,
+    music_started: bool,
+}
+
+impl Cutscene {
+    // 读取过场目录下的脚本
+    pub fn load(name: &str) -> io::Result<Self> {
+        let path = Path::new(CUTSCENE_DIR).join(name);
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn parse(text: &str) -> io::Result<Self> {
+        let mut slides: Vec<Slide> = Vec::new();
+        let mut music = None;
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, rest) = line.split_once(' ').unwrap_or((line, ""));
+            let rest = rest.trim();
+            match key {
+                "music" => music = Some(rest.to_string()),
+                "slide" => {
+                    let mut parts = rest.split_whitespace();
+                    let seconds: f32 = parts
+                        .next()
+                        .and_then(|s| s.parse().ok())
+                        .ok_or_else(|| invalid("幻灯片时长无效"))?;
+                    slides.push(Slide {
+                        duration_ticks: (seconds * TICKS_PER_SECOND as f32) as u64,
+                        image: parts.next().map(str::to_string),
+                        lines: Vec::new(),
+                    });
+                }
+                "text" => slides
+                    .last_mut()
+                    .ok_or_else(|| invalid("文字必须写在幻灯片之后"))?
+                    .lines
+                    .push(rest.to_string()),
+                _ => return Err(invalid("未知的指令")),
+            }
+        }
+        Ok(Cutscene {
+            slides,
+            music,
+            index: 0,
+            tick: 0,
+            images: HashMap::new(),
+            source: None,
+            music_started: false,
+        })
+    }
+
+    // 推进一帧，返回是否播完
+    pub fn update(&mut self) -> bool {
+        if let Some(slide) = self.slides.get(self.index) {
+            self.tick += 1;
+            if self.tick >= slide.duration_ticks {
+                self.next_slide();
+            }
+        }
+        self.finished()
+    }
+
+    // 跳到下一张
+    pub fn next_slide(&mut self) {
+        self.index += 1;
+        self.tick = 0;
+    }
+
+    // 跳过整段
+    pub fn skip(&mut self) {
+        self.index = self.slides.len();
+    }
+
+    pub fn finished(&self) -> bool {
+        self.index >= self.slides.len()
+    }
+
+    pub fn draw(&mut self, ctx: &mut Context, screen_w: f32, screen_h: f32) -> GameResult {
+        // 音乐和图片在第一次绘制时加载，缺失的资源直接跳过
+        if !self.music_started {
+            self.music_started = true;
+            if let Some(path) = &self.music {
+                if let Ok(mut source) = audio::Source::new(ctx, path) {
+                    source.set_repeat(true);
+                    if source.play(ctx).is_ok() {
+                        self.source = Some(source);
+                    }
+                }
+            }
+        }
+
+        let slide = match self.slides.get(self.index) {
+            Some(slide) => slide.clone(),
+            None => return Ok(()),
+        };
+
+        // 淡入淡出
+        let fade_ticks = (TICKS_PER_SECOND as u64 / 2).min(slide.duration_ticks / 2).max(1);
+        let remaining = slide.duration_ticks.saturating_sub(self.tick);
+        let alpha = (self.tick.min(remaining).min(fade_ticks) as f32 / fade_ticks as f32).min(1.0);
+
+        if let Some(path) = &slide.image {
+            if !self.images.contains_key(path) {
+                if let Ok(image) = Image::new(ctx, path) {
+                    self.images.insert(path.clone(), image);
+                }
+            }
+            if let Some(image) = self.images.get(path) {
+                let scale = (screen_w / image.width() as f32).min(screen_h * 0.65 / image.height() as f32);
+                let x = (screen_w - image.width() as f32 * scale) / 2.0;
+                graphics::draw(
+                    ctx,
+                    image,
+                    graphics::DrawParam::default()
+                        .dest(na::Point2::new(x, 30.0))
+                        .scale(na::Vector2::new(scale, scale))
+                        .color(Color::new(1.0, 1.0, 1.0, alpha)),
+                )?;
+            }
+        }
+
+        let text_top = if slide.image.is_some() { screen_h * 0.72 } else { screen_h * 0.4 };
+        for (i, line) in slide.lines.iter().enumerate() {
+            let color = Color::new(1.0, 1.0, 1.0, alpha);
+            draw_centered_text(ctx, screen_w, line, 22.0, color, text_top + i as f32 * 30.0)?;
+        }
+        draw_centered_text(
+            ctx,
+            screen_w,
+            "Space: next   Esc: skip",
+            14.0,
+            Color::new(0.7, 0.7, 0.7, 1.0),
+            screen_h - 30.0,
+        )
+    }
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
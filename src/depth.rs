@@ -0,0 +1,85 @@
+// 按深度生成物品：稀有物品只出现在一定深度以下，越深的物品成色越好、越值钱，
+// 冒险往深处出钩才有回报
+use rand::{rngs::StdRng, Rng};
+
+use crate::config::GameConfig;
+use crate::worlds::Theme;
+use crate::{BagSize, Item, ItemType};
+
+const SURFACE: f32 = 100.0; // 物品最浅的生成位置
+const VEIN_STEP: f32 = 1.6; // 矿脉里相邻两块的间距，按物品大小计
+const VEIN_BEND: f32 = 0.6; // 矿脉每一步最多拐多少弧度
+
+pub struct DepthSpawner<'a> {
+    config: &'a GameConfig,
+    theme: &'static Theme,
+}
+
+impl<'a> DepthSpawner<'a> {
+    pub fn new(config: &'a GameConfig, theme: &'static Theme) -> Self {
+        DepthSpawner { config, theme }
+    }
+
+    // 纵坐标换算成深度比例：0 为最浅处，1 为矿底
+    pub fn depth(&self, y: f32) -> f32 {
+        let bottom = self.config.mine_depth - self.config.item_size;
+        ((y - SURFACE) / (bottom - SURFACE).max(1.0)).clamp(0.0, 1.0)
+    }
+
+    // 在矿洞里随机位置生成一件物品，按所在深度选物品种类并加成价值
+    pub fn spawn(&self, rng: &mut StdRng) -> Item {
+        let item_size = self.config.item_size;
+        let x = rng.gen_range(item_size..self.config.screen_width - item_size);
+        let y = rng.gen_range(SURFACE..self.config.mine_depth - item_size);
+        let depth = self.depth(y);
+
+        let item_type = self.theme.roll_item(rng, depth, self.config.profile().rock_weight);
+        let mut item = Item::new(item_type, x, y, item_size);
+        item.roll_value(rng, self.config.value_variance);
+        item.scale_value(1.0 + self.config.depth_value_bonus * depth);
+        item
+    }
+
+    // 奖励关：只生成金袋，越深大袋越多
+    pub fn spawn_gold(&self, rng: &mut StdRng) -> Item {
+        let item_size = self.config.item_size;
+        let x = rng.gen_range(item_size..self.config.screen_width - item_size);
+        let y = rng.gen_range(SURFACE..self.config.mine_depth - item_size);
+        let depth = self.depth(y);
+
+        let mut item = Item::new(ItemType::Gold(BagSize::roll(rng, depth)), x, y, item_size);
+        item.roll_value(rng, self.config.value_variance);
+        item.scale_value(1.0 + self.config.depth_value_bonus * depth);
+        item
+    }
+
+    // 矿脉：沿一条随机弯曲的短线排开 3 到 max 块金银，越深金子越多
+    pub fn spawn_vein(&self, rng: &mut StdRng, max: usize) -> Vec<Item> {
+        let item_size = self.config.item_size;
+        let (min_x, max_x) = (item_size, self.config.screen_width - item_size);
+        let (min_y, max_y) = (SURFACE, self.config.mine_depth - item_size);
+        let count = rng.gen_range(3..=6).min(max);
+        let mut x = rng.gen_range(min_x..max_x);
+        let mut y = rng.gen_range(min_y..max_y);
+        let mut heading = rng.gen_range(0.0..std::f32::consts::TAU);
+
+        let mut vein = Vec::with_capacity(count);
+        for _ in 0..count {
+            let depth = self.depth(y);
+            let item_type = if rng.gen::<f32>() < 0.3 + 0.5 * depth {
+                ItemType::Gold(BagSize::roll(rng, depth))
+            } else {
+                ItemType::Silver
+            };
+            let mut item = Item::new(item_type, x, y, item_size);
+            item.roll_value(rng, self.config.value_variance);
+            item.scale_value(1.0 + self.config.depth_value_bonus * depth);
+            vein.push(item);
+
+            heading += rng.gen_range(-VEIN_BEND..VEIN_BEND);
+            x = (x + heading.cos() * item_size * VEIN_STEP).clamp(min_x, max_x);
+            y = (y + heading.sin() * item_size * VEIN_STEP).clamp(min_y, max_y);
+        }
+        vein
+    }
+}
@@ -0,0 +1,123 @@
+// 调试信息：各子系统的实体数量与大致堆内存占用，用来尽早发现泄漏
+use ggez::{
+    graphics::{self, Color, Text, TextFragment},
+    nalgebra as na, Context, GameResult,
+};
+use std::mem::size_of;
+
+use crate::analysis::ThrowRecord;
+use crate::effects::{Particle, ScorePopup};
+use crate::profiler;
+use crate::replay::ReplayInput;
+use crate::{GameState, Item};
+
+const PANEL_X: f32 = 10.0;
+const PANEL_Y: f32 = 200.0;
+const LINE_HEIGHT: f32 = 16.0;
+
+// 一个子系统的统计
+#[derive(Debug, Clone)]
+pub struct DiagnosticEntry {
+    pub name: &'static str,
+    pub count: usize,
+    pub heap_bytes: usize,
+}
+
+impl DiagnosticEntry {
+    // 按 Vec 容量估算堆内存
+    fn from_vec<T>(name: &'static str, count: usize, vec: &Vec<T>) -> Self {
+        DiagnosticEntry {
+            name,
+            count,
+            heap_bytes: vec.capacity() * size_of::<T>(),
+        }
+    }
+}
+
+// 收集当前各子系统的统计
+pub fn collect(state: &GameState) -> Vec<DiagnosticEntry> {
+    let mut entries = Vec::new();
+
+    let active_items = state.items.iter().filter(|item| !item.collected).count();
+    entries.push(DiagnosticEntry::from_vec::<Item>("items", active_items, &state.items));
+
+    // 对象池为预分配，堆占用固定
+    entries.push(DiagnosticEntry {
+        name: "particles",
+        count: state.effects.particles.alive_count(),
+        heap_bytes: state.effects.particles.capacity() * size_of::<Particle>(),
+    });
+    entries.push(DiagnosticEntry {
+        name: "popups",
+        count: state.effects.popups.alive_count(),
+        heap_bytes: state.effects.popups.capacity() * size_of::<ScorePopup>(),
+    });
+
+    let background_bytes = state.background.cached_bytes();
+    entries.push(DiagnosticEntry {
+        name: "background cache",
+        count: (background_bytes > 0) as usize,
+        heap_bytes: background_bytes,
+    });
+
+    entries.push(DiagnosticEntry::from_vec::<ReplayInput>(
+        "replay inputs",
+        state.recording.inputs.len(),
+        &state.recording.inputs,
+    ));
+
+    if let Some(replay) = state.input.replay() {
+        entries.push(DiagnosticEntry::from_vec::<ReplayInput>(
+            "playback inputs",
+            replay.inputs.len(),
+            &replay.inputs,
+        ));
+    }
+
+    if let Some(analysis) = &state.analysis {
+        entries.push(DiagnosticEntry::from_vec::<ThrowRecord>(
+            "analysis throws",
+            analysis.throws.len(),
+            &analysis.throws,
+        ));
+    }
+
+    let frame = profiler::latest_frame();
+    entries.push(DiagnosticEntry::from_vec("profiler scopes", frame.scopes.len(), &frame.scopes));
+
+    entries
+}
+
+// 在调试层上绘制统计表
+pub fn draw_overlay(ctx: &mut Context, state: &GameState) -> GameResult {
+    let entries = collect(state);
+    let total: usize = entries.iter().map(|e| e.heap_bytes).sum();
+
+    let mut y = PANEL_Y;
+    for entry in &entries {
+        draw_line(ctx, &format!("{:<16} {:>6}  {:>8}", entry.name, entry.count, format_bytes(entry.heap_bytes)), y)?;
+        y += LINE_HEIGHT;
+    }
+    draw_line(ctx, &format!("{:<16} {:>6}  {:>8}", "total heap", "", format_bytes(total)), y)?;
+    y += LINE_HEIGHT;
+
+    // 已注册的插件，确认遥测之类的开关是否生效
+    draw_line(ctx, &format!("{:<16} {}", "plugins", state.plugins.names().join(", ")), y)
+}
+
+fn draw_line(ctx: &mut Context, line: &str, y: f32) -> GameResult {
+    let text = Text::new(TextFragment::new(line)
+        .color(Color::WHITE)
+        .font_size(14));
+    graphics::draw(ctx, &text, graphics::DrawParam::default().dest(na::Point2::new(PANEL_X, y)))
+}
+
+fn format_bytes(bytes: usize) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f32 / (1024.0 * 1024.0))
+    } else if bytes >= 1024 {
+        format!("{:.1} KB", bytes as f32 / 1024.0)
+    } else {
+        format!("{} B", bytes)
+    }
+}
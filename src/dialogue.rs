@@ -0,0 +1,230 @@
+// 对话框：头像、说话人、逐字显示的台词和可选的分支选项，店主和剧情角色共用
+//
+// 对话脚本为纯文本，文字均为本地化键：
+//   [节点名]                   开始一个节点
+//   speaker <说话人键>
+//   portrait <图片路径>
+//   text <台词键>
+//   choice <选项键> -> <节点名|end>
+//   next <节点名|end>          没有选项时的去向，缺省为下一个节点
+use ggez::{
+    graphics::{self, Color, Image, Rect, Text, TextFragment},
+    nalgebra as na, Context, GameResult,
+};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::locale::Strings;
+
+pub const DIALOGUE_DIR: &str = "dialogues";
+const CHARS_PER_TICK: f32 = 0.75; // 打字机速度
+const BOX_HEIGHT: f32 = 150.0;
+const PORTRAIT_SIZE: f32 = 110.0;
+
+#[derive(Debug, Clone)]
+pub struct Choice {
+    pub text: String,
+    pub target: Option<String>, // None 表示结束对话
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DialogueNode {
+    pub name: String,
+    pub speaker: String,
+    pub portrait: Option<String>,
+    pub text: String,
+    pub choices: Vec<Choice>,
+    pub next: Option<Option<String>>, // 显式指定的去向
+}
+
+pub struct DialogueBox {
+    nodes: Vec<DialogueNode>,
+    current: Option<usize>,
+    shown_chars: f32,
+    selected: usize,
+    portraits: HashMap<String, Image>,
+    pub picked: Vec<String>, // 玩家选过的选项键，供调用方处理结果
+}
+
+impl DialogueBox {
+    // 读取对话目录下的脚本
+    pub fn load(name: &str) -> io::Result<Self> {
+        let path = Path::new(DIALOGUE_DIR).join(name);
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn parse(text: &str) -> io::Result<Self> {
+        let mut nodes: Vec<DialogueNode> = Vec::new();
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                nodes.push(DialogueNode {
+                    name: name.to_string(),
+                    ..DialogueNode::default()
+                });
+                continue;
+            }
+            let node = nodes.last_mut().ok_or_else(|| invalid("内容必须写在节点之后"))?;
+            let (key, rest) = line.split_once(' ').unwrap_or((line, ""));
+            let rest = rest.trim();
+            match key {
+                "speaker" => node.speaker = rest.to_string(),
+                "portrait" => node.portrait = Some(rest.to_string()),
+                "text" => node.text = rest.to_string(),
+                "choice" => {
+                    let (text, target) = rest.split_once("->").ok_or_else(|| invalid("选项缺少 ->"))?;
+                    node.choices.push(Choice {
+                        text: text.trim().to_string(),
+                        target: parse_target(target),
+                    });
+                }
+                "next" => node.next = Some(parse_target(rest)),
+                _ => return Err(invalid("未知的指令")),
+            }
+        }
+        if nodes.is_empty() {
+            return Err(invalid("对话为空"));
+        }
+        Ok(DialogueBox {
+            nodes,
+            current: Some(0),
+            shown_chars: 0.0,
+            selected: 0,
+            portraits: HashMap::new(),
+            picked: Vec::new(),
+        })
+    }
+
+    pub fn finished(&self) -> bool {
+        self.current.is_none()
+    }
+
+    fn node(&self) -> Option<&DialogueNode> {
+        self.current.map(|i| &self.nodes[i])
+    }
+
+    // 逐字显示
+    pub fn update(&mut self) {
+        self.shown_chars += CHARS_PER_TICK;
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        if let Some(node) = self.node() {
+            self.selected = (self.selected + 1).min(node.choices.len().saturating_sub(1));
+        }
+    }
+
+    // 确认：台词未显示完时先显示全部，否则按选项或默认去向跳转
+    pub fn advance(&mut self, strings: &Strings) {
+        let node = match self.node() {
+            Some(node) => node.clone(),
+            None => return,
+        };
+        if (self.shown_chars as usize) < strings.get(&node.text).chars().count() {
+            self.shown_chars = f32::MAX;
+            return;
+        }
+
+        let target = if let Some(choice) = node.choices.get(self.selected) {
+            self.picked.push(choice.text.clone());
+            choice.target.clone()
+        } else {
+            match node.next {
+                Some(target) => target,
+                None => {
+                    let index = self.current.unwrap_or(0) + 1;
+                    self.nodes.get(index).map(|n| n.name.clone())
+                }
+            }
+        };
+        self.current = target.and_then(|name| self.nodes.iter().position(|n| n.name == name));
+        self.shown_chars = 0.0;
+        self.selected = 0;
+    }
+
+    pub fn draw(&mut self, ctx: &mut Context, strings: &Strings, screen_w: f32, screen_h: f32) -> GameResult {
+        let node = match self.node() {
+            Some(node) => node.clone(),
+            None => return Ok(()),
+        };
+
+        let panel = Rect::new(10.0, screen_h - BOX_HEIGHT - 10.0, screen_w - 20.0, BOX_HEIGHT);
+        graphics::rectangle(
+            ctx,
+            graphics::DrawParam::default().dest(panel.point()),
+            &panel,
+            Color::new(0.05, 0.05, 0.1, 0.9),
+        )?;
+
+        // 头像
+        let mut text_x = panel.x + 15.0;
+        if let Some(path) = &node.portrait {
+            if !self.portraits.contains_key(path) {
+                if let Ok(image) = Image::new(ctx, path) {
+                    self.portraits.insert(path.clone(), image);
+                }
+            }
+            if let Some(image) = self.portraits.get(path) {
+                let scale = PORTRAIT_SIZE / image.width().max(image.height()) as f32;
+                graphics::draw(
+                    ctx,
+                    image,
+                    graphics::DrawParam::default()
+                        .dest(na::Point2::new(panel.x + 20.0, panel.y + 20.0))
+                        .scale(na::Vector2::new(scale, scale)),
+                )?;
+                text_x += PORTRAIT_SIZE + 20.0;
+            }
+        }
+
+        let speaker = Text::new(TextFragment::new(strings.get(&node.speaker))
+            .color(Color::new(1.0, 0.84, 0.0, 1.0))
+            .font_size(20));
+        graphics::draw(ctx, &speaker, graphics::DrawParam::default().dest(na::Point2::new(text_x, panel.y + 12.0)))?;
+
+        // 打字机效果
+        let full = strings.get(&node.text);
+        let shown: String = full.chars().take(self.shown_chars as usize).collect();
+        let line = Text::new(TextFragment::new(shown)
+            .color(Color::WHITE)
+            .font_size(18));
+        graphics::draw(ctx, &line, graphics::DrawParam::default().dest(na::Point2::new(text_x, panel.y + 42.0)))?;
+
+        // 台词显示完才出现选项
+        if self.shown_chars as usize >= full.chars().count() {
+            for (i, choice) in node.choices.iter().enumerate() {
+                let (marker, color) = if i == self.selected {
+                    ("> ", Color::new(1.0, 0.84, 0.0, 1.0))
+                } else {
+                    ("  ", Color::WHITE)
+                };
+                let text = Text::new(TextFragment::new(format!("{}{}", marker, strings.get(&choice.text)))
+                    .color(color)
+                    .font_size(16));
+                let y = panel.y + 80.0 + i as f32 * 20.0;
+                graphics::draw(ctx, &text, graphics::DrawParam::default().dest(na::Point2::new(text_x, y)))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_target(target: &str) -> Option<String> {
+    match target.trim() {
+        "end" => None,
+        name => Some(name.to_string()),
+    }
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
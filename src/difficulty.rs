@@ -0,0 +1,138 @@
+// 难度：简单/普通/困难各对应一套参数，决定关卡时长、目标分、石头多少和危险物的密度，
+// 开启自适应难度时再按导演给的调整量微调。生成关卡时从配置里取参数，回放文件头记录难度，保证重放时布局一致
+use std::time::Duration;
+
+const NUDGE_HAZARDS: f32 = 0.3; // 导演调整量为 ±1 时危险物和石头增减的比例
+const NUDGE_UPGRADE: f32 = 0.1; // 导演调整量为 ±1 时物品升档概率的增减
+const PLUS_ROCKS: f32 = 0.3; // 还债战役每个新周目石头权重的增量
+const PLUS_HAZARDS: f32 = 0.25; // 每个新周目危险物的增量
+const PLUS_UPGRADE: f32 = 0.05; // 每个新周目物品升档概率的减量
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+// 一档难度的全部参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Profile {
+    pub time_scale: f32,    // 关卡时长的倍数
+    pub first_target: i32,  // 第一关的目标分
+    pub target_step: i32,   // 每关目标分的增量
+    pub rock_weight: f32,   // 石头在生成表里的权重倍数
+    pub hazard_scale: f32,  // 炸药桶、锯片、地鼠的数量和落石、地震频率的倍数
+    pub upgrade_bonus: f32, // 物品升一档品质的概率加成
+}
+
+impl Difficulty {
+    pub fn profile(self) -> Profile {
+        match self {
+            Difficulty::Easy => Profile {
+                time_scale: 1.25,
+                first_target: 400,
+                target_step: 350,
+                rock_weight: 0.6,
+                hazard_scale: 0.5,
+                upgrade_bonus: 0.0,
+            },
+            Difficulty::Normal => Profile {
+                time_scale: 1.0,
+                first_target: 500,
+                target_step: 450,
+                rock_weight: 1.0,
+                hazard_scale: 1.0,
+                upgrade_bonus: 0.0,
+            },
+            Difficulty::Hard => Profile {
+                time_scale: 0.8,
+                first_target: 650,
+                target_step: 550,
+                rock_weight: 1.5,
+                hazard_scale: 1.5,
+                upgrade_bonus: 0.0,
+            },
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Difficulty> {
+        match name {
+            "easy" => Some(Difficulty::Easy),
+            "normal" => Some(Difficulty::Normal),
+            "hard" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    // 主菜单里左右切换
+    pub fn harder(self) -> Difficulty {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            _ => Difficulty::Hard,
+        }
+    }
+
+    pub fn easier(self) -> Difficulty {
+        match self {
+            Difficulty::Hard => Difficulty::Normal,
+            _ => Difficulty::Easy,
+        }
+    }
+}
+
+impl Profile {
+    // 按导演的调整量微调：表现好时危险更多、好东西更少，表现差时反过来
+    pub fn nudged(self, nudge: f32) -> Profile {
+        Profile {
+            rock_weight: self.rock_weight * (1.0 + NUDGE_HAZARDS * nudge),
+            hazard_scale: self.hazard_scale * (1.0 + NUDGE_HAZARDS * nudge),
+            upgrade_bonus: self.upgrade_bonus - NUDGE_UPGRADE * nudge,
+            ..self
+        }
+    }
+
+    // 还债战役的新周目：石头更多、危险更多、好东西更少
+    pub fn new_game_plus(self, plus: u32) -> Profile {
+        let plus = plus as f32;
+        Profile {
+            rock_weight: self.rock_weight * (1.0 + PLUS_ROCKS * plus),
+            hazard_scale: self.hazard_scale * (1.0 + PLUS_HAZARDS * plus),
+            upgrade_bonus: self.upgrade_bonus - PLUS_UPGRADE * plus,
+            ..self
+        }
+    }
+
+    pub fn time_limit(&self, base: Duration) -> Duration {
+        base.mul_f32(self.time_scale)
+    }
+
+    // 危险物的数量
+    pub fn hazards(&self, count: usize) -> usize {
+        (count as f32 * self.hazard_scale).round() as usize
+    }
+
+    // 危险事件的平均间隔秒数，越难越频繁；0 仍表示关闭
+    pub fn interval(&self, seconds: u64) -> u64 {
+        if seconds == 0 {
+            return 0;
+        }
+        ((seconds as f32 / self.hazard_scale).round() as u64).max(1)
+    }
+}
@@ -0,0 +1,52 @@
+// 自适应难度导演（可选）：盯着玩家最近几关的表现——空钩多少、抓了多少石头骷髅之类的废品、
+// 超出目标分多少——在关卡之间把难度往上或往下推一点，让游戏既有挑战又不至于太难。
+// 调整量写进配置，由难度参数统一作用到物品品质和危险物的频率上，回放文件头会记下它
+use crate::events::{EventSubscriber, GameEvent};
+use crate::ItemType;
+
+const STEP: f32 = 0.25; // 每关最多调整多少
+const MISS_WEIGHT: f32 = 0.5;
+const JUNK_WEIGHT: f32 = 0.5;
+
+#[derive(Debug, Clone, Default)]
+pub struct Director {
+    nudge: f32, // -1 最宽松，1 最严苛
+    throws: u32,
+    grabs: u32,
+    junk: u32,
+}
+
+impl Director {
+    pub fn nudge(&self) -> f32 {
+        self.nudge
+    }
+
+    // 一关结束：按本关表现调整下一关的难度，再清空本关的计数
+    pub fn finish_level(&mut self, score: i32, target: Option<i32>) {
+        let margin = target.map_or(0.0, |target| score as f32 / target.max(1) as f32 - 1.0);
+        // 一钩最多算一次命中：蝙蝠抢走后又钩回来之类的重复抓取不能把空钩率压成负数
+        let hits = self.grabs.min(self.throws);
+        let miss_rate = if self.throws == 0 { 0.0 } else { (1.0 - hits as f32 / self.throws as f32).clamp(0.0, 1.0) };
+        let junk_rate = if self.grabs == 0 { 0.0 } else { self.junk as f32 / self.grabs as f32 };
+        let performance = (margin - MISS_WEIGHT * miss_rate - JUNK_WEIGHT * junk_rate).clamp(-1.0, 1.0);
+        self.nudge = (self.nudge + STEP * performance).clamp(-1.0, 1.0);
+        self.throws = 0;
+        self.grabs = 0;
+        self.junk = 0;
+    }
+}
+
+impl EventSubscriber for Director {
+    fn on_event(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::HookThrown { player: 0, .. } => self.throws += 1,
+            GameEvent::ItemGrabbed { player: 0, item_type, .. } => {
+                self.grabs += 1;
+                if matches!(item_type, ItemType::Rock | ItemType::Skull | ItemType::Bone) {
+                    self.junk += 1;
+                }
+            }
+            _ => (),
+        }
+    }
+}
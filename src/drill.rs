@@ -0,0 +1,152 @@
+// 钻头：代替出钩，垂直向下钻一段距离，沿途的物品全部带回，但只按折扣价值计分
+use ggez::{
+    graphics::{self, Color, Rect},
+    nalgebra as na, Context, GameResult,
+};
+
+use crate::config::GameConfig;
+use crate::Item;
+
+const DRILL_WIDTH: f32 = 24.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DrillState {
+    Ready,
+    Boring,
+    Returning,
+    Cooldown(u64), // 剩余冷却帧数
+}
+
+#[derive(Debug)]
+pub struct Drill {
+    pub state: DrillState,
+    pub origin: na::Point2<f32>,
+    pub depth: f32,
+    pub collected: Vec<usize>, // 钻到的物品索引
+    max_depth: f32,
+    speed: f32,
+    cooldown_ticks: u64,
+    anim_tick: u64,
+}
+
+impl Drill {
+    pub fn new(config: &GameConfig) -> Self {
+        Drill {
+            state: DrillState::Ready,
+            origin: na::Point2::new(0.0, 0.0),
+            depth: 0.0,
+            collected: Vec::new(),
+            max_depth: config.drill_depth,
+            speed: config.drill_speed,
+            cooldown_ticks: config.drill_cooldown_ticks,
+            anim_tick: 0,
+        }
+    }
+
+    // 从矿工位置开钻
+    pub fn start(&mut self, origin: na::Point2<f32>) -> bool {
+        if self.state != DrillState::Ready {
+            return false;
+        }
+        self.state = DrillState::Boring;
+        self.origin = origin;
+        self.depth = 0.0;
+        self.collected.clear();
+        true
+    }
+
+    pub fn busy(&self) -> bool {
+        matches!(self.state, DrillState::Boring | DrillState::Returning)
+    }
+
+    // 推进一帧，返回本帧刚钻到的物品；钻头回到地面时 finished 为 true
+    pub fn update(&mut self, items: &mut [Item]) -> (Vec<usize>, bool) {
+        self.anim_tick += 1;
+        let mut hits = Vec::new();
+        let mut finished = false;
+        match self.state {
+            DrillState::Ready => (),
+            DrillState::Boring => {
+                self.depth += self.speed;
+                let tip_y = self.origin.y + self.depth;
+                for (i, item) in items.iter_mut().enumerate() {
+                    let half = item.size() / 2.0;
+                    if !item.collected
+                        && (item.position.x - self.origin.x).abs() < half + DRILL_WIDTH / 2.0
+                        && item.position.y - half <= tip_y
+                    {
+                        item.collected = true;
+                        self.collected.push(i);
+                        hits.push(i);
+                    }
+                }
+                if self.depth >= self.max_depth {
+                    self.state = DrillState::Returning;
+                }
+            }
+            DrillState::Returning => {
+                // 收回比钻进快
+                self.depth -= self.speed * 2.0;
+                if self.depth <= 0.0 {
+                    self.depth = 0.0;
+                    self.state = DrillState::Cooldown(self.cooldown_ticks);
+                    finished = true;
+                }
+            }
+            DrillState::Cooldown(remaining) => {
+                self.state = if remaining <= 1 {
+                    DrillState::Ready
+                } else {
+                    DrillState::Cooldown(remaining - 1)
+                };
+            }
+        }
+        (hits, finished)
+    }
+
+    // 冷却进度（0 表示刚开始冷却，1 表示可用）
+    pub fn readiness(&self) -> f32 {
+        match self.state {
+            DrillState::Ready => 1.0,
+            DrillState::Cooldown(remaining) => 1.0 - remaining as f32 / self.cooldown_ticks.max(1) as f32,
+            _ => 0.0,
+        }
+    }
+
+    pub fn draw(&self, ctx: &mut Context, items: &[Item]) -> GameResult {
+        if !self.busy() {
+            return Ok(());
+        }
+
+        // 钻杆
+        let shaft = Rect::new(self.origin.x - 3.0, self.origin.y, 6.0, self.depth);
+        graphics::rectangle(
+            ctx,
+            graphics::DrawParam::default().dest(shaft.point()),
+            &shaft,
+            Color::new(0.45, 0.45, 0.5, 1.0),
+        )?;
+
+        // 钻头：旋转时明暗交替
+        let tip_y = self.origin.y + self.depth;
+        let shade = if self.anim_tick / 4 % 2 == 0 { 0.9 } else { 0.6 };
+        let bit = Rect::new(self.origin.x - DRILL_WIDTH / 2.0, tip_y - 8.0, DRILL_WIDTH, 16.0);
+        graphics::rectangle(
+            ctx,
+            graphics::DrawParam::default().dest(bit.point()),
+            &bit,
+            Color::new(shade, shade, shade * 0.8, 1.0),
+        )?;
+
+        // 钻到的物品挂在钻杆上跟着回来
+        for (n, &i) in self.collected.iter().enumerate() {
+            if let Some(item) = items.get(i) {
+                let size = item.size() * 0.6;
+                let y = tip_y - 16.0 - n as f32 * (size + 2.0);
+                let rect = Rect::new(self.origin.x - size / 2.0, y - size, size, size);
+                graphics::rectangle(ctx, graphics::DrawParam::default().dest(rect.point()), &rect, item.color())?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,276 @@
+// 粒子与得分飘字，底层使用预分配的对象池，避免爆炸时频繁分配内存
+use ggez::{
+    graphics::{self, spritebatch::SpriteBatch, Color, Image, Text, TextFragment},
+    nalgebra as na, Context, GameResult,
+};
+use rand::{thread_rng, Rng};
+use rayon::prelude::*;
+
+use crate::events::{EventSubscriber, GameEvent};
+use crate::parallel::PARALLEL_THRESHOLD;
+use crate::ItemType;
+
+const MAX_PARTICLES: usize = 4096;
+const MAX_POPUPS: usize = 32;
+const PARTICLE_SIZE: f32 = 4.0;
+const PARTICLE_GRAVITY: f32 = 300.0;
+const POPUP_LIFE: f32 = 1.0;
+const POPUP_RISE_SPEED: f32 = 40.0;
+const PURE_THRESHOLD: f32 = 1.12; // 成色高于此值时提示"成色极佳"
+const POOR_THRESHOLD: f32 = 0.88;
+
+// 可放入对象池的对象
+pub trait Poolable: Default {
+    fn alive(&self) -> bool;
+}
+
+// 固定容量的对象池：按环形顺序复用槽位，池满时回收最早生成的对象
+#[derive(Debug)]
+pub struct Pool<T> {
+    slots: Vec<T>,
+    cursor: usize,
+}
+
+impl<T: Poolable> Pool<T> {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Pool {
+            slots: (0..capacity).map(|_| T::default()).collect(),
+            cursor: 0,
+        }
+    }
+
+    // 取出下一个槽位，由调用者填充
+    pub fn spawn(&mut self) -> &mut T {
+        let index = self.cursor;
+        self.cursor = (self.cursor + 1) % self.slots.len();
+        &mut self.slots[index]
+    }
+
+    pub fn iter_alive(&self) -> impl Iterator<Item = &T> {
+        self.slots.iter().filter(|slot| slot.alive())
+    }
+
+    pub fn iter_alive_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.slots.iter_mut().filter(|slot| slot.alive())
+    }
+
+    // 对所有存活对象执行更新，数量多时用 rayon 并行
+    pub fn update_alive<F>(&mut self, f: F)
+    where
+        T: Send,
+        F: Fn(&mut T) + Sync + Send,
+    {
+        if self.alive_count() >= PARALLEL_THRESHOLD {
+            self.slots.par_iter_mut().filter(|slot| slot.alive()).for_each(f);
+        } else {
+            self.iter_alive_mut().for_each(f);
+        }
+    }
+
+    pub fn alive_count(&self) -> usize {
+        self.iter_alive().count()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+}
+
+// 粒子
+#[derive(Debug, Clone)]
+pub struct Particle {
+    pub position: na::Point2<f32>,
+    pub velocity: na::Vector2<f32>,
+    pub color: Color,
+    pub life: f32,
+    pub max_life: f32,
+}
+
+impl Default for Particle {
+    fn default() -> Self {
+        Particle {
+            position: na::Point2::new(0.0, 0.0),
+            velocity: na::Vector2::new(0.0, 0.0),
+            color: Color::WHITE,
+            life: 0.0,
+            max_life: 1.0,
+        }
+    }
+}
+
+impl Poolable for Particle {
+    fn alive(&self) -> bool {
+        self.life > 0.0
+    }
+}
+
+// 得分飘字
+#[derive(Debug, Clone)]
+pub struct ScorePopup {
+    pub position: na::Point2<f32>,
+    pub value: i32,
+    pub note: Option<&'static str>,
+    pub life: f32,
+}
+
+impl Default for ScorePopup {
+    fn default() -> Self {
+        ScorePopup {
+            position: na::Point2::new(0.0, 0.0),
+            value: 0,
+            note: None,
+            life: 0.0,
+        }
+    }
+}
+
+impl Poolable for ScorePopup {
+    fn alive(&self) -> bool {
+        self.life > 0.0
+    }
+}
+
+// 所有视觉特效
+pub struct Effects {
+    pub particles: Pool<Particle>,
+    pub popups: Pool<ScorePopup>,
+    // 粒子精灵批，首次绘制时创建
+    particle_batch: Option<SpriteBatch>,
+}
+
+impl Effects {
+    pub fn new() -> Self {
+        Effects {
+            particles: Pool::with_capacity(MAX_PARTICLES),
+            popups: Pool::with_capacity(MAX_POPUPS),
+            particle_batch: None,
+        }
+    }
+
+    // 在某处炸开一团粒子（纯视觉效果，不使用游戏的随机数，避免影响回放）
+    pub fn burst(&mut self, position: na::Point2<f32>, color: Color, count: usize) {
+        let mut rng = thread_rng();
+        for _ in 0..count {
+            let angle = rng.gen_range(0.0..std::f32::consts::PI * 2.0);
+            let speed = rng.gen_range(40.0..160.0);
+            let life = rng.gen_range(0.4..0.9);
+            let particle = self.particles.spawn();
+            particle.position = position;
+            particle.velocity = na::Vector2::new(angle.cos() * speed, angle.sin() * speed);
+            particle.color = color;
+            particle.life = life;
+            particle.max_life = life;
+        }
+    }
+
+    // 显示得分飘字
+    pub fn popup(&mut self, position: na::Point2<f32>, value: i32, note: Option<&'static str>) {
+        let popup = self.popups.spawn();
+        popup.position = position;
+        popup.value = value;
+        popup.note = note;
+        popup.life = POPUP_LIFE;
+    }
+
+    // 每个粒子的积分互不依赖，可以安全并行
+    pub fn update(&mut self, dt: f32) {
+        self.particles.update_alive(|particle| {
+            particle.velocity.y += PARTICLE_GRAVITY * dt;
+            particle.position += particle.velocity * dt;
+            particle.life -= dt;
+        });
+        self.popups.update_alive(|popup| {
+            popup.position.y -= POPUP_RISE_SPEED * dt;
+            popup.life -= dt;
+        });
+    }
+
+    pub fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        // 所有粒子共用一张 1x1 白色贴图，每帧把位置和颜色加进同一个精灵批，一次绘制调用画完
+        let batch = match self.particle_batch.as_mut() {
+            Some(batch) => batch,
+            None => {
+                let pixel = Image::solid(ctx, 1, Color::WHITE)?;
+                self.particle_batch.insert(SpriteBatch::new(pixel))
+            }
+        };
+        batch.clear();
+        for particle in self.particles.iter_alive() {
+            let mut color = particle.color;
+            color.a = particle.life / particle.max_life;
+            batch.add(
+                graphics::DrawParam::default()
+                    .dest(na::Point2::new(
+                        particle.position.x - PARTICLE_SIZE / 2.0,
+                        particle.position.y - PARTICLE_SIZE / 2.0,
+                    ))
+                    .scale(na::Vector2::new(PARTICLE_SIZE, PARTICLE_SIZE))
+                    .color(color),
+            );
+        }
+        graphics::draw(ctx, batch, graphics::DrawParam::default())?;
+
+        for popup in self.popups.iter_alive() {
+            // 倒扣的钱用红字
+            let color = if popup.value < 0 {
+                Color::new(0.9, 0.15, 0.15, popup.life / POPUP_LIFE)
+            } else {
+                Color::new(1.0, 0.84, 0.0, popup.life / POPUP_LIFE)
+            };
+            let text = Text::new(TextFragment::new(format!("{:+}", popup.value))
+                .color(color)
+                .font_size(20));
+            graphics::draw(ctx, &text, graphics::DrawParam::default().dest(popup.position))?;
+
+            if let Some(note) = popup.note {
+                let note_text = Text::new(TextFragment::new(note)
+                    .color(Color::new(1.0, 1.0, 1.0, popup.life / POPUP_LIFE))
+                    .font_size(14));
+                let below = na::Point2::new(popup.position.x, popup.position.y + 22.0);
+                graphics::draw(ctx, &note_text, graphics::DrawParam::default().dest(below))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl EventSubscriber for Effects {
+    fn on_event(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::ItemGrabbed { item_type, position, .. } | GameEvent::ItemDrilled { item_type, position } => {
+                self.burst(*position, item_type.color(), 16);
+            }
+            GameEvent::ItemBanked { value, purity, position, .. } => {
+                // 价值在拉回后才揭晓，成色特别好或差时附上一句评语
+                let note = if *purity >= PURE_THRESHOLD {
+                    Some("Unusually pure!")
+                } else if *purity <= POOR_THRESHOLD {
+                    Some("Poor quality")
+                } else {
+                    None
+                };
+                self.popup(*position, *value, note);
+            }
+            GameEvent::MysteryBagOpened { reward, position } => {
+                self.popup(*position, reward.money(), Some(reward.label()));
+            }
+            GameEvent::MinerHit { position, .. } => {
+                self.burst(*position, Color::new(0.45, 0.42, 0.4, 1.0), 20);
+            }
+            GameEvent::RockShattered { value, position } => {
+                self.burst(*position, ItemType::Rock.color(), 12);
+                self.popup(*position, *value, None);
+            }
+            GameEvent::RopeCut { position } => {
+                self.burst(*position, Color::new(1.0, 0.9, 0.5, 1.0), 16);
+            }
+            GameEvent::BossHit { position, .. } => {
+                self.burst(*position, Color::new(1.0, 0.84, 0.0, 1.0), 24);
+            }
+            GameEvent::ExplosionTriggered { position, radius } => {
+                self.burst(*position, Color::new(1.0, 0.5, 0.1, 1.0), (*radius as usize).max(32));
+            }
+            GameEvent::HookThrown { .. } | GameEvent::LevelCompleted { .. } => (),
+        }
+    }
+}
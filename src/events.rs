@@ -0,0 +1,97 @@
+// 游戏事件总线：玩法代码只负责发出事件，音效、粒子、成就、统计、联网等子系统订阅后各自处理
+use ggez::nalgebra as na;
+
+use crate::mystery::BagReward;
+use crate::ItemType;
+
+// 玩法事件
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    // 钩子发射
+    HookThrown {
+        player: usize, // 出钩的玩家，0 为玩家一
+        angle: f32,
+    },
+    // 钩子抓住物品
+    ItemGrabbed {
+        player: usize,
+        item_type: ItemType,
+        position: na::Point2<f32>,
+    },
+    // 钻头钻到物品（不算出钩命中）
+    ItemDrilled {
+        item_type: ItemType,
+        position: na::Point2<f32>,
+    },
+    // 物品拉回并计入分数
+    ItemBanked {
+        player: usize,
+        item_type: ItemType,
+        value: i32,
+        purity: f32, // 成色，1.0 为标准
+        position: na::Point2<f32>,
+    },
+    // 神秘袋拉回后揭晓内容
+    MysteryBagOpened {
+        reward: BagReward,
+        position: na::Point2<f32>,
+    },
+    // 钩中首领但没抓住
+    BossHit {
+        health: u32, // 剩余血量
+        position: na::Point2<f32>,
+    },
+    // 碎石增益下钩到的石头当场粉碎
+    RockShattered {
+        value: i32,
+        position: na::Point2<f32>,
+    },
+    // 锯片割断了绳子
+    RopeCut {
+        position: na::Point2<f32>,
+    },
+    // 矿工被落石砸中
+    MinerHit {
+        hearts: u32, // 剩下的心
+        position: na::Point2<f32>,
+    },
+    // 发生爆炸
+    ExplosionTriggered {
+        position: na::Point2<f32>,
+        radius: f32,
+    },
+    // 关卡结束
+    LevelCompleted {
+        level: u32,
+        score: i32,
+        ticks: u64,
+        skipped: Vec<ItemType>, // 结束时仍留在场上的物品
+    },
+}
+
+// 事件订阅者
+pub trait EventSubscriber {
+    fn on_event(&mut self, event: &GameEvent);
+}
+
+// 事件队列：一帧内发出的事件在帧末由 GameState::dispatch_events 统一分发给各订阅者
+#[derive(Default)]
+pub struct EventBus {
+    queue: Vec<GameEvent>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        EventBus::default()
+    }
+
+    // 发出事件
+    pub fn emit(&mut self, event: GameEvent) {
+        self.queue.push(event);
+    }
+
+    // 取出本帧的事件
+    pub fn drain(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.queue)
+    }
+}
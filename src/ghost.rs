@@ -0,0 +1,109 @@
+// 最佳成绩的幽灵：经典闯关每一帧记下矿车位置和主钩钩尖，刷新某关最高分时把这一局存成该关的幽灵，
+// 下次打这一关时沿用那一局的种子和难度，在同一个布局上半透明地跟着重演，方便对照自己最好的那一次怎么走位、什么时候出钩。
+// 幽灵文件放在用户数据目录的 ghosts 子目录下，每关一个：第一行 `seed 种子 难度`，之后每行一帧：`x` 或 `x 钩尖x 钩尖y`
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use ggez::{
+    graphics::{self, Color, Rect},
+    nalgebra as na, Context, GameResult,
+};
+
+use crate::difficulty::Difficulty;
+use crate::storage;
+use crate::Miner;
+
+const GHOST_DIR: &str = "ghosts";
+const GHOST_COLOR: Color = Color::new(0.85, 0.9, 1.0, 0.35);
+
+// 一帧里幽灵的样子
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    x: f32,
+    tip: Option<na::Point2<f32>>, // 钩子伸出时的钩尖
+}
+
+fn path(level: u32) -> io::Result<PathBuf> {
+    Ok(storage::data_subdir(GHOST_DIR)?.join(format!("level_{}.txt", level)))
+}
+
+// 这一局的逐帧记录
+#[derive(Debug, Default)]
+pub struct GhostRecorder {
+    frames: Vec<Frame>,
+}
+
+impl GhostRecorder {
+    pub fn record(&mut self, x: f32, tip: Option<na::Point2<f32>>) {
+        self.frames.push(Frame { x, tip });
+    }
+
+    // 存成某关的幽灵，覆盖原来的
+    pub fn save(&self, level: u32, seed: u64, difficulty: Difficulty) -> io::Result<()> {
+        let mut out = fs::File::create(path(level)?)?;
+        writeln!(out, "seed {} {}", seed, difficulty.name())?;
+        for frame in &self.frames {
+            match frame.tip {
+                Some(tip) => writeln!(out, "{:.1} {:.1} {:.1}", frame.x, tip.x, tip.y)?,
+                None => writeln!(out, "{:.1}", frame.x)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// 回放中的幽灵
+#[derive(Debug, Default)]
+pub struct Ghost {
+    pub seed: u64,              // 幽灵那一局的种子
+    pub difficulty: Difficulty, // 幽灵那一局的难度
+    frames: Vec<Frame>,
+}
+
+impl Ghost {
+    // 读取某关的幽灵，没有存过或文件损坏时返回 None
+    pub fn load(level: u32) -> Option<Ghost> {
+        let text = fs::read_to_string(path(level).ok()?).ok()?;
+        let mut lines = text.lines();
+        let (seed, difficulty) = match lines.next()?.split_whitespace().collect::<Vec<_>>()[..] {
+            ["seed", seed, difficulty] => (seed.parse().ok()?, Difficulty::from_name(difficulty)?),
+            _ => return None,
+        };
+        let frames = lines
+            .map(|line| {
+                let values = line.split_whitespace().map(|value| value.parse().ok()).collect::<Option<Vec<f32>>>()?;
+                match values[..] {
+                    [x] => Some(Frame { x, tip: None }),
+                    [x, tip_x, tip_y] => Some(Frame { x, tip: Some(na::Point2::new(tip_x, tip_y)) }),
+                    _ => None,
+                }
+            })
+            .collect::<Option<Vec<Frame>>>()?;
+        Some(Ghost {
+            seed,
+            difficulty,
+            frames,
+        })
+    }
+
+    // 画出第 tick 帧的幽灵矿车和绳子；幽灵那一局已经结束时不画
+    pub fn draw(&self, ctx: &mut Context, tick: u64, miner: &Miner) -> GameResult {
+        let frame = match self.frames.get(tick as usize) {
+            Some(frame) => frame,
+            None => return Ok(()),
+        };
+        let body = Rect::new(
+            frame.x - miner.width / 2.0,
+            miner.position.y - miner.height / 2.0,
+            miner.width,
+            miner.height,
+        );
+        graphics::rectangle(ctx, graphics::DrawParam::default().dest(body.point()), &body, GHOST_COLOR)?;
+        if let Some(tip) = frame.tip {
+            let origin = na::Point2::new(frame.x, miner.position.y);
+            graphics::line(ctx, graphics::DrawParam::default(), &[origin, tip], 2.0, GHOST_COLOR)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+// 物品的重力：下面垫着的物品被抓走或炸掉后，压在上面的物品会掉下来，
+// 落到别的物品上或矿底才停住。没被动过的物品嵌在土里，不会自己往下掉
+use crate::Item;
+
+const GRAVITY: f32 = 0.25; // 每帧增加的下落速度
+const MAX_FALL_SPEED: f32 = 8.0;
+const SUPPORT_GAP: f32 = 4.0; // 上下两件物品相隔不超过这个距离就算压在一起
+
+// 物品 below 是否托着物品 above
+fn supports(below: &Item, above: &Item) -> bool {
+    if below.collected || below.position.y <= above.position.y {
+        return false;
+    }
+    let overlap = (below.size() + above.size()) / 2.0;
+    let top = below.position.y - below.size() / 2.0;
+    let bottom = above.position.y + above.size() / 2.0;
+    (below.position.x - above.position.x).abs() < overlap && top - bottom <= SUPPORT_GAP
+}
+
+// 布局生成后记录每件物品压在哪件物品上
+pub fn link_supports(items: &mut [Item]) {
+    for i in 0..items.len() {
+        if items[i].falling {
+            continue;
+        }
+        items[i].resting_on = (0..items.len()).find(|&j| j != i && supports(&items[j], &items[i]));
+    }
+}
+
+// 每帧：失去支撑的物品开始下落，下落中的物品落到别的物品或矿底上停住
+pub fn settle(items: &mut [Item], floor: f32) {
+    for i in 0..items.len() {
+        if items[i].collected {
+            continue;
+        }
+        if let Some(j) = items[i].resting_on {
+            if !supports(&items[j], &items[i]) {
+                items[i].resting_on = None;
+                items[i].falling = true;
+            }
+        }
+        if !items[i].falling {
+            continue;
+        }
+
+        let item = &items[i];
+        let half = item.size() / 2.0;
+        let velocity = (item.velocity + GRAVITY).min(MAX_FALL_SPEED);
+        let bottom = item.position.y + half;
+        let mut landed_y = None;
+        let mut landed_on = None;
+        // 这一帧会碰到的最高的一件静止物品
+        for (k, other) in items.iter().enumerate() {
+            if k == i || other.collected || other.falling {
+                continue;
+            }
+            let top = other.position.y - other.size() / 2.0;
+            let overlap = (item.size() + other.size()) / 2.0;
+            if (other.position.x - item.position.x).abs() < overlap
+                && top >= bottom - SUPPORT_GAP
+                && top <= bottom + velocity
+                && landed_y.map_or(true, |y| top - half < y)
+            {
+                landed_y = Some(top - half);
+                landed_on = Some(k);
+            }
+        }
+        if landed_y.is_none() && bottom + velocity >= floor {
+            landed_y = Some(floor - half);
+        }
+
+        let item = &mut items[i];
+        match landed_y {
+            Some(y) => {
+                item.position.y = y;
+                item.velocity = 0.0;
+                item.falling = false;
+                item.resting_on = landed_on;
+            }
+            None => {
+                item.position.y += velocity;
+                item.velocity = velocity;
+            }
+        }
+    }
+}
@@ -0,0 +1,104 @@
+// 输入抽象：模拟每帧从 InputSource 取出命令执行，键盘、回放文件、AI、联机对手都实现同一接口
+use crate::ai::Board;
+use crate::replay::{Replay, ReplayPlayer};
+
+// 一帧内可执行的命令
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputAction {
+    // 踩住方向，矿车朝该方向加速
+    MoveLeft,
+    MoveRight,
+    // 松开方向键，矿车滑行减速
+    Coast,
+    // power 为出钩速度倍率，普通出钩为 1.0
+    Throw { angle: f32, power: f32 },
+    Drill,
+    // 向目标点抛出炸药
+    LobDynamite { x: f32, y: f32 },
+    // 用炸药炸掉正在拖回的物品
+    BlastCatch,
+}
+
+// 输入源
+pub trait InputSource {
+    // 取出本帧要执行的命令
+    fn poll(&mut self, tick: u64) -> Vec<InputAction>;
+
+    // 本地设备（键盘/手柄）产生的命令，非本地输入源忽略
+    fn push_local(&mut self, _action: InputAction) {}
+
+    // 取命令之前先看一眼场面，只有 AI 用得上
+    fn observe(&mut self, _board: Board) {}
+
+    // 输入来自回放时返回回放数据
+    fn replay(&self) -> Option<&Replay> {
+        None
+    }
+
+    // 输入源是否已经耗尽（例如回放播完）
+    fn finished(&self, _tick: u64) -> bool {
+        false
+    }
+}
+
+// 键盘输入：事件回调里只缓存命令，下一帧统一执行
+#[derive(Debug, Default)]
+pub struct KeyboardInput {
+    pending: Vec<InputAction>,
+}
+
+impl KeyboardInput {
+    pub fn new() -> Self {
+        KeyboardInput::default()
+    }
+}
+
+impl InputSource for KeyboardInput {
+    fn poll(&mut self, _tick: u64) -> Vec<InputAction> {
+        std::mem::take(&mut self.pending)
+    }
+
+    fn push_local(&mut self, action: InputAction) {
+        self.pending.push(action);
+    }
+}
+
+// 键盘上按住的方向键，按下的先后顺序排列。只看按键本身，不看模拟里矿车的状态：
+// 同一帧里按下又松开的方向键也能正确地发出滑行
+#[derive(Debug, Default)]
+pub struct Steering {
+    held: Vec<InputAction>,
+}
+
+impl Steering {
+    // 按下方向键（MoveLeft 或 MoveRight）
+    pub fn press(&mut self, direction: InputAction) {
+        self.held.retain(|held| *held != direction);
+        self.held.push(direction);
+    }
+
+    // 松开方向键，返回要发出的命令：松开的是当前方向时，还按着另一个方向就换过去，都松开了就滑行
+    pub fn release(&mut self, direction: InputAction) -> Option<InputAction> {
+        let current = self.held.last() == Some(&direction);
+        self.held.retain(|held| *held != direction);
+        if !current {
+            return None;
+        }
+        Some(self.held.last().copied().unwrap_or(InputAction::Coast))
+    }
+}
+
+// 回放文件作为输入源
+impl InputSource for ReplayPlayer {
+    fn poll(&mut self, tick: u64) -> Vec<InputAction> {
+        self.actions_at(tick)
+    }
+
+    fn replay(&self) -> Option<&Replay> {
+        Some(&self.replay)
+    }
+
+    fn finished(&self, tick: u64) -> bool {
+        ReplayPlayer::finished(self, tick)
+    }
+}
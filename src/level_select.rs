@@ -0,0 +1,139 @@
+// 选关地图：经典闯关的关卡沿一条蛇形小路排开，显示当前难度下每关的最佳星级和最高分。
+// 任一难度达标过的关卡解锁下一关，进度直接从纪录文件里的星级推算，不另存档
+use ggez::{
+    graphics::{self, Color, Rect, Text, TextFragment},
+    nalgebra as na, Context, GameResult,
+};
+
+use crate::difficulty::Difficulty;
+use crate::levels;
+use crate::records::Records;
+use crate::{draw_centered_text, draw_stars};
+
+const COLUMNS: u32 = 5;
+const ROWS: u32 = 3;
+const PAGE_SIZE: u32 = COLUMNS * ROWS; // 一屏显示的关卡数
+const NODE_SIZE: f32 = 56.0;
+const MARGIN: f32 = 120.0;
+const FIRST_ROW_Y: f32 = 160.0;
+const ROW_SPACING: f32 = 150.0;
+
+#[derive(Debug, Clone)]
+pub struct LevelSelect {
+    pub selected: u32,
+    unlocked: u32,          // 已解锁的最高关
+    difficulty: Difficulty, // 星级和最高分按这个难度显示
+}
+
+impl LevelSelect {
+    // 打开地图时默认选中最新解锁的一关
+    pub fn new(records: &Records, difficulty: Difficulty) -> Self {
+        let unlocked = highest_unlocked(records);
+        LevelSelect {
+            selected: unlocked,
+            unlocked,
+            difficulty,
+        }
+    }
+
+    // 左右沿小路前后移动，上下跨一行
+    pub fn step(&mut self, delta: i32) {
+        let target = self.selected as i32 + delta;
+        self.selected = target.clamp(1, self.unlocked as i32) as u32;
+    }
+
+    pub fn step_row(&mut self, down: bool) {
+        self.step(if down { COLUMNS as i32 } else { -(COLUMNS as i32) });
+    }
+
+    // 鼠标点到的已解锁关卡
+    pub fn level_at(&self, x: f32, y: f32, screen_w: f32) -> Option<u32> {
+        let first = self.page_start();
+        (first..first + PAGE_SIZE)
+            .filter(|&level| level <= self.unlocked)
+            .find(|&level| node_rect(level - first, screen_w).contains(na::Point2::new(x, y)))
+    }
+
+    fn page_start(&self) -> u32 {
+        (self.selected - 1) / PAGE_SIZE * PAGE_SIZE + 1
+    }
+
+    pub fn draw(&self, ctx: &mut Context, records: &Records, screen_w: f32, screen_h: f32) -> GameResult {
+        draw_centered_text(ctx, screen_w, "Select Level", 36.0, Color::new(1.0, 0.84, 0.0, 1.0), 60.0)?;
+        let first = self.page_start();
+
+        // 先画连接各关的小路，再画关卡
+        let path: Vec<na::Point2<f32>> = (0..PAGE_SIZE)
+            .map(|slot| {
+                let rect = node_rect(slot, screen_w);
+                na::Point2::new(rect.x + rect.w / 2.0, rect.y + rect.h / 2.0)
+            })
+            .collect();
+        graphics::line(ctx, graphics::DrawParam::default(), &path, 6.0, Color::new(0.55, 0.4, 0.25, 1.0))?;
+
+        for slot in 0..PAGE_SIZE {
+            let level = first + slot;
+            let rect = node_rect(slot, screen_w);
+            let locked = level > self.unlocked;
+            let color = if locked {
+                Color::new(0.3, 0.3, 0.3, 1.0)
+            } else if level == self.selected {
+                Color::new(1.0, 0.84, 0.0, 1.0)
+            } else {
+                Color::new(0.6, 0.45, 0.25, 1.0)
+            };
+            graphics::rectangle(ctx, graphics::DrawParam::default().dest(rect.point()), &rect, color)?;
+
+            let label = Text::new(TextFragment::new(if locked { "-".to_string() } else { level.to_string() })
+                .color(Color::BLACK)
+                .font_size(24));
+            let size = label.dimensions(ctx)?;
+            let center = na::Point2::new(rect.x + (rect.w - size.w) / 2.0, rect.y + (rect.h - size.h) / 2.0);
+            graphics::draw(ctx, &label, graphics::DrawParam::default().dest(center))?;
+
+            if locked {
+                continue;
+            }
+            let stars = records.best(&levels::stars_key(level, self.difficulty)).unwrap_or(0) as u32;
+            draw_stars(ctx, rect.x + rect.w / 2.0, rect.y + rect.h + 14.0, 7.0, stars)?;
+            if let Some(best) = records.best(&levels::best_key(level, self.difficulty)) {
+                let text = Text::new(TextFragment::new(format!("${}", best))
+                    .color(Color::WHITE)
+                    .font_size(14));
+                let size = text.dimensions(ctx)?;
+                let below = na::Point2::new(rect.x + (rect.w - size.w) / 2.0, rect.y + rect.h + 26.0);
+                graphics::draw(ctx, &text, graphics::DrawParam::default().dest(below))?;
+            }
+        }
+
+        let hint = "Arrows: move   Enter / click: play   T: tutorial   Esc: back";
+        draw_centered_text(ctx, screen_w, hint, 18.0, Color::new(0.7, 0.7, 0.7, 1.0), screen_h - 50.0)
+    }
+}
+
+// 从第一关起，拿到星的关卡解锁下一关
+pub fn highest_unlocked(records: &Records) -> u32 {
+    let mut level = 1;
+    let cleared = |level| {
+        [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard]
+            .into_iter()
+            .any(|difficulty| records.best(&levels::stars_key(level, difficulty)).map_or(false, |stars| stars > 0))
+    };
+    while cleared(level) {
+        level += 1;
+    }
+    level
+}
+
+// 一屏中第 slot 个关卡的位置，奇数行反向排列，连起来是一条蛇形小路
+fn node_rect(slot: u32, screen_w: f32) -> Rect {
+    let row = slot / COLUMNS;
+    let column = if row % 2 == 0 { slot % COLUMNS } else { COLUMNS - 1 - slot % COLUMNS };
+    let spacing = (screen_w - MARGIN * 2.0) / (COLUMNS - 1) as f32;
+    Rect::new(
+        MARGIN + spacing * column as f32 - NODE_SIZE / 2.0,
+        FIRST_ROW_Y + ROW_SPACING * row as f32,
+        NODE_SIZE,
+        NODE_SIZE,
+    )
+}
@@ -0,0 +1,94 @@
+// 关卡递进：每关有目标分和时限，越往后物品越密、品质越好，目标分也越高。
+// 自由模式达到目标分进入下一关，未达标则关卡失败；战役和剧情沿用各自的目标分，
+// 只按关卡号取物品密度和世界主题，保证回放只凭关卡号就能生成同样的布局
+use std::time::Duration;
+
+use crate::boss;
+use crate::config::GameConfig;
+use crate::difficulty::Difficulty;
+use crate::worlds::World;
+
+const ITEMS_PER_LEVEL: usize = 2; // 每关多生成的物品数
+const UPGRADE_CHANCE_PER_LEVEL: f32 = 0.05; // 每关物品升一档品质的概率增量
+const MAX_UPGRADE_CHANCE: f32 = 0.5;
+const TWO_STAR_RATIO: f32 = 1.5; // 得分达到目标分的几倍评两星
+const THREE_STAR_RATIO: f32 = 2.0;
+pub const MAX_STARS: u32 = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Level {
+    pub number: u32,
+    pub target: i32,
+    pub time_limit: Duration,
+    pub item_count: usize,
+    pub upgrade_chance: f32, // 生成的物品升一档品质的概率
+    pub world: World,
+    pub boss: bool, // 首领关：有一只要钩好几次的金猪
+    pub bonus: bool, // 秘密奖励关：只有金袋，没有目标分
+}
+
+impl Level {
+    // 第 number 关（从 1 开始）
+    pub fn nth(number: u32, config: &GameConfig) -> Self {
+        let step = number.max(1) - 1;
+        let profile = config.profile();
+        Level {
+            number,
+            target: profile.first_target + profile.target_step * step as i32,
+            time_limit: profile.time_limit(config.game_duration),
+            item_count: (config.item_count + ITEMS_PER_LEVEL * step as usize).min(config.item_count * 2),
+            upgrade_chance: (UPGRADE_CHANCE_PER_LEVEL * step as f32 + profile.upgrade_bonus).clamp(0.0, MAX_UPGRADE_CHANCE),
+            world: config.world.unwrap_or_else(|| World::for_level(number)),
+            boss: boss::is_boss_level(number),
+            bonus: false,
+        }
+    }
+
+    // 第 number 关之前插入的奖励关：金袋加倍，时间很短
+    pub fn bonus(number: u32, config: &GameConfig) -> Self {
+        Level {
+            time_limit: Duration::from_secs(config.bonus_stage_seconds),
+            item_count: config.item_count * 2,
+            upgrade_chance: 0.0,
+            boss: false,
+            bonus: true,
+            ..Level::nth(number, config)
+        }
+    }
+
+    pub fn time_limit_ticks(&self) -> u64 {
+        self.time_limit.as_secs() * crate::TICKS_PER_SECOND as u64
+    }
+}
+
+// 按得分超出目标分的程度评 1 到 3 星，未达标为 0 星
+pub fn stars(score: i32, target: i32) -> u32 {
+    if score < target {
+        return 0;
+    }
+    let ratio = score as f32 / target.max(1) as f32;
+    if ratio >= THREE_STAR_RATIO {
+        3
+    } else if ratio >= TWO_STAR_RATIO {
+        2
+    } else {
+        1
+    }
+}
+
+// 各关最佳星级存在纪录文件里用的键，按难度分开；普通难度沿用不带后缀的旧键
+pub fn stars_key(number: u32, difficulty: Difficulty) -> String {
+    format!("stars_level_{}{}", number, difficulty_suffix(difficulty))
+}
+
+// 各关最高分的键
+pub fn best_key(number: u32, difficulty: Difficulty) -> String {
+    format!("best_level_{}{}", number, difficulty_suffix(difficulty))
+}
+
+fn difficulty_suffix(difficulty: Difficulty) -> String {
+    match difficulty {
+        Difficulty::Normal => String::new(),
+        other => format!("_{}", other.name()),
+    }
+}
@@ -0,0 +1,32 @@
+// 本地化文本：lang/<语言>.txt 中每行一个 `键 = 文本`，找不到的键原样显示
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+pub const LANG_DIR: &str = "lang";
+
+#[derive(Debug, Clone, Default)]
+pub struct Strings {
+    table: HashMap<String, String>,
+}
+
+impl Strings {
+    // 读取某种语言的文本表
+    pub fn load(language: &str) -> io::Result<Self> {
+        let path = Path::new(LANG_DIR).join(format!("{}.txt", language));
+        let text = fs::read_to_string(path)?;
+        let table = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+            .collect();
+        Ok(Strings { table })
+    }
+
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.table.get(key).map_or(key, String::as_str)
+    }
+}
@@ -2,28 +2,408 @@ use ggez::{
     event::{self, EventHandler},
     graphics::{self, Color, DrawMode, Font, Image, Rect, Text, TextFragment},
     input::keyboard::{KeyCode, KeyMods},
+    input::mouse::MouseButton,
     nalgebra as na, Context, GameResult,
 };
-use rand::{thread_rng, Rng};
-use std::time::{Duration, Instant};
-
-// 游戏常量
-const SCREEN_WIDTH: f32 = 800.0;
-const SCREEN_HEIGHT: f32 = 600.0;
-const MINER_WIDTH: f32 = 60.0;
-const MINER_HEIGHT: f32 = 40.0;
-const HOOK_LENGTH: f32 = 200.0;
-const HOOK_SPEED: f32 = 5.0;
-const ITEM_SIZE: f32 = 30.0;
-const GAME_DURATION: Duration = Duration::from_secs(60); // 1分钟游戏时间
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+use std::path::Path;
+
+#[macro_use]
+mod profiler;
+mod achievements;
+mod ai;
+mod analysis;
+mod background;
+mod bats;
+mod bonus;
+mod boss;
+mod buffs;
+mod camera;
+mod campaign;
+mod combo;
+mod config;
+mod contracts;
+mod conveyors;
+mod crash;
+mod cutscene;
+mod depth;
+mod diagnostics;
+mod dialogue;
+mod difficulty;
+mod director;
+mod drill;
+mod effects;
+mod events;
+mod ghost;
+mod gravity;
+mod input;
+mod level_select;
+mod levels;
+mod locale;
+mod market;
+mod modes;
+mod modifiers;
+mod moles;
+mod mystery;
+mod objectives;
+mod parallel;
+mod plugin;
+mod portals;
+mod powerups;
+mod prestige;
+mod projectile;
+mod quakes;
+mod records;
+mod replay;
+mod rockfall;
+mod rubber_band;
+mod sandbox;
+mod saws;
+mod sfx;
+mod shop;
+mod slots;
+mod sim;
+mod stats;
+mod storage;
+mod story;
+mod telemetry;
+mod terrain;
+mod tutorial;
+mod versus;
+mod water;
+mod worlds;
+
+use achievements::Achievements;
+use ai::{AiInput, Board, Skill, Target};
+use analysis::ReplayAnalysis;
+use background::BackgroundCache;
+use bats::Bats;
+use bonus::Secrets;
+use boss::{Boss, BossHit};
+use buffs::{BuffKind, Buffs};
+use camera::Camera;
+use combo::Combo;
+use campaign::{DebtCampaign, Settlement};
+use config::GameConfig;
+use contracts::{Contract, ContractOffer, Payout};
+use conveyors::Conveyors;
+use crash::RunState;
+use cutscene::Cutscene;
+use depth::DepthSpawner;
+use dialogue::DialogueBox;
+use difficulty::Difficulty;
+use director::Director;
+use drill::Drill;
+use effects::Effects;
+use events::{EventBus, EventSubscriber, GameEvent};
+use ghost::{Ghost, GhostRecorder};
+use input::{InputAction, InputSource, KeyboardInput, Steering};
+use level_select::LevelSelect;
+use levels::Level;
+use locale::Strings;
+use market::Market;
+use modes::{EndlessRun, GameMode, PUZZLE_THROW_BONUS, REFILL_SECONDS, TIME_ATTACK_LIMIT_SECONDS};
+use modifiers::{DoubleScore, GemPolish, RockBook, ScoreModifier};
+use moles::MoleWarren;
+use mystery::BagReward;
+use objectives::{ObjectiveStatus, ObjectiveTracker};
+use plugin::{GamePlugin, PluginRegistry, PluginView, SpeedrunTimer};
+use portals::{Portals, PORTAL_RADIUS};
+use powerups::PowerUps;
+use prestige::Prestige;
+use projectile::DynamiteProjectile;
+use quakes::{Earthquakes, SHAKE_TICKS};
+use records::Records;
+use rockfall::RockFall;
+use sandbox::Sandbox;
+use saws::SawBlades;
+use sfx::SoundEffects;
+use shop::{Shop, Ware};
+use slots::SlotMachine;
+use stats::StatsTracker;
+use story::{StoryLevel, StoryManifest, StoryProgress, StoryRun};
+use telemetry::Telemetry;
+use terrain::{Dig, Terrain};
+use tutorial::Tutorial;
+use versus::{Rival, RIVAL};
+use water::Water;
+use worlds::World;
+use replay::{Loadout, Replay, ReplayPlayer};
+
+// 游戏常量（可调参数见 config.rs）
+const TICKS_PER_SECOND: u32 = 60; // 固定帧率，保证回放可复现
+const SPAWN_REROLLS: usize = 8; // 布局不达标时最多重抽几次，之后直接修补
+const STRENGTH_FACTOR: f32 = 1.8; // 大力水生效时拖物品的收钩速度倍率
+const BAG_STRENGTH_SECONDS: u64 = 15; // 神秘袋里的大力水持续的秒数
+const MAGNET_SECONDS: u64 = 30; // 商店磁铁在下一关开头生效的秒数
+const MAX_RICOCHETS: u32 = 1; // 反弹钩每次出钩最多在侧墙上反弹几次
+const CLAW_SIZE: f32 = 5.0; // 钩爪的半边长，碰撞和绘制用同一个尺寸
+const BIG_CLAW_SIZE: f32 = 12.0; // 买了大钩爪之后的半边长
+const HOOK_SPEED_TIERS: [f32; 4] = [1.0, 1.2, 1.4, 1.6]; // 钩速升级各档的速度倍率，0 为未升级
+const CHEST_JUNK_VALUE: i32 = 5; // 没钥匙打不开的宝箱只当烂木头卖
+const CLOCK_SECONDS: u64 = 10; // 拉回一个时钟本关加的秒数
+const SHATTER_VALUE: i32 = 5; // 碎石增益粉碎一块石头给的分
+const IDLE_ROPE_LENGTH: f32 = 30.0; // 摆钩模式下空闲时绳子的长度
+const ROPE_SEGMENTS: usize = 12; // 被风吹弯的绳子分成几段来画
+const POWERUP_SECONDS: u64 = 15; // 漂浮道具带来的增益持续的秒数
+const BURN_SECONDS: u64 = 2; // 没有防火绳时，拖着金水收钩多久绳子就被烧断
+const CART_SPEED_SCALE: f32 = 30.0; // 矿车满速（像素/秒）是 miner_speed 的多少倍
+const CART_ACCEL: f32 = 500.0; // 踩住方向时的加速度（像素/秒²）
+const CART_FRICTION: f32 = 220.0; // 松开后滑行的减速度
+const CART_SKID_BRAKE: f32 = 900.0; // 反向时打滑刹车的减速度
+const BAG_TIE_COLOR: Color = Color::new(0.9, 0.8, 0.3, 1.0); // 袋口扎绳的颜色
+const FUSE_COLOR: Color = Color::new(1.0, 0.6, 0.1, 1.0); // 炸药桶引信火花的颜色
+const MENU_ENTRIES: [&str; 13] = [
+    "Start Game",
+    "Endless",
+    "Time Attack",
+    "Daily Challenge",
+    "Puzzle",
+    "Story",
+    "Debt Campaign",
+    "Achievements",
+    "Versus",
+    "Co-op",
+    "Versus CPU",
+    "Sandbox",
+    "Open Replay",
+];
+const VERSUS_CPU_ENTRY: usize = 10; // 选中时左右键切换电脑对手的等级
+
+// 金袋的大小
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BagSize {
+    Small,
+    Medium,
+    Large,
+}
+
+// 一种金袋的价值、重量和尺寸（相对普通物品的倍数）
+struct BagSpec {
+    value: i32,
+    weight: f32,
+    size: f32,
+}
+
+impl BagSize {
+    fn spec(self) -> BagSpec {
+        match self {
+            BagSize::Small => BagSpec {
+                value: 50,
+                weight: 0.8,
+                size: 0.7,
+            },
+            BagSize::Medium => BagSpec {
+                value: 100,
+                weight: 1.5,
+                size: 1.0,
+            },
+            BagSize::Large => BagSpec {
+                value: 250,
+                weight: 3.0,
+                size: 1.6,
+            },
+        }
+    }
+
+    // 按深度抽金袋大小，越深大袋越多
+    fn roll(rng: &mut impl Rng, depth: f32) -> BagSize {
+        let pick = rng.gen::<f32>();
+        if pick < 0.3 * depth {
+            BagSize::Large
+        } else if pick < 0.6 {
+            BagSize::Medium
+        } else {
+            BagSize::Small
+        }
+    }
+}
 
 // 物品类型
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum ItemType {
-    Gold,
+    Gold(BagSize), // 大中小三种金袋
     Silver,
     Diamond,
     Rock,
+    OilCan,     // 补充缆绳油，不计分
+    MysteryBag, // 拉回后才揭晓内容
+    Mole,       // 抓到的地鼠
+    DiamondMole, // 叼着钻石的地鼠
+    GoldenMole, // 难得一见的金地鼠
+    TntBarrel,  // 钩到就爆炸，炸毁附近的物品
+    GoldenPig,  // 首领关抓到的金猪
+    Key,        // 本关打开宝箱用的钥匙，不计分
+    Chest,      // 有钥匙才值大钱，没钥匙只是一堆烂木头
+    Skull,      // 受诅咒的骷髅，拉回来倒扣钱
+    Bone,       // 几乎不值钱的骨头
+    Clock,      // 拉回后本关加时，不计分
+    FuelCan,    // 补充绞盘燃料，不计分
+    MoltenGold, // 熔岩里的金水，极值钱但会烧断绳子
+    MagnetPickup,   // 漂浮道具：拉回后获得一段时间的磁铁
+    StrengthPickup, // 漂浮道具：拉回后获得一段时间的大力水
+    DoublePickup,   // 漂浮道具：拉回后一段时间内价值翻倍
+    RockBreakerPickup, // 漂浮道具：拉回后一段时间内钩到的石头当场粉碎
+}
+
+impl ItemType {
+    // 按名称查找（关卡文件等文本格式使用）
+    fn from_name(name: &str) -> Option<ItemType> {
+        match name {
+            "SmallGold" => Some(ItemType::Gold(BagSize::Small)),
+            "Gold" => Some(ItemType::Gold(BagSize::Medium)),
+            "LargeGold" => Some(ItemType::Gold(BagSize::Large)),
+            "Silver" => Some(ItemType::Silver),
+            "Diamond" => Some(ItemType::Diamond),
+            "Rock" => Some(ItemType::Rock),
+            "OilCan" => Some(ItemType::OilCan),
+            "MysteryBag" => Some(ItemType::MysteryBag),
+            "Mole" => Some(ItemType::Mole),
+            "DiamondMole" => Some(ItemType::DiamondMole),
+            "GoldenMole" => Some(ItemType::GoldenMole),
+            "TntBarrel" => Some(ItemType::TntBarrel),
+            "GoldenPig" => Some(ItemType::GoldenPig),
+            "Key" => Some(ItemType::Key),
+            "Chest" => Some(ItemType::Chest),
+            "Skull" => Some(ItemType::Skull),
+            "Bone" => Some(ItemType::Bone),
+            "Clock" => Some(ItemType::Clock),
+            "FuelCan" => Some(ItemType::FuelCan),
+            "MoltenGold" => Some(ItemType::MoltenGold),
+            "MagnetPickup" => Some(ItemType::MagnetPickup),
+            "StrengthPickup" => Some(ItemType::StrengthPickup),
+            "DoublePickup" => Some(ItemType::DoublePickup),
+            "RockBreakerPickup" => Some(ItemType::RockBreakerPickup),
+            _ => None,
+        }
+    }
+
+    // 显示用的名称，与 from_name 对应
+    fn name(self) -> &'static str {
+        match self {
+            ItemType::Gold(BagSize::Small) => "SmallGold",
+            ItemType::Gold(BagSize::Medium) => "Gold",
+            ItemType::Gold(BagSize::Large) => "LargeGold",
+            ItemType::Silver => "Silver",
+            ItemType::Diamond => "Diamond",
+            ItemType::Rock => "Rock",
+            ItemType::OilCan => "OilCan",
+            ItemType::MysteryBag => "MysteryBag",
+            ItemType::Mole => "Mole",
+            ItemType::DiamondMole => "DiamondMole",
+            ItemType::GoldenMole => "GoldenMole",
+            ItemType::TntBarrel => "TntBarrel",
+            ItemType::GoldenPig => "GoldenPig",
+            ItemType::Key => "Key",
+            ItemType::Chest => "Chest",
+            ItemType::Skull => "Skull",
+            ItemType::Bone => "Bone",
+            ItemType::Clock => "Clock",
+            ItemType::FuelCan => "FuelCan",
+            ItemType::MoltenGold => "MoltenGold",
+            ItemType::MagnetPickup => "MagnetPickup",
+            ItemType::StrengthPickup => "StrengthPickup",
+            ItemType::DoublePickup => "DoublePickup",
+            ItemType::RockBreakerPickup => "RockBreakerPickup",
+        }
+    }
+
+    // 同一类物品（不区分金袋大小），附加目标和行情按类别计算
+    fn same_kind(self, other: ItemType) -> bool {
+        std::mem::discriminant(&self) == std::mem::discriminant(&other)
+    }
+
+    // 基础价值
+    fn base_value(self) -> i32 {
+        match self {
+            ItemType::Gold(size) => size.spec().value,
+            ItemType::Silver => 50,
+            ItemType::Diamond => 200,
+            ItemType::Rock => 10,
+            ItemType::OilCan => 0,
+            ItemType::MysteryBag => 0, // 价值开袋时才决定
+            ItemType::Mole => 20,
+            ItemType::DiamondMole => 600, // 钻石外加赏金
+            ItemType::GoldenMole => 2500, // 头奖
+            ItemType::TntBarrel => 0,
+            ItemType::GoldenPig => 3000,
+            ItemType::Key => 0,
+            ItemType::Chest => 800, // 用钥匙打开时的价值
+            ItemType::Skull => -150,
+            ItemType::Bone => 2,
+            ItemType::Clock => 0,
+            ItemType::FuelCan => 0,
+            ItemType::MoltenGold => 1200,
+            ItemType::MagnetPickup | ItemType::StrengthPickup | ItemType::DoublePickup | ItemType::RockBreakerPickup => 0,
+        }
+    }
+
+    // 宝石类物品（宝石抛光对其生效）
+    fn is_gem(self) -> bool {
+        matches!(self, ItemType::Diamond | ItemType::DiamondMole)
+    }
+
+    // 重量：拖着物品收钩时速度与重量成反比
+    fn weight(self) -> f32 {
+        match self {
+            ItemType::Gold(size) => size.spec().weight,
+            ItemType::Silver => 1.2,
+            ItemType::Diamond => 0.5, // 小而轻，收得飞快
+            ItemType::Rock => 3.0,    // 大石头拖得最慢
+            ItemType::OilCan => 1.0,
+            ItemType::MysteryBag => 0.8,
+            ItemType::Mole | ItemType::DiamondMole | ItemType::GoldenMole => 0.6,
+            ItemType::TntBarrel => 1.0,
+            ItemType::GoldenPig => 4.0, // 又大又沉
+            ItemType::Key => 0.4,
+            ItemType::Chest => 2.5,
+            ItemType::Skull => 1.0,
+            ItemType::Bone => 0.6,
+            ItemType::Clock => 0.5,
+            ItemType::FuelCan => 1.0,
+            ItemType::MoltenGold => 2.0,
+            ItemType::MagnetPickup | ItemType::StrengthPickup | ItemType::DoublePickup | ItemType::RockBreakerPickup => 0.3,
+        }
+    }
+
+    // 物品类型对应的颜色
+    fn color(self) -> Color {
+        match self {
+            ItemType::Gold(_) => Color::new(1.0, 0.84, 0.0, 1.0), // 金色
+            ItemType::Silver => Color::new(0.75, 0.75, 0.75, 1.0), // 银色
+            ItemType::Diamond => Color::new(0.0, 1.0, 1.0, 1.0), // 钻石蓝
+            ItemType::Rock => Color::new(0.5, 0.5, 0.5, 1.0), // 灰色
+            ItemType::OilCan => Color::new(0.6, 0.1, 0.6, 1.0), // 紫色油桶
+            ItemType::MysteryBag => Color::new(0.55, 0.35, 0.2, 1.0), // 麻袋色
+            ItemType::Mole => Color::new(0.45, 0.35, 0.3, 1.0),
+            ItemType::DiamondMole => Color::new(0.3, 0.7, 0.75, 1.0), // 带着钻石光泽
+            ItemType::GoldenMole => Color::new(1.0, 0.8, 0.1, 1.0),
+            ItemType::TntBarrel => Color::new(0.85, 0.1, 0.1, 1.0), // 红色炸药桶
+            ItemType::GoldenPig => Color::new(1.0, 0.75, 0.1, 1.0),
+            ItemType::Key => Color::new(0.8, 0.65, 0.3, 1.0), // 黄铜钥匙
+            ItemType::Chest => Color::new(0.4, 0.22, 0.1, 1.0), // 深色木箱
+            ItemType::Skull => Color::new(0.95, 0.95, 0.88, 1.0), // 惨白骷髅
+            ItemType::Bone => Color::new(0.8, 0.74, 0.6, 1.0),
+            ItemType::Clock => Color::new(0.3, 0.9, 0.4, 1.0), // 绿色怀表
+            ItemType::FuelCan => Color::new(0.95, 0.55, 0.1, 1.0), // 橙色油罐
+            ItemType::MoltenGold => Color::new(1.0, 0.35, 0.05, 1.0), // 通红的金水
+            ItemType::MagnetPickup => Color::new(0.8, 0.15, 0.2, 1.0), // 和增益图标同色
+            ItemType::StrengthPickup => Color::new(0.9, 0.4, 0.1, 1.0),
+            ItemType::DoublePickup => Color::new(0.95, 0.75, 0.05, 1.0),
+            ItemType::RockBreakerPickup => Color::new(0.45, 0.55, 0.7, 1.0),
+        }
+    }
+}
+
+// 影响物品成色的增益，由 GameState 按当前状态组装；按物品类型加倍的升级走结算修正
+#[derive(Debug, Clone, Copy, Default)]
+struct ValueContext {
+    luck: bool, // 幸运草：成色不低于标准
+}
+
+impl ValueContext {
+    // 不受任何增益影响，生成布局等场合使用
+    const NONE: ValueContext = ValueContext { luck: false };
 }
 
 // 物品结构体
@@ -32,43 +412,120 @@ struct Item {
     item_type: ItemType,
     position: na::Point2<f32>,
     collected: bool,
+    base_size: f32,
+    value: i32, // 生成时在基础价值上随机浮动
+    velocity: f32,             // 下落速度
+    falling: bool,             // 失去支撑正在下落
+    resting_on: Option<usize>, // 压在哪件物品上
+    drifting: bool,            // 这一帧被水流、传送带或磁铁挪动过
 }
 
 impl Item {
     // 创建新物品
-    fn new(item_type: ItemType, x: f32, y: f32) -> Self {
+    fn new(item_type: ItemType, x: f32, y: f32, base_size: f32) -> Self {
         Item {
             item_type,
             position: na::Point2::new(x, y),
             collected: false,
+            base_size,
+            value: item_type.base_value(),
+            velocity: 0.0,
+            falling: false,
+            resting_on: None,
+            drifting: false,
         }
     }
 
-    // 获取物品价值
-    fn value(&self) -> i32 {
-        match self.item_type {
-            ItemType::Gold => 100,
-            ItemType::Silver => 50,
-            ItemType::Diamond => 200,
-            ItemType::Rock => 10,
+    // 在基础价值上下随机浮动 variance 比例
+    fn roll_value(&mut self, rng: &mut StdRng, variance: f32) {
+        if variance > 0.0 {
+            let factor = rng.gen_range(1.0 - variance..=1.0 + variance);
+            self.value = (self.item_type.base_value() as f32 * factor).round() as i32;
+        }
+    }
+
+    // 按比例调整生成时的价值（如深处的物品成色更好）
+    fn scale_value(&mut self, factor: f32) {
+        self.value = (self.value as f32 * factor).round() as i32;
+    }
+
+    // 在增益影响下的物品价值
+    fn value(&self, context: &ValueContext) -> i32 {
+        let mut value = self.value as f32;
+        if context.luck {
+            value = value.max(self.item_type.base_value() as f32);
+        }
+        value.round() as i32
+    }
+
+    // 升一档品质（石头→银→金→钻石），成色保持不变；已是最高档时返回 false
+    fn upgrade(&mut self) -> bool {
+        let next = match self.item_type {
+            ItemType::Rock => ItemType::Silver,
+            ItemType::Silver => ItemType::Gold(BagSize::Medium),
+            ItemType::Gold(_) => ItemType::Diamond,
+            _ => return false,
+        };
+        let purity = self.purity();
+        self.item_type = next;
+        self.value = (next.base_value() as f32 * purity).round() as i32;
+        true
+    }
+
+    // 成色：实际价值相对基础价值的比例
+    fn purity(&self) -> f32 {
+        match self.item_type.base_value() {
+            0 => 1.0,
+            base => self.value as f32 / base as f32,
         }
     }
 
     // 获取物品颜色
     fn color(&self) -> Color {
-        match self.item_type {
-            ItemType::Gold => Color::new(1.0, 0.84, 0.0, 1.0), // 金色
-            ItemType::Silver => Color::new(0.75, 0.75, 0.75, 1.0), // 银色
-            ItemType::Diamond => Color::new(0.0, 1.0, 1.0, 1.0), // 钻石蓝
-            ItemType::Rock => Color::new(0.5, 0.5, 0.5, 1.0), // 灰色
+        self.item_type.color()
+    }
+
+    // 正在下落或漂动的物品不进静态背景，每帧单独绘制
+    fn moving(&self) -> bool {
+        self.falling || self.drifting
+    }
+
+    // 在物品当前位置绘制方块和袋口扎绳
+    fn draw(&self, ctx: &mut Context) -> GameResult {
+        let size = self.size();
+        let rect = Rect::new(self.position.x - size / 2.0, self.position.y - size / 2.0, size, size);
+        graphics::rectangle(ctx, graphics::DrawParam::default().dest(rect.point()), &rect, self.color())?;
+        if let Some(tie) = self.bag_tie() {
+            graphics::rectangle(ctx, graphics::DrawParam::default().dest(tie.point()), &tie, BAG_TIE_COLOR)?;
+        }
+        Ok(())
+    }
+
+    // 袋装物品（金袋、神秘袋）袋口扎绳的位置，其他物品没有
+    fn bag_tie(&self) -> Option<Rect> {
+        if !matches!(self.item_type, ItemType::Gold(_) | ItemType::MysteryBag) {
+            return None;
         }
+        let size = self.size();
+        Some(Rect::new(self.position.x - size / 4.0, self.position.y - size / 2.0 + 4.0, size / 2.0, 4.0))
+    }
+
+    // 获取物品重量
+    fn weight(&self) -> f32 {
+        self.item_type.weight()
     }
 
     // 获取物品大小
     fn size(&self) -> f32 {
         match self.item_type {
-            ItemType::Rock => ITEM_SIZE * 1.5, // 石头更大一些
-            _ => ITEM_SIZE,
+            ItemType::Gold(size) => self.base_size * size.spec().size,
+            ItemType::Rock => self.base_size * 1.5, // 石头更大一些
+            ItemType::GoldenPig => self.base_size * 2.5,
+            ItemType::Chest => self.base_size * 1.3,
+            ItemType::Key => self.base_size * 0.7,
+            ItemType::Bone => self.base_size * 0.8,
+            ItemType::Clock => self.base_size * 0.8,
+            _ => self.base_size,
         }
     }
 }
@@ -89,84 +546,249 @@ struct Hook {
     length: f32,
     state: HookState,
     attached_item: Option<usize>, // 附着的物品索引
+    max_length: f32,
+    speed: f32,
+    extend_speed: f32, // 本次出钩的伸出速度
+    heaviness: f32,    // 拖着物品收钩时的减速倍率
+    strength: Option<f32>, // 大力水：拖着物品时无视重量的收钩倍率
+    load_weight: f32,  // 附着物品的重量
+    swing: Option<(f32, f32)>, // 摆钩的每帧相位增量和摆幅（弧度），None 时不摆动
+    swing_phase: f32,
+    claw: f32, // 钩爪的半边长
+    respawn: u64, // 绳子被割断后重新装好钩子还要等的帧数
+    winch: f32,     // 绞盘的速度倍率，燃料烧干时变慢
+    drag: f32,      // 水的阻力带来的速度倍率，水面以上为 1
+    wind: f32,      // 本关的风，每帧把伸出的钩子往横向吹偏的像素
+    drift: f32,     // 钩子被风吹偏的水平距离，正数向右
+    travelled: f32, // 本帧钩子移动的距离，用来计算燃料消耗
+    fireproof: bool, // 防火绳，拖金水不会被烧断
+    burn: u64,       // 拖着金水收钩已经烧了多少帧
+    ricochet: bool,  // 反弹钩：碰到侧墙时反射出钩方向
+    bounces: u32,    // 本次出钩已经反弹的次数
+    bounce: Option<(na::Point2<f32>, f32)>, // 反弹点和反弹时的绳长
+    wall: f32,       // 右侧墙的位置（屏幕宽度），左侧墙为 0
+    player: usize,   // 出钩的玩家，0 为玩家一
+    insured: bool,   // 这一钩已经用保险挡过一次断绳，剩下的路程不会再被割断
+    portals: Option<[na::Point2<f32>; 2]>, // 本关一对传送门的圆心
+    warp: Option<(na::Point2<f32>, f32)>,  // 钻过传送门时钻出的门和钻进时的绳长
 }
 
 impl Hook {
     // 创建新钩子
-    fn new(x: f32, y: f32) -> Self {
+    fn new(x: f32, y: f32, config: &GameConfig) -> Self {
         Hook {
             position: na::Point2::new(x, y),
             angle: std::f32::consts::PI / 2.0, // 初始角度向下
             length: 0.0,
             state: HookState::Idle,
             attached_item: None,
+            max_length: config.hook_length,
+            speed: config.hook_speed,
+            extend_speed: config.hook_speed,
+            heaviness: 1.0,
+            strength: None,
+            load_weight: 1.0,
+            swing: if config.pendulum {
+                Some((config.swing_speed / TICKS_PER_SECOND as f32, config.swing_arc.to_radians()))
+            } else {
+                None
+            },
+            swing_phase: 0.0,
+            claw: CLAW_SIZE,
+            respawn: 0,
+            winch: 1.0,
+            drag: 1.0,
+            wind: 0.0,
+            drift: 0.0,
+            travelled: 0.0,
+            fireproof: false,
+            burn: 0,
+            ricochet: false,
+            bounces: 0,
+            bounce: None,
+            wall: config.screen_width,
+            player: 0,
+            insured: false,
+            portals: None,
+            warp: None,
         }
     }
 
-    // 更新钩子位置
-    fn update(&mut self, dt: f32) {
+    // 更新钩子位置，origin 为绳子起点（矿工位置）
+    fn update(&mut self, origin: na::Point2<f32>, _dt: f32) {
+        let before = self.length;
+        let reach_before = self.reach();
         match self.state {
             HookState::Idle => {
-                // 闲置状态，钩子在矿工位置
+                // 闲置状态，钩子在矿工位置；摆钩模式下围绕正下方来回摆动
                 self.length = 0.0;
+                self.drift = 0.0;
+                self.respawn = self.respawn.saturating_sub(1);
+                if let Some((step, arc)) = self.swing {
+                    self.swing_phase += step;
+                    self.angle = std::f32::consts::FRAC_PI_2 + arc * self.swing_phase.sin();
+                }
             }
             HookState::Thrown => {
                 // 抛出状态，钩子向外延伸
-                self.length += HOOK_SPEED;
-                if self.length >= HOOK_LENGTH {
+                self.length += self.extend_speed * self.winch * self.drag;
+                // 伸出去的时候一路被风吹偏，偏移越积越多；反弹或钻过传送门之后不再吹偏
+                if self.bounce.is_none() && self.warp.is_none() {
+                    self.drift += self.wind;
+                }
+                // 反弹钩碰到侧墙时在墙上记下反弹点，之后沿反射的方向继续伸出
+                let tip = self.rope_point(origin, 1.0);
+                if self.ricochet && self.bounces < MAX_RICOCHETS && self.warp.is_none() && (tip.x < 0.0 || tip.x > self.wall) {
+                    self.bounces += 1;
+                    self.bounce = Some((na::Point2::new(tip.x.clamp(0.0, self.wall), tip.y), self.length));
+                }
+                // 钩尖钻进一个传送门时记下另一个门和此时的绳长，之后从那个门沿原方向接着伸出
+                if let (Some(ends), None, None) = (self.portals, self.bounce, self.warp) {
+                    if let Some(k) = ends.iter().position(|end| na::distance(end, &tip) < PORTAL_RADIUS) {
+                        self.warp = Some((ends[1 - k], self.length));
+                    }
+                }
+                if self.length >= self.max_length {
                     self.state = HookState::Retracting;
                 }
             }
             HookState::Retracting => {
-                // 收回状态，钩子向内收缩
-                self.length -= HOOK_SPEED;
+                // 收回状态，钩子向内收缩，拖着物品时速度与物品重量成反比
+                let speed = match (self.attached_item, self.strength) {
+                    (Some(_), Some(factor)) => self.speed * factor,
+                    (Some(_), None) => self.speed / (self.heaviness * self.load_weight),
+                    (None, _) => self.speed,
+                };
+                self.length -= speed * self.winch * self.drag;
                 if self.length <= 0.0 {
                     self.length = 0.0;
-                    self.state = HookState::Idle;
-                    self.attached_item = None; // 收回时释放物品
+                    self.state = HookState::Idle; // 附着的物品由 GameState 结算后释放
                 }
+                // 收回时沿着原来弯曲的路径回来
+                if reach_before > 0.0 {
+                    self.drift *= self.reach() / reach_before;
+                }
+            }
+        }
+
+        self.travelled = (self.length - before).abs();
+
+        // 计算钩子位置：沿出钩方向伸出，再加上风吹出的横向偏移；反弹过就从反弹点沿反射方向伸出，
+        // 钻过传送门就从另一个门沿原方向伸出
+        self.position = match (self.bounce, self.warp) {
+            (Some((point, at)), _) if self.length > at => {
+                let (sin, cos) = (std::f32::consts::PI - self.angle).sin_cos();
+                let beyond = self.length - at;
+                na::Point2::new(point.x + cos * beyond, point.y + sin * beyond)
+            }
+            (_, Some((exit, at))) if self.length > at => {
+                let (sin, cos) = self.angle.sin_cos();
+                let beyond = self.length - at;
+                na::Point2::new(exit.x + cos * beyond, exit.y + sin * beyond)
             }
+            _ => self.rope_point(origin, 1.0),
+        };
+    }
+
+    // 起点到反弹点（或钻进的传送门）这一段绳子的长度，没有反弹也没钻过门时为整根绳长
+    fn reach(&self) -> f32 {
+        match (self.bounce, self.warp) {
+            (Some((_, at)), _) | (_, Some((_, at))) => self.length.min(at),
+            _ => self.length,
         }
+    }
+
+    // 第一段绳子上从起点（0）到钩尖或反弹点（1）的一点，风的偏移沿水平方向逐渐累积，绳子弯成一条曲线
+    fn rope_point(&self, origin: na::Point2<f32>, t: f32) -> na::Point2<f32> {
+        let (sin, cos) = self.angle.sin_cos();
+        let along = self.reach() * t;
+        let across = self.drift * t * t;
+        na::Point2::new(origin.x + cos * along + across, origin.y + sin * along)
+    }
 
-        // 计算钩子位置
-        self.position.x = self.angle.cos() * self.length;
-        self.position.y = self.angle.sin() * self.length;
+    // 画绳子用的折线，反弹过的绳子在反弹点折向钩尖；钻过传送门的绳子断成两段，第二段从钻出的门连到钩尖
+    fn rope(&self, origin: na::Point2<f32>) -> Vec<Vec<na::Point2<f32>>> {
+        let mut points: Vec<_> = (0..=ROPE_SEGMENTS)
+            .map(|i| self.rope_point(origin, i as f32 / ROPE_SEGMENTS as f32))
+            .collect();
+        match (self.bounce, self.warp) {
+            (Some((_, at)), _) if self.length > at => points.push(self.position),
+            (_, Some((exit, at))) if self.length > at => return vec![points, vec![exit, self.position]],
+            _ => (),
+        }
+        vec![points]
     }
 
     // 发射钩子
-    fn throw(&mut self, angle: f32) {
-        if self.state == HookState::Idle {
+    fn throw(&mut self, angle: f32, power: f32) {
+        if self.ready() {
             self.angle = angle;
+            self.extend_speed = self.speed * power;
             self.state = HookState::Thrown;
             self.length = 0.0;
             self.attached_item = None;
+            self.bounces = 0;
+            self.bounce = None;
+            self.warp = None;
+            self.insured = false;
         }
     }
 
-    // 检查是否碰撞到物品
-    fn check_collision(&mut self, items: &mut [Item]) {
+    // 空闲且装好了钩子，可以出钩
+    fn ready(&self) -> bool {
+        self.state == HookState::Idle && self.respawn == 0
+    }
+
+    // 绳子被割断：钩子和附着的物品一起丢失，过 respawn 帧才装好新钩子
+    fn cut(&mut self, respawn: u64) -> Option<usize> {
+        self.state = HookState::Idle;
+        self.length = 0.0;
+        self.load_weight = 1.0;
+        self.respawn = respawn;
+        self.attached_item.take()
+    }
+
+    // 炸掉附着的物品，钩子立即收回
+    fn snap_back(&mut self) -> Option<usize> {
+        let item = self.attached_item.take()?;
+        self.state = HookState::Idle;
+        self.length = 0.0;
+        self.load_weight = 1.0;
+        Some(item)
+    }
+
+    // 抓住物品开始收钩
+    fn grab(&mut self, index: usize, item: &mut Item) {
+        self.attached_item = Some(index);
+        self.load_weight = item.weight();
+        self.burn = 0;
+        item.collected = true;
+        self.state = HookState::Retracting;
+    }
+
+    // 钩爪碰到的物品及其到钩尖的距离，没碰到返回 None
+    fn touching(&self, items: &[Item], terrain: &Terrain) -> Option<(usize, f32)> {
         if self.state != HookState::Thrown || self.attached_item.is_some() {
-            return;
+            return None;
         }
 
         let hook_x = self.position.x;
         let hook_y = self.position.y;
 
-        for (i, item) in items.iter_mut().enumerate() {
-            if !item.collected {
+        for (i, item) in items.iter().enumerate() {
+            // 埋在泥土里的物品要先挖开
+            if !item.collected && !terrain.buried(item.position) {
                 let item_x = item.position.x;
                 let item_y = item.position.y;
-                let item_size = item.size() / 2.0;
+                let reach = item.size() / 2.0 + self.claw;
 
-                // 简单的矩形碰撞检测
-                if (hook_x - item_x).abs() < item_size && (hook_y - item_y).abs() < item_size {
-                    self.attached_item = Some(i);
-                    item.collected = true;
-                    self.state = HookState::Retracting;
-                    break;
+                // 简单的矩形碰撞检测，钩爪碰到物品的边缘就算抓住
+                if (hook_x - item_x).abs() < reach && (hook_y - item_y).abs() < reach {
+                    return Some((i, na::distance(&self.position, &item.position)));
                 }
             }
         }
+        None
     }
 }
 
@@ -176,301 +798,3627 @@ struct Miner {
     position: na::Point2<f32>,
     width: f32,
     height: f32,
+    speed: f32,
+    velocity: f32, // 矿车的水平速度（像素/秒）
+    throttle: f32, // -1 向左加速，1 向右加速，0 滑行
+    max_x: f32,    // 可移动的最右侧（屏幕宽度）
+    stamina: f32,
+    max_stamina: f32,
+    hearts: u32, // 被落石砸中扣一颗，扣完本局结束
+    stun: u64,   // 被砸晕剩余的帧数，晕着不能走动
+    carry_drag: f32,
 }
 
 impl Miner {
     // 创建新矿工
-    fn new(x: f32, y: f32) -> Self {
+    fn new(x: f32, y: f32, config: &GameConfig) -> Self {
         Miner {
             position: na::Point2::new(x, y),
-            width: MINER_WIDTH,
-            height: MINER_HEIGHT,
+            width: config.miner_width,
+            height: config.miner_height,
+            speed: config.miner_speed,
+            velocity: 0.0,
+            throttle: 0.0,
+            max_x: config.screen_width,
+            stamina: config.max_stamina,
+            max_stamina: config.max_stamina,
+            hearts: config.miner_hearts,
+            stun: 0,
+            carry_drag: config.carry_drag,
         }
     }
 
-    // 移动矿工
-    fn move_left(&mut mut) {
-        if self.position.x > self.width / 2.0 {
-            self.position.x -= 5.0;
+    fn rect(&self) -> Rect {
+        Rect::new(
+            self.position.x - self.width / 2.0,
+            self.position.y - self.height / 2.0,
+            self.width,
+            self.height,
+        )
+    }
+
+    // 被落石砸中
+    fn hit(&mut self, stun: u64) {
+        self.hearts = self.hearts.saturating_sub(1);
+        self.stun = stun;
+    }
+
+    // 消耗体力，不够时返回 false
+    fn spend_stamina(&mut self, amount: f32) -> bool {
+        if self.stamina < amount {
+            return false;
         }
+        self.stamina -= amount;
+        true
+    }
+
+    // 恢复或消耗体力（amount 为负表示消耗），限制在 0 到上限之间
+    fn change_stamina(&mut self, amount: f32) {
+        self.stamina = (self.stamina + amount).max(0.0).min(self.max_stamina);
+    }
+
+    // 踩住方向
+    fn move_left(&mut self) {
+        self.throttle = -1.0;
     }
 
-    // 移动矿工
     fn move_right(&mut self) {
-        if self.position.x < SCREEN_WIDTH - self.width / 2.0 {
-            self.position.x += 5.0;
+        self.throttle = 1.0;
+    }
+
+    // 松开方向键
+    fn coast(&mut self) {
+        self.throttle = 0.0;
+    }
+
+    // 矿车：踩住方向加速，松开后滑行慢慢停下，反向时先打滑刹住再加速；晕着时只能滑行。
+    // load 为钩子正拖着的物品重量，越重满速越低。正在打滑时返回 true
+    fn update(&mut self, dt: f32, load: f32) -> bool {
+        let throttle = if self.stun > 0 { 0.0 } else { self.throttle };
+        let top_speed = self.speed * CART_SPEED_SCALE / (1.0 + load * self.carry_drag);
+        let skidding = throttle != 0.0 && self.velocity * throttle < 0.0;
+        if skidding {
+            self.velocity += throttle * CART_SKID_BRAKE * dt;
+        } else if throttle != 0.0 {
+            self.velocity = (self.velocity + throttle * CART_ACCEL * dt).clamp(-top_speed, top_speed);
+        } else {
+            let slowed = (self.velocity.abs() - CART_FRICTION * dt).max(0.0);
+            self.velocity = slowed.copysign(self.velocity);
+        }
+
+        // 撞到屏幕边缘停下
+        self.position.x += self.velocity * dt;
+        let (min_x, max_x) = (self.width / 2.0, self.max_x - self.width / 2.0);
+        if self.position.x < min_x || self.position.x > max_x {
+            self.position.x = self.position.x.clamp(min_x, max_x);
+            self.velocity = 0.0;
+        }
+        skidding
+    }
+
+    fn draw(&self, ctx: &mut Context, color: Color) -> GameResult {
+        let body = self.rect();
+        graphics::rectangle(ctx, graphics::DrawParam::default().dest(body.point()), &body, color)?;
+        // 矿车的两个轮子
+        for side in [-1.0, 1.0] {
+            let wheel = Rect::new(self.position.x + side * self.width / 3.0 - 5.0, body.y + body.h - 4.0, 10.0, 8.0);
+            graphics::rectangle(ctx, graphics::DrawParam::default().dest(wheel.point()), &wheel, Color::new(0.2, 0.2, 0.2, 1.0))?;
         }
+        Ok(())
     }
 }
 
+// 场景
+#[derive(Debug, PartialEq)]
+enum Scene {
+    MainMenu,
+    Playing,
+    Cutscene,
+    Shop,
+    Achievements,
+    LevelSelect,
+    Slots,
+}
+
 // 游戏状态
 struct GameState {
+    config: GameConfig,
     miner: Miner,
-    hook: Hook,
+    hooks: Vec<Hook>, // 第一个是主钩，买了双钩后还有第二个
     items: Vec<Item>,
-    score: i32,
-    start_time: Instant,
+    level_score: i32, // 本关得分，用于判断是否达标
+    wallet: i64,      // 跨关卡保留的钱，每关结束时存入本关得分，用于商店和还债
+    tick: u64,
+    duration_ticks: u64, // 本关时长
+    bonus_ticks: u64,    // 本关拉回时钟累计加的时间，各模式的时限都往后推
+    throws_left: u32,    // 解谜模式本关还能出几钩
     game_over: bool,
+    scene: Scene,
+    menu_selected: usize,
+    daily_day: u64, // 正在进行的每日挑战是哪一天的
+    seed: u64,
+    level: u32,
+    player: String,
+    recording: Replay,
+    input: Box<dyn InputSource>,
+    message: Option<String>,
+    analysis: Option<ReplayAnalysis>,
+    show_analysis: bool,
+    effects: Effects,
+    background: BackgroundCache,
+    events: EventBus,
+    plugins: PluginRegistry,
+    stats: StatsTracker,
+    persist: bool, // 是否把数据写入磁盘（离屏模拟时为 false）
+    cable_oil: f32,
+    winch_fuel: f32,
+    wind: f32, // 本关的风
+    drill: Drill,
+    drill_unlocked: bool,
+    dynamite: u32,
+    keys: u32, // 本关捡到还没用掉的钥匙
+    appraiser: bool, // 鉴定师升级：鼠标悬停显示物品的准确价值
+    projectiles: Vec<DynamiteProjectile>,
+    moles: MoleWarren,
+    saws: SawBlades, // 会割断绳子的锯片
+    bats: Bats,      // 会叼走钩子上物品的蝙蝠
+    portals: Portals, // 把钩子传送到另一头的传送门
+    conveyors: Conveyors,
+    terrain: Terrain,
+    water: Water, // 水下关卡的水域
+    quakes: Earthquakes,
+    powerups: PowerUps, // 矿洞上层漂过的道具
+    rock_fall: RockFall,
+    world: World, // 本关的世界主题
+    boss: Option<Boss>, // 首领关的金猪，抓住后变成物品
+    camera: Camera,
+    sfx: SoundEffects,
+    market: Market,
+    campaign: Option<DebtCampaign>, // 还债战役进行中
+    story: Option<StoryRun>,        // 剧情战役进行中
+    cutscene: Option<Cutscene>,
+    after_cutscene: Scene,          // 过场动画播完后切换到的场景
+    dialogue: Option<DialogueBox>,  // 打开时暂停玩法
+    shop: Option<Shop>,
+    level_select: Option<LevelSelect>, // 经典闯关的选关地图
+    secrets: Secrets,     // 本关隐藏条件的进度
+    bonus_unlocked: bool, // 已解锁、下一局进入奖励关
+    bonus_stage: bool,    // 当前这一局是奖励关
+    tutorial: Option<Tutorial>, // 正在打教学关
+    sandbox: Sandbox,           // 沙盒模式里选中的物品
+    fuses: Vec<(u64, usize)>,   // 被波及、引信正在烧的炸药桶：(爆炸的帧, 物品下标)
+    slots: Option<SlotMachine>, // 关卡之间的老虎机
+    rival: Option<Rival>, // 双人对战的玩家二
+    versus_cpu: bool,     // 玩家二由电脑控制
+    ghost: Option<Ghost>, // 本关最佳成绩的幽灵
+    ghost_recorder: GhostRecorder,
+    ai_skill: Skill,      // 主菜单里选的电脑对手等级
+    objectives: ObjectiveTracker, // 本关附加目标
+    contract_offer: Option<ContractOffer>, // 打开时暂停玩法
+    contract: Option<(usize, Contract)>,   // 接下的合约及其在附加目标中的位置
+    shop_discount: f32,
+    next_seed: u64, // 商店之后那一关的种子，进店时先定下，店里就能预告那一关的行情
+    prestige: Prestige,
+    round_prestige: Option<Prestige>, // 本关生效的声望：剧情关卡的声望或还债战役的周目，回放时取自文件头
+    mode: GameMode,
+    difficulty: Difficulty, // 主菜单里选的难度，开局时写入配置
+    director: Director,     // 自适应难度导演
+    endless: EndlessRun,
+    records: Records, // 各模式的最佳纪录
+    achievements: Achievements,
+    crash_resume: Option<(Replay, RunState)>, // 上次崩溃留下的快照，等玩家决定是否继续
+    extra_dynamite: u32,            // 商店买下、下一关开局时发放的炸药
+    next_level_wares: Vec<Ware>,    // 商店买下、只在下一关生效的道具
+    buffs: Buffs,                   // 本关生效的增益
+    combo: Combo,                   // 连续拉回贵重物品的连击
+    rock_book: bool,                // 本局买过石头收藏图鉴
+    gem_polish: bool,               // 本局买过宝石抛光
+    hook_tier: u8,                  // 本局买到的钩速升级档位
+    big_claw: bool,                 // 本局买过大钩爪
+    dual_hook: bool,                // 本局买过双钩
+    fireproof_rope: bool,           // 本局买过防火绳
+    ricochet_hook: bool,            // 本局买过反弹钩
+    insurance: u32,                 // 商店买下的保险，每份抵掉一次倒扣钱或断绳
+    strings: Strings,
+    throw_charge: f32, // 蓄力出钩的蓄力值（0~1）
+    charging: bool,
+    steering: Steering, // 玩家一按住的方向键
 }
 
 impl GameState {
     // 创建新游戏状态
-    fn new(ctx: &mut Context) -> GameResult<Self> {
-        let miner = Miner::new(SCREEN_WIDTH / 2.0, 50.0);
-        let hook = Hook::new(miner.position.x, miner.position.y);
-        let mut items = Vec::new();
-
-        // 生成随机物品
-        let mut rng = thread_rng();
-        for _ in 0..20 {
-            let item_type = match rng.gen_range(0..10) {
-                0..=4 => ItemType::Gold,
-                5..=7 => ItemType::Silver,
-                8 => ItemType::Diamond,
-                9 => ItemType::Rock,
-                _ => unreachable!(),
-            };
-
-            let x = rng.gen_range(ITEM_SIZE..SCREEN_WIDTH - ITEM_SIZE);
-            let y = rng.gen_range(100.0..SCREEN_HEIGHT - ITEM_SIZE);
-
-            items.push(Item::new(item_type, x, y));
+    fn new(_ctx: &mut Context, config: GameConfig) -> GameResult<Self> {
+        let mut state = Self::headless(config);
+        state.persist = true;
+        state.prestige = Prestige::load();
+        state.records = Records::load();
+        state.achievements = Achievements::load();
+        state.crash_resume = crash::pending_resume(&state.config);
+        state.message = crash::take_notice();
+        // 语言文件读取失败时退回空表（直接显示键名）
+        state.strings = match Strings::load(&state.config.language) {
+            Ok(strings) => strings,
+            Err(err) => {
+                state.message = Some(format!("Failed to load language {}: {}", state.config.language, err));
+                Strings::default()
+            }
+        };
+        state.stats = StatsTracker::with_lifetime();
+        state.drill_unlocked = state.stats.lifetime.totals.score >= state.config.drill_unlock_score;
+        state.register_plugin(Box::new(SpeedrunTimer::default()));
+        if state.config.telemetry {
+            state.register_telemetry();
         }
+        Ok(state)
+    }
 
-        Ok(GameState {
-            miner,
-            hook,
-            items,
-            score: 0,
-            start_time: Instant::now(),
+    // 不依赖窗口上下文创建游戏状态（回放分析等离屏模拟使用）
+    fn headless(config: GameConfig) -> Self {
+        let seed = thread_rng().gen();
+        let player = std::env::var("USER").unwrap_or_else(|_| "Player".to_string());
+        let mut state = GameState {
+            miner: Miner::new(config.screen_width / 2.0, 50.0, &config),
+            hooks: vec![Hook::new(config.screen_width / 2.0, 50.0, &config)],
+            items: Vec::new(),
+            level_score: 0,
+            wallet: 0,
+            tick: 0,
+            duration_ticks: 0,
+            bonus_ticks: 0,
+            throws_left: 0,
             game_over: false,
-        })
+            scene: Scene::MainMenu,
+            menu_selected: 0,
+            daily_day: 0,
+            seed,
+            level: 1,
+            recording: Replay::new(seed, 1, &player),
+            player,
+            input: Box::new(KeyboardInput::new()),
+            message: None,
+            analysis: None,
+            show_analysis: false,
+            effects: Effects::new(),
+            background: BackgroundCache::new(config.screen_width, config.mine_depth),
+            events: EventBus::new(),
+            plugins: PluginRegistry::new(),
+            stats: StatsTracker::new(),
+            persist: false,
+            cable_oil: 0.0,
+            winch_fuel: 0.0,
+            wind: 0.0,
+            drill: Drill::new(&config),
+            drill_unlocked: config.drill_unlock_score <= 0,
+            dynamite: 0,
+            keys: 0,
+            appraiser: config.appraiser,
+            projectiles: Vec::new(),
+            moles: MoleWarren::default(),
+            saws: SawBlades::default(),
+            bats: Bats::default(),
+            portals: Portals::default(),
+            conveyors: Conveyors::default(),
+            terrain: Terrain::default(),
+            water: Water::default(),
+            quakes: Earthquakes::default(),
+            powerups: PowerUps::default(),
+            rock_fall: RockFall::default(),
+            world: World::default(),
+            boss: None,
+            camera: Camera::new(config.screen_height, config.mine_depth),
+            sfx: SoundEffects::new(),
+            market: Market::roll(seed, 1, config.market_volatility),
+            campaign: None,
+            story: None,
+            cutscene: None,
+            after_cutscene: Scene::MainMenu,
+            dialogue: None,
+            shop: None,
+            level_select: None,
+            secrets: Secrets::default(),
+            bonus_unlocked: false,
+            bonus_stage: false,
+            tutorial: None,
+            sandbox: Sandbox::default(),
+            fuses: Vec::new(),
+            slots: None,
+            rival: None,
+            versus_cpu: false,
+            ghost: None,
+            ghost_recorder: GhostRecorder::default(),
+            ai_skill: config.ai_skill,
+            objectives: ObjectiveTracker::default(),
+            contract_offer: None,
+            contract: None,
+            shop_discount: 1.0,
+            next_seed: 0,
+            prestige: Prestige::default(),
+            round_prestige: None,
+            mode: GameMode::Classic,
+            difficulty: config.difficulty,
+            director: Director::default(),
+            endless: EndlessRun::default(),
+            records: Records::default(),
+            achievements: Achievements::default(),
+            crash_resume: None,
+            extra_dynamite: 0,
+            next_level_wares: Vec::new(),
+            buffs: Buffs::default(),
+            combo: Combo::default(),
+            rock_book: false,
+            gem_polish: false,
+            hook_tier: 0,
+            big_claw: false,
+            dual_hook: false,
+            fireproof_rope: false,
+            ricochet_hook: false,
+            insurance: 0,
+            strings: Strings::default(),
+            throw_charge: 0.0,
+            charging: false,
+            steering: Steering::default(),
+            config,
+        };
+        state.start_round(seed, Box::new(KeyboardInput::new()));
+        state
     }
 
-    // 更新游戏状态
-    fn update(&mut self, dt: f32) {
-        if self.game_over {
-            return;
-        }
-
-        // 检查游戏是否结束
-        if Instant::now() - self.start_time >= GAME_DURATION {
-            self.game_over = true;
-            return;
+    // 开局时带进本关的升级和道具，写进回放文件头
+    fn loadout(&self) -> Loadout {
+        Loadout {
+            hook_tier: self.hook_tier,
+            insurance: self.insurance,
+            dynamite: self.extra_dynamite,
+            big_claw: self.big_claw,
+            dual_hook: self.dual_hook,
+            fireproof_rope: self.fireproof_rope,
+            ricochet_hook: self.ricochet_hook,
+            rock_book: self.rock_book,
+            gem_polish: self.gem_polish,
+            strength: self.next_level_wares.contains(&Ware::StrengthDrink),
+            luck: self.next_level_wares.contains(&Ware::LuckyClover),
+            magnet: self.next_level_wares.contains(&Ware::Magnet),
+            drill: self.drill_unlocked,
         }
+    }
 
-        // 更新钩子
-        self.hook.update(dt);
-
-        // 检查钩子与物品的碰撞
-        self.hook.check_collision(&mut self.items);
+    // 回放开局前换上录制时的升级和道具；钻头是否解锁直接读文件头，不改动玩家自己的进度
+    fn apply_loadout(&mut self, loadout: &Loadout) {
+        self.hook_tier = loadout.hook_tier;
+        self.insurance = loadout.insurance;
+        self.extra_dynamite = loadout.dynamite;
+        self.big_claw = loadout.big_claw;
+        self.dual_hook = loadout.dual_hook;
+        self.fireproof_rope = loadout.fireproof_rope;
+        self.ricochet_hook = loadout.ricochet_hook;
+        self.rock_book = loadout.rock_book;
+        self.gem_polish = loadout.gem_polish;
+        self.next_level_wares = [
+            (loadout.strength, Ware::StrengthDrink),
+            (loadout.luck, Ware::LuckyClover),
+            (loadout.magnet, Ware::Magnet),
+        ]
+        .into_iter()
+        .filter_map(|(owned, ware)| owned.then_some(ware))
+        .collect();
+    }
 
-        // 如果钩子收回且有附着的物品，增加分数
-        if self.hook.state == HookState::Idle && self.hook.attached_item.is_some() {
-            if let Some(item_idx) = self.hook.attached_item {
-                if item_idx < self.items.len() {
-                    let item = &self.items[item_idx];
-                    self.score += item.value();
+    // 用给定种子和输入源开始一局
+    fn start_round(&mut self, seed: u64, input: Box<dyn InputSource>) {
+        if let Some(replay) = input.replay() {
+            self.apply_loadout(&replay.header.loadout);
+        }
+        let loadout = self.loadout();
+        let miner = Miner::new(self.config.screen_width / 2.0, 50.0, &self.config);
+        let hook_count = if self.dual_hook { 2 } else { 1 };
+        self.wind = Self::roll_wind(seed, &self.config);
+        self.hooks = (0..hook_count)
+            .map(|_| {
+                let mut hook = Hook::new(miner.position.x, miner.position.y, &self.config);
+                hook.wind = self.wind;
+                hook.speed *= HOOK_SPEED_TIERS[self.hook_tier as usize];
+                if self.big_claw {
+                    hook.claw = BIG_CLAW_SIZE;
+                }
+                hook.fireproof = self.fireproof_rope;
+                hook.ricochet = self.ricochet_hook;
+                hook
+            })
+            .collect();
+        self.miner = miner;
+        // 双人对战与合作：两人分站左右，玩家二另有一个钩子，总是摆钩出钩
+        self.rival = None;
+        if matches!(self.mode, GameMode::Versus | GameMode::Coop) {
+            self.miner.position.x = self.config.screen_width / 3.0;
+            let rival = Miner::new(self.config.screen_width * 2.0 / 3.0, 50.0, &self.config);
+            let mut hook = Hook::new(rival.position.x, rival.position.y, &self.config);
+            hook.player = RIVAL;
+            hook.wind = self.wind;
+            hook.swing.get_or_insert((self.config.swing_speed / TICKS_PER_SECOND as f32, self.config.swing_arc.to_radians()));
+            self.hooks.push(hook);
+            let mut rival = Rival::new(rival);
+            if self.versus_cpu {
+                rival.input = Box::new(AiInput::new(self.ai_skill, seed.wrapping_add(0x6169)));
+            }
+            self.rival = Some(rival);
+        }
+        // 回放按录制时的模式、难度和目标分生成同样的布局；每日挑战人人布局相同，固定普通难度，导演也不插手
+        (self.config.difficulty, self.config.nudge) = match input.replay() {
+            Some(replay) => {
+                self.mode = replay.header.mode;
+                (replay.header.difficulty, replay.header.nudge)
+            }
+            None if self.mode == GameMode::Daily => (Difficulty::Normal, 0.0),
+            None if self.config.director => (self.difficulty, self.director.nudge()),
+            None => (self.difficulty, 0.0),
+        };
+        self.endless = EndlessRun::default();
+        if let Some(replay) = input.replay() {
+            self.bonus_stage = replay.header.bonus;
+        }
+        // 还债战役的新周目让物品更差、危险更多，回放同样按文件头生成
+        self.config.new_game_plus = match input.replay() {
+            Some(replay) => replay.header.new_game_plus,
+            None => self.campaign.as_ref().map_or(0, |campaign| campaign.plus),
+        };
+        let target = match input.replay() {
+            Some(replay) => replay.header.target,
+            None => self.level_target(),
+        };
+        let level = if self.bonus_stage {
+            Level::bonus(self.level, &self.config)
+        } else {
+            Level::nth(self.level, &self.config)
+        };
+        self.items = Self::spawn_items(&self.config, &mut StdRng::seed_from_u64(seed), target, &level);
+        gravity::link_supports(&mut self.items);
+        self.world = level.world;
+        self.camera = Camera::new(self.config.screen_height, self.config.mine_depth);
+        self.background.set_world(level.world);
+        self.level_score = 0;
+        self.tick = 0;
+        self.duration_ticks = level.time_limit_ticks();
+        self.bonus_ticks = 0;
+        self.throws_left = self.config.puzzle_throws;
+        self.game_over = false;
+        self.seed = seed;
+        self.recording = Replay::new(seed, self.level, &self.player);
+        self.recording.header.target = target;
+        self.recording.header.mode = self.mode;
+        self.recording.header.difficulty = self.config.difficulty;
+        self.recording.header.nudge = self.config.nudge;
+        self.recording.header.bonus = self.bonus_stage;
+        self.recording.header.new_game_plus = self.config.new_game_plus;
+        self.recording.header.loadout = loadout;
+        self.secrets = Secrets::new(&self.items);
+        self.input = input;
+        // 经典闯关自由模式读取本关的幽灵，只在同一个布局上显示；这一局从头记录
+        self.ghost_recorder = GhostRecorder::default();
+        self.ghost = None;
+        if self.races_ghost() && !self.is_replay() {
+            let difficulty = self.config.difficulty;
+            self.ghost = Ghost::load(self.level).filter(|ghost| ghost.seed == seed && ghost.difficulty == difficulty);
+        }
+        self.message = None;
+        self.dialogue = None;
+        // 经典闯关每关随机几个附加目标，剧情关卡之后换成关卡文件里的目标
+        self.objectives = if self.config.optional_objectives && self.mode == GameMode::Classic && !level.bonus && self.tutorial.is_none() {
+            let mut rng = StdRng::seed_from_u64(seed.wrapping_add(0x71756573));
+            ObjectiveTracker::new(objectives::roll(&mut rng, self.level), target)
+        } else {
+            ObjectiveTracker::default()
+        };
+        self.contract_offer = None;
+        self.contract = None;
+        self.stats.start_run(seed, self.level);
+        self.throw_charge = 0.0;
+        self.charging = false;
+        self.cable_oil = self.config.max_cable_oil;
+        self.winch_fuel = self.config.max_winch_fuel;
+        self.drill = Drill::new(&self.config);
+        self.dynamite = self.config.starting_dynamite + std::mem::take(&mut self.extra_dynamite);
+        self.keys = 0;
+        let wares = std::mem::take(&mut self.next_level_wares);
+        self.buffs = Buffs::default();
+        for ware in wares {
+            match ware {
+                Ware::StrengthDrink => self.buffs.add(BuffKind::Strength, None),
+                Ware::LuckyClover => self.buffs.add(BuffKind::Luck, None),
+                Ware::Magnet => self.buffs.add(BuffKind::Magnet, Some(MAGNET_SECONDS * TICKS_PER_SECOND as u64)),
+                _ => (),
+            }
+        }
+        self.combo = Combo::new(self.config.combo_seconds * TICKS_PER_SECOND as u64, self.config.combo_step);
+        self.projectiles.clear();
+        self.fuses.clear();
+        self.boss = if level.boss { Some(Boss::spawn(&self.config)) } else { None };
+        if let Some(boss) = &self.boss {
+            self.message = Some(format!("Boss level! Hook the golden pig {} times to catch it", boss.health));
+        }
+        self.saws = SawBlades::roll(seed, &self.config);
+        self.bats = Bats::roll(seed, &self.config);
+        self.portals = Portals::roll(seed, &self.config);
+        self.conveyors = Conveyors::roll(seed, &self.config, &mut self.items);
+        gravity::link_supports(&mut self.items);
+        self.terrain = Terrain::roll(seed, &self.config, &self.items);
+        self.water = Water::new(seed, &self.config, level.world.theme());
+        self.quakes = Earthquakes::new(seed, &self.config);
+        self.powerups = PowerUps::new(seed, &self.config);
+        let ground = self.miner.position.y + self.miner.height / 2.0;
+        self.rock_fall = RockFall::new(seed, &self.config, ground);
+        self.moles = MoleWarren::roll(seed, &self.config, self.level, level.world.theme().moles);
+        self.market = Market::roll(seed, self.level, self.config.market_volatility);
+        // 奖励关只有金子：没有锯片、蝙蝠、地震、落石和地鼠
+        if level.bonus {
+            self.saws = SawBlades::default();
+            self.bats = Bats::default();
+            self.quakes = Earthquakes::default();
+            self.rock_fall = RockFall::default();
+            self.moles = MoleWarren::default();
+            self.message = Some("Bonus stage! Grab as much gold as you can".to_string());
+        }
+        // 教学关和沙盒没有危险物和道具；教学关还换成固定布局，也没有泥土和传送带
+        if self.tutorial.is_some() || self.mode == GameMode::Sandbox {
+            self.powerups = PowerUps::default();
+            self.saws = SawBlades::default();
+            self.bats = Bats::default();
+            self.quakes = Earthquakes::default();
+            self.rock_fall = RockFall::default();
+            self.moles = MoleWarren::default();
+            self.boss = None;
+            self.message = None;
+        }
+        if self.tutorial.is_some() {
+            self.items = tutorial::layout(&self.config);
+            self.secrets = Secrets::new(&self.items);
+            self.terrain = Terrain::default();
+            self.conveyors = Conveyors::default();
+            self.portals = Portals::default();
+        }
+        for hook in self.hooks.iter_mut() {
+            hook.portals = self.portals.ends();
+        }
+        let view = self.plugin_view();
+        self.plugins.level_start(&view);
+        // 还债战役的新周目按周目加成价值，回放时周目取自文件头；
+        // 剧情关卡的回放按文件头重新载入固定布局，按录制时的声望调整
+        self.round_prestige = (self.config.new_game_plus > 0).then(|| Prestige::new_game_plus(self.config.new_game_plus));
+        let story_level = self.input.replay().and_then(|replay| Some((replay.header.story?, replay.header.prestige)));
+        if let Some((index, prestige)) = story_level {
+            match StoryManifest::load(Path::new(story::STORY_FILE)) {
+                Ok(manifest) if index < manifest.levels.len() => {
+                    self.setup_story_level(index, &manifest.levels[index], Prestige { level: prestige, new_game_plus: 0 });
                 }
-                self.hook.attached_item = None;
+                Ok(_) => self.message = Some(format!("Story level {} is missing from {}", index + 1, story::STORY_FILE)),
+                Err(err) => self.message = Some(format!("Failed to load {}: {}", story::STORY_FILE, err)),
             }
         }
+    }
 
-        // 更新钩子的起始位置为矿工位置
-        self.hook.position.x = self.miner.position.x + self.hook.angle.cos() * self.hook.length;
-        self.hook.position.y = self.miner.position.y + self.hook.angle.sin() * self.hook.length;
+    // 本关的风，正数向右吹；按种子决定，回放时完全一致
+    fn roll_wind(seed: u64, config: &GameConfig) -> f32 {
+        if config.max_wind <= 0.0 {
+            return 0.0;
+        }
+        StdRng::seed_from_u64(seed.wrapping_add(0x77696e64)).gen_range(-config.max_wind..=config.max_wind)
     }
 
-    // 绘制游戏
-    fn draw(&mut self, ctx: &mut Context, graphics: &mut graphics::GraphicsContext) -> GameResult {
-        graphics::clear(ctx, Color::new(0.0, 0.2, 0.4, 1.0)); // 深蓝色背景
+    // 注册玩法插件
+    fn register_plugin(&mut self, plugin: Box<dyn GamePlugin>) {
+        self.plugins.register(plugin);
+    }
 
-        // 绘制矿工
-        let miner_rect = Rect::new(
-            self.miner.position.x - self.miner.width / 2.0,
-            self.miner.position.y - self.miner.height / 2.0,
-            self.miner.width,
-            self.miner.height,
-        );
-        graphics::rectangle(
-            ctx,
-            graphics::DrawParam::default().dest(miner_rect.point()),
-            &miner_rect,
-            Color::new(0.8, 0.5, 0.3, 1.0), // 棕色矿工
-        )?;
+    fn register_telemetry(&mut self) {
+        let plugin = Telemetry::new(&self.config.telemetry_endpoint, self.config.telemetry_batch_size);
+        self.register_plugin(Box::new(plugin));
+    }
 
-        // 绘制钩子
-        if self.hook.length > 0.0 {
-            let start = na::Point2::new(self.miner.position.x, self.miner.position.y);
-            let end = na::Point2::new(self.hook.position.x, self.hook.position.y);
-            
-            // 绘制绳子
-            graphics::line(
-                ctx,
-                graphics::DrawParam::default(),
-                &[start, end],
-                2.0,
-                Color::new(0.5, 0.3, 0.1, 1.0), // 棕色绳子
-            )?;
+    // 主菜单里开关匿名遥测，并写回配置文件
+    fn toggle_telemetry(&mut self) {
+        self.config.telemetry = !self.config.telemetry;
+        if self.config.telemetry {
+            self.register_telemetry();
+        } else {
+            self.plugins.unregister("telemetry");
+        }
+        if let Err(err) = GameConfig::save_setting("telemetry", &self.config.telemetry.to_string()) {
+            self.message = Some(format!("Failed to save telemetry setting: {}", err));
+        }
+    }
 
-            // 绘制钩子
-            let hook_rect = Rect::new(
-                end.x - 5.0,
-                end.y - 5.0,
-                10.0,
-                10.0,
-            );
-            graphics::rectangle(
-                ctx,
-                graphics::DrawParam::default().dest(hook_rect.point()),
-                &hook_rect,
-                Color::new(0.7, 0.7, 0.7, 1.0), // 灰色钩子
-            )?;
+    // 提供给插件的只读快照
+    fn plugin_view(&self) -> PluginView {
+        PluginView {
+            seed: self.seed,
+            level: self.level,
+            tick: self.tick,
+            score: self.level_score,
+            target: self.level_target(),
+            replaying: self.is_replay(),
+            screen_width: self.config.screen_width,
+            screen_height: self.config.screen_height,
+        }
+    }
 
-            // 如果钩子附着了物品，绘制物品
-            if let Some(item_idx) = self.hook.attached_item {
-                if item_idx < self.items.len() {
-                    let item = &self.items[item_idx];
-                    let item_size = item.size();
-                    let item_rect = Rect::new(
-                        end.x - item_size / 2.0,
-                        end.y - item_size / 2.0,
-                        item_size,
-                        item_size,
-                    );
-                    graphics::rectangle(
-                        ctx,
-                        graphics::DrawParam::default().dest(item_rect.point()),
-                        &item_rect,
-                        item.color(),
-                    )?;
-                }
-            }
-        }
-
-        // 绘制物品
-        for item in &self.items {
-            if !item.collected {
-                let item_size = item.size();
-                let item_rect = Rect::new(
-                    item.position.x - item_size / 2.0,
-                    item.position.y - item_size / 2.0,
-                    item_size,
-                    item_size,
-                );
-                graphics::rectangle(
-                    ctx,
-                    graphics::DrawParam::default().dest(item_rect.point()),
-                    &item_rect,
-                    item.color(),
-                )?;
+    // 本关的目标分：剧情关卡的目标，或还债战役中本关应摊的欠款
+    fn level_target(&self) -> Option<i32> {
+        if self.bonus_stage || self.tutorial.is_some() {
+            return None;
+        }
+        if let Some(story) = &self.story {
+            return Some(story.current().target);
+        }
+        match &self.campaign {
+            Some(campaign) => {
+                let levels = self.config.debt_levels_per_payment.max(1) as i64;
+                Some((campaign.next_payment() / levels) as i32)
             }
+            None => match self.mode {
+                GameMode::Classic | GameMode::Puzzle | GameMode::Coop => Some(Level::nth(self.level, &self.config).target),
+                GameMode::Endless => Some(self.endless.goal()),
+                GameMode::TimeAttack | GameMode::Daily | GameMode::Versus | GameMode::Sandbox => None,
+            },
         }
+    }
 
-        // 绘制分数和时间
-        let time_left = GAME_DURATION - (Instant::now() - self.start_time);
-        let time_left_seconds = time_left.as_secs();
-
-        let score_text = Text::new(TextFragment::new(format!("Score: {}", self.score))
-            .color(Color::WHITE)
-            .font_size(24));
-        graphics::draw(
-            ctx,
-            &score_text,
-            graphics::DrawParam::default().dest(na::Point2::new(10.0, 10.0)),
-        )?;
-
-        let time_text = Text::new(TextFragment::new(format!("Time: {}s", time_left_seconds))
-            .color(Color::WHITE)
-            .font_size(24));
-        graphics::draw(
-            ctx,
-            &time_text,
-            graphics::DrawParam::default().dest(na::Point2::new(SCREEN_WIDTH - 120.0, 10.0)),
-        )?;
-
-        // 如果游戏结束，绘制游戏结束界面
-        if self.game_over {
-            let game_over_text = Text::new(TextFragment::new("Game Over!")
-                .color(Color::RED)
-                .font_size(48));
-            let game_over_rect = game_over_text.dimensions(ctx)?;
-            graphics::draw(
-                ctx,
-                &game_over_text,
-                graphics::DrawParam::default().dest(na::Point2::new(
-                    SCREEN_WIDTH / 2.0 - game_over_rect.w / 2.0,
-                    SCREEN_HEIGHT / 2.0 - 50.0,
-                )),
-            )?;
-
-            let final_score_text = Text::new(TextFragment::new(format!("Final Score: {}", self.score))
-                .color(Color::WHITE)
-                .font_size(32));
-            let final_score_rect = final_score_text.dimensions(ctx)?;
-            graphics::draw(
-                ctx,
-                &final_score_text,
-                graphics::DrawParam::default().dest(na::Point2::new(
-                    SCREEN_WIDTH / 2.0 - final_score_rect.w / 2.0,
-                    SCREEN_HEIGHT / 2.0 + 10.0,
-                )),
-            )?;
+    // 经典闯关本关的星级，其他模式不评星
+    fn level_stars(&self) -> Option<u32> {
+        if self.mode != GameMode::Classic || !self.is_freeplay() || self.bonus_stage || self.knocked_out() {
+            return None;
         }
-
-        graphics::present(ctx)?;
-        Ok(())
+        self.level_target().map(|target| levels::stars(self.level_score, target))
     }
-}
 
-impl EventHandler for GameState {
-    fn update(&mut self, _ctx: &mut Context, dt: f32) -> GameResult {
-        self.update(dt);
-        Ok(())
+    // 自由模式（不在战役或剧情中）
+    fn is_freeplay(&self) -> bool {
+        self.campaign.is_none() && self.story.is_none()
     }
 
-    fn draw(&mut self, ctx: &mut Context, graphics: &mut graphics::GraphicsContext) -> GameResult {
-        self.draw(ctx, graphics)
+    // 自由模式本关是否达标
+    fn level_cleared(&self) -> bool {
+        self.level_target().map_or(true, |target| self.level_score >= target)
     }
 
-    fn key_down_event(
-        &mut self,
-        _ctx: &mut Context,
-        keycode: KeyCode,
-        _keymods: KeyMods,
-        _repeat: bool,
-    ) -> GameResult {
-        if self.game_over {
-            return Ok(());
+    // 生成物品：总价值要够达到目标分并留出余量，开局钩子够得着的地方至少有一件贵重物品；
+    // 重抽几次仍不满足时直接修补布局，避免限时关卡开局就注定打不过
+    fn spawn_items(config: &GameConfig, rng: &mut StdRng, target: Option<i32>, level: &Level) -> Vec<Item> {
+        let origin = na::Point2::new(config.screen_width / 2.0, 50.0);
+        let required = target.map_or(0, |target| (target as f32 * config.spawn_value_margin) as i32);
+        let mut items = Self::roll_items(config, rng, level);
+        for _ in 1..SPAWN_REROLLS {
+            if Self::layout_ok(&items, origin, config.hook_length, required) {
+                break;
+            }
+            items = Self::roll_items(config, rng, level);
         }
+        Self::patch_layout(&mut items, origin, config.hook_length, required);
 
-        match keycode {
-            KeyCode::Left => {
-                self.miner.move_left();
-            }
-            KeyCode::Right => {
-                self.miner.move_right();
+        // 缆绳油规则下，在较深处放几个油桶
+        if config.cable_oil {
+            let item_size = config.item_size;
+            for _ in 0..config.oil_can_count {
+                let x = rng.gen_range(item_size..config.screen_width - item_size);
+                let y = rng.gen_range(config.mine_depth / 2.0..config.mine_depth - item_size);
+                items.push(Item::new(ItemType::OilCan, x, y, item_size));
             }
-            KeyCode::Space => {
-                // 计算钩子发射角度（基于鼠标位置）
-                let mouse_pos = _ctx.mouse.position();
-                let angle = (mouse_pos.y - self.miner.position.y).atan2(mouse_pos.x - self.miner.position.x);
-                self.hook.throw(angle);
+        }
+
+        // 绞盘燃料规则下，在较深处放几个油罐
+        if config.winch_fuel {
+            let item_size = config.item_size;
+            for _ in 0..config.fuel_can_count {
+                let x = rng.gen_range(item_size..config.screen_width - item_size);
+                let y = rng.gen_range(config.mine_depth / 2.0..config.mine_depth - item_size);
+                items.push(Item::new(ItemType::FuelCan, x, y, item_size));
             }
-            _ => (),
         }
 
-        Ok(())
-    }
-}
+        // 神秘袋和炸药桶不计入布局价值，放在修补之后
+        let item_size = config.item_size;
+        for _ in 0..config.mystery_bag_count {
+            let x = rng.gen_range(item_size..config.screen_width - item_size);
+            let y = rng.gen_range(150.0..config.mine_depth - item_size);
+            items.push(Item::new(ItemType::MysteryBag, x, y, item_size));
+        }
+        let tnt_count = if level.bonus { 0 } else { config.tnt_count * level.world.theme().tnt_factor };
+        for _ in 0..config.profile().hazards(tnt_count) {
+            let x = rng.gen_range(item_size..config.screen_width - item_size);
+            let y = rng.gen_range(150.0..config.mine_depth - item_size);
+            items.push(Item::new(ItemType::TntBarrel, x, y, item_size));
+        }
+        // 偶尔在深处放一个时钟
+        if config.clock_chance > 0.0 && rng.gen::<f32>() < config.clock_chance {
+            let x = rng.gen_range(item_size..config.screen_width - item_size);
+            let y = rng.gen_range(config.mine_depth / 2.0..config.mine_depth - item_size);
+            items.push(Item::new(ItemType::Clock, x, y, item_size));
+        }
+        // 每个宝箱配一把钥匙，宝箱放在深处
+        for _ in 0..config.chest_count {
+            let x = rng.gen_range(item_size..config.screen_width - item_size);
+            let y = rng.gen_range(150.0..config.mine_depth - item_size);
+            items.push(Item::new(ItemType::Key, x, y, item_size));
+            let x = rng.gen_range(item_size..config.screen_width - item_size);
+            let y = rng.gen_range(config.mine_depth / 2.0..config.mine_depth - item_size);
+            items.push(Item::new(ItemType::Chest, x, y, item_size));
+        }
+        items
+    }
+
+    // 钩子从 origin 出发能否够到该物品
+    fn within_reach(item: &Item, origin: na::Point2<f32>, reach: f32) -> bool {
+        na::distance(&item.position, &origin) - item.size() / 2.0 <= reach
+    }
+
+    fn is_prize(item: &Item) -> bool {
+        item.item_type.base_value() >= ItemType::Gold(BagSize::Medium).base_value()
+    }
+
+    fn layout_ok(items: &[Item], origin: na::Point2<f32>, reach: f32, required: i32) -> bool {
+        let total: i32 = items.iter().map(|item| item.value(&ValueContext::NONE)).sum();
+        total >= required && items.iter().any(|item| Self::is_prize(item) && Self::within_reach(item, origin, reach))
+    }
+
+    // 修补布局：把离钩子最近的物品挪进范围并升为贵重物品，再从最便宜的物品开始升档直到总价值够
+    fn patch_layout(items: &mut [Item], origin: na::Point2<f32>, reach: f32, required: i32) {
+        let has_prize = items.iter().any(|item| Self::is_prize(item) && Self::within_reach(item, origin, reach));
+        if !has_prize {
+            // 骷髅和骨头升不了档，不拿来充当奖品
+            let nearest = items
+                .iter_mut()
+                .filter(|item| item.item_type.base_value() > ItemType::Bone.base_value())
+                .min_by(|a, b| na::distance(&a.position, &origin).total_cmp(&na::distance(&b.position, &origin)));
+            if let Some(item) = nearest {
+                if !Self::within_reach(item, origin, reach) {
+                    let direction = (item.position - origin).normalize();
+                    item.position = origin + direction * reach * 0.8;
+                }
+                while !Self::is_prize(item) && item.upgrade() {}
+            }
+        }
+
+        while items.iter().map(|item| item.value(&ValueContext::NONE)).sum::<i32>() < required {
+            let cheapest = items
+                .iter_mut()
+                .filter(|item| matches!(item.item_type, ItemType::Rock | ItemType::Silver | ItemType::Gold(_)))
+                .min_by_key(|item| item.value(&ValueContext::NONE));
+            match cheapest {
+                Some(item) => {
+                    item.upgrade();
+                }
+                None => break, // 全是钻石仍不够，只能如此
+            }
+        }
+    }
+
+    // 随机抽一套物品布局，关卡越靠后物品越多、品质越好，越深的物品越稀有越值钱
+    fn roll_items(config: &GameConfig, rng: &mut StdRng, level: &Level) -> Vec<Item> {
+        let spawner = DepthSpawner::new(config, level.world.theme());
+        let mut items = Vec::new();
+        while items.len() < level.item_count {
+            // 偶尔生成一条成串的金银矿脉
+            if !level.bonus && config.vein_chance > 0.0 && rng.gen::<f32>() < config.vein_chance {
+                items.extend(spawner.spawn_vein(rng, level.item_count - items.len()));
+                continue;
+            }
+            let mut item = if level.bonus { spawner.spawn_gold(rng) } else { spawner.spawn(rng) };
+            // 第一关不额外取随机数
+            if level.upgrade_chance > 0.0 && rng.gen::<f32>() < level.upgrade_chance {
+                item.upgrade();
+            }
+            items.push(item);
+        }
+        items
+    }
+
+    // 当前生效的价值升级
+    fn value_context(&self) -> ValueContext {
+        ValueContext {
+            luck: self.buffs.has(BuffKind::Luck),
+        }
+    }
+
+    // 当前生效的结算修正，按套用顺序排列
+    fn score_modifiers(&self) -> Vec<&dyn ScoreModifier> {
+        let mut modifiers: Vec<&dyn ScoreModifier> = vec![&self.market, &self.combo];
+        if self.rock_book {
+            modifiers.push(&RockBook);
+        }
+        if self.gem_polish {
+            modifiers.push(&GemPolish);
+        }
+        if let Some(prestige) = &self.round_prestige {
+            modifiers.push(prestige);
+        }
+        if self.buffs.has(BuffKind::Double) {
+            modifiers.push(&DoubleScore);
+        }
+        modifiers
+    }
+
+    // 物品拉回时的结算价值：在升级后的价值上依次套用结算修正
+    fn bank_value(&self, item: &Item) -> i32 {
+        let value = item.value(&self.value_context()) as f32;
+        modifiers::apply_all(&self.score_modifiers(), item.item_type, value).round() as i32
+    }
+
+    // 剩余帧数，无尽模式下为距离下一个检查点的帧数
+    fn ticks_left(&self) -> u64 {
+        // 教学关不计时，教完且钩子都收回时结束
+        if let Some(tutorial) = &self.tutorial {
+            let idle = self.hooks.iter().all(|hook| hook.state == HookState::Idle && hook.attached_item.is_none());
+            return if tutorial.finished() && idle { 0 } else { u64::MAX };
+        }
+        match self.mode {
+            GameMode::Sandbox => u64::MAX,
+            GameMode::Classic | GameMode::Daily | GameMode::Puzzle | GameMode::Versus | GameMode::Coop => {
+                (self.duration_ticks + self.bonus_ticks).saturating_sub(self.tick)
+            }
+            GameMode::Endless => (self.endless.deadline() + self.bonus_ticks).saturating_sub(self.tick),
+            GameMode::TimeAttack if self.mine_cleared() => 0,
+            GameMode::TimeAttack => {
+                (TIME_ATTACK_LIMIT_SECONDS * TICKS_PER_SECOND as u64 + self.bonus_ticks).saturating_sub(self.tick)
+            }
+        }
+    }
+
+    // 计时赛：场上的物品都已拉回（钩子上也没有挂着的）
+    fn mine_cleared(&self) -> bool {
+        self.hooks.iter().all(|hook| hook.attached_item.is_none())
+            && self.bats.cargo().next().is_none()
+            && self.items.iter().all(|item| item.collected)
+    }
+
+    // 解谜模式：达标或出钩次数用完，且钩子都收回结算完毕
+    fn puzzle_finished(&self) -> bool {
+        self.mode == GameMode::Puzzle
+            && (self.throws_left == 0 || self.level_cleared())
+            && self.hooks.iter().all(|hook| hook.state == HookState::Idle && hook.attached_item.is_none())
+    }
+
+    // 矿工的心被落石扣光
+    fn knocked_out(&self) -> bool {
+        self.config.miner_hearts > 0 && self.miner.hearts == 0
+    }
+
+    // 第一个空闲的钩子
+    fn idle_hook(&self) -> Option<usize> {
+        self.idle_hook_of(0)
+    }
+
+    // 某个玩家第一个可以出钩的钩子
+    fn idle_hook_of(&self, player: usize) -> Option<usize> {
+        self.hooks.iter().position(|hook| hook.player == player && hook.ready())
+    }
+
+    // 第 i 个钩子的绳子起点：出钩玩家的矿车
+    fn hook_origin(&self, i: usize) -> na::Point2<f32> {
+        match &self.rival {
+            Some(rival) if self.hooks[i].player == RIVAL => rival.miner.position,
+            _ => self.miner.position,
+        }
+    }
+
+    // 拉回的物品记到出钩玩家的得分上，合作时两人共用关卡得分；骷髅倒扣钱，但得分不会扣成负数
+    fn credit(&mut self, player: usize, value: i32) {
+        match self.rival.as_mut() {
+            Some(rival) if player == RIVAL && self.mode == GameMode::Versus => rival.score = (rival.score + value).max(0),
+            _ => self.level_score = (self.level_score + value).max(0),
+        }
+    }
+
+    // 某个玩家的钩子正拖回来的物品总重量
+    fn carried_weight(&self, player: usize) -> f32 {
+        self.hooks
+            .iter()
+            .filter(|hook| hook.player == player && hook.state == HookState::Retracting && hook.attached_item.is_some())
+            .map(|hook| hook.load_weight)
+            .sum()
+    }
+
+    // 用掉一份保险，只有玩家一买得到保险
+    fn claim_insurance(&mut self, player: usize) -> bool {
+        if player != 0 || self.insurance == 0 {
+            return false;
+        }
+        self.insurance -= 1;
+        self.message = Some("Insurance paid out!".to_string());
+        true
+    }
+
+    // 倒扣钱的物品有保险时不扣
+    fn insure(&mut self, player: usize, value: i32) -> i32 {
+        if value < 0 && self.claim_insurance(player) {
+            0
+        } else {
+            value
+        }
+    }
+
+    // 电脑对手看到的场面：没拉回也没埋着的物品，以及它自己的矿车和钩子
+    fn ai_board(&self) -> Board {
+        let hook = self.hooks.iter().find(|hook| hook.player == RIVAL);
+        Board {
+            targets: self
+                .items
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| !item.collected && !self.terrain.buried(item.position))
+                .map(|(index, item)| Target {
+                    index,
+                    position: item.position,
+                    value: item.value,
+                    weight: item.weight(),
+                })
+                .collect(),
+            origin: self.rival.as_ref().map_or(self.miner.position, |rival| rival.miner.position),
+            angle: hook.map_or(std::f32::consts::FRAC_PI_2, |hook| hook.angle),
+            arc: hook.and_then(|hook| hook.swing).map_or(0.0, |(_, arc)| arc),
+            reach: hook.map_or(self.config.hook_length, |hook| hook.max_length),
+            ready: hook.map_or(false, |hook| hook.ready()),
+        }
+    }
+
+    // 执行玩家二的输入：只能开矿车和出钩
+    fn apply_rival_action(&mut self, action: InputAction) {
+        let rival = match self.rival.as_mut() {
+            Some(rival) => rival,
+            None => return,
+        };
+        match action {
+            InputAction::MoveLeft => rival.miner.move_left(),
+            InputAction::MoveRight => rival.miner.move_right(),
+            InputAction::Coast => rival.miner.coast(),
+            InputAction::Throw { angle, power } => {
+                if let Some(i) = self.idle_hook_of(RIVAL) {
+                    self.hooks[i].throw(angle, power);
+                    self.events.emit(GameEvent::HookThrown { player: RIVAL, angle });
+                }
+            }
+            _ => (),
+        }
+    }
+
+    // 场上还没拉回的物品数
+    fn items_left(&self) -> usize {
+        self.items.iter().filter(|item| !item.collected).count()
+    }
+
+    // 无尽模式和对战：场上值钱的物品不到一半时补充一批，随机数由种子和帧号决定；
+    // 对战时新一波按分差给落后一方那半边的物品升档
+    fn replenish_items(&mut self) {
+        let remaining = self
+            .items
+            .iter()
+            .filter(|item| !item.collected && item.item_type.base_value() > 0)
+            .count();
+        if remaining >= self.config.item_count / 2 {
+            return;
+        }
+        let mut rng = StdRng::seed_from_u64(self.seed ^ self.tick.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+        let nth = if self.mode == GameMode::Endless { self.endless.checkpoint + 1 } else { self.level };
+        let level = Level {
+            item_count: self.config.item_count / 2,
+            world: self.world,
+            ..Level::nth(nth, &self.config)
+        };
+        let mut wave = Self::roll_items(&self.config, &mut rng, &level);
+        if let Some(rival) = self.rival.as_ref().filter(|_| self.mode == GameMode::Versus) {
+            rubber_band::apply(&mut wave, (self.level_score, rival.score), &self.config, &mut rng);
+        }
+        self.items.extend(wave);
+        gravity::link_supports(&mut self.items);
+        self.background.invalidate();
+    }
+
+    // 执行一个输入动作
+    fn apply_action(&mut self, action: InputAction) {
+        match action {
+            InputAction::MoveLeft => self.miner.move_left(),
+            InputAction::MoveRight => self.miner.move_right(),
+            InputAction::Coast => self.miner.coast(),
+            InputAction::Throw { angle, power } => {
+                // 交给第一个空闲的钩子
+                let cost = self.config.stamina_throw_cost;
+                let idle = self.idle_hook();
+                // 解谜模式出钩次数用完就不能再出钩
+                let out_of_throws = self.mode == GameMode::Puzzle && self.throws_left == 0;
+                if let Some(i) = idle.filter(|_| !self.drill.busy() && !out_of_throws) {
+                    if !self.config.stamina || self.miner.spend_stamina(cost) {
+                        // 缆绳油规则下，剩余的油决定这一钩能伸多深
+                        if self.config.cable_oil {
+                            let reach = self.cable_oil / self.config.cable_oil_per_pixel;
+                            self.hooks[i].max_length = self.config.hook_length.min(reach);
+                        }
+                        self.hooks[i].throw(angle, power);
+                        if self.mode == GameMode::Puzzle {
+                            self.throws_left -= 1;
+                        }
+                        self.events.emit(GameEvent::HookThrown { player: 0, angle });
+                    }
+                }
+            }
+            InputAction::Drill => {
+                let unlocked = match self.input.replay() {
+                    Some(replay) => replay.header.loadout.drill,
+                    None => self.drill_unlocked,
+                };
+                if unlocked && self.hooks.iter().all(|hook| hook.state == HookState::Idle) {
+                    self.drill.start(self.miner.position);
+                }
+            }
+            InputAction::LobDynamite { x, y } => {
+                if self.dynamite > 0 {
+                    self.dynamite -= 1;
+                    let target = na::Point2::new(x, y);
+                    self.projectiles.push(DynamiteProjectile::lob(self.miner.position, target));
+                }
+            }
+            InputAction::BlastCatch => self.blast_catch(),
+        }
+    }
+
+    // 炸掉正在拖回的物品：物品不计分，钩子立即收回；两个钩子都在拖时炸先出的那个
+    fn blast_catch(&mut self) {
+        let i = match self
+            .hooks
+            .iter()
+            .position(|hook| hook.player == 0 && hook.state == HookState::Retracting && hook.attached_item.is_some())
+        {
+            Some(i) => i,
+            None => return,
+        };
+        if self.dynamite == 0 {
+            return;
+        }
+        self.dynamite -= 1;
+        let position = self.hooks[i].position;
+        if let Some(item_idx) = self.hooks[i].snap_back() {
+            let radius = self.items[item_idx].size() / 2.0;
+            self.events.emit(GameEvent::ExplosionTriggered { position, radius });
+        }
+    }
+
+    // 在某处引爆：半径内的杂物（石头）被炸掉，为后面的宝物清出通路
+    fn explode(&mut self, position: na::Point2<f32>, radius: f32) {
+        self.blast(position, radius, |item| item.item_type == ItemType::Rock);
+    }
+
+    // 炸掉半径内满足条件的物品，返回被炸掉的数量
+    fn blast(&mut self, position: na::Point2<f32>, radius: f32, destroys: impl Fn(&Item) -> bool) -> usize {
+        let mut destroyed = 0;
+        for item in self.items.iter_mut() {
+            if !item.collected
+                && destroys(item)
+                && na::distance(&item.position, &position) <= radius + item.size() / 2.0
+            {
+                item.collected = true;
+                destroyed += 1;
+            }
+        }
+        self.events.emit(GameEvent::ExplosionTriggered { position, radius });
+        destroyed
+    }
+
+    // 第 i 个钩子钩到炸药桶：原地爆炸，半径内的物品全部炸毁，钩子空手收回
+    fn detonate_barrel(&mut self, i: usize) {
+        let position = self.hooks[i].position;
+        if self.hooks[i].snap_back().is_some() {
+            self.barrel_blast(position);
+            if self.mode == GameMode::Coop && self.config.coop_friendly_fire {
+                self.blast_ropes(position, self.config.tnt_radius, self.hooks[i].player);
+            }
+        }
+    }
+
+    // 碎石增益：钩到的石头当场粉碎，只给一点分，钩子空手快速收回
+    fn shatter_rock(&mut self, i: usize) {
+        let (position, player) = (self.hooks[i].position, self.hooks[i].player);
+        if self.hooks[i].attached_item.take().is_none() {
+            return;
+        }
+        self.hooks[i].load_weight = 1.0;
+        self.credit(player, SHATTER_VALUE);
+        self.events.emit(GameEvent::RockShattered {
+            value: SHATTER_VALUE,
+            position,
+        });
+    }
+
+    // 炸药桶爆炸：半径内的物品全部炸毁，其他炸药桶则点燃引信，稍后接着炸，一路连锁下去
+    fn barrel_blast(&mut self, position: na::Point2<f32>) {
+        let radius = self.config.tnt_radius;
+        let due = self.tick + (self.config.tnt_fuse_seconds * TICKS_PER_SECOND as f32).round() as u64;
+        for (index, item) in self.items.iter().enumerate() {
+            let lit = self.fuses.iter().any(|&(_, queued)| queued == index);
+            if !item.collected
+                && !lit
+                && item.item_type == ItemType::TntBarrel
+                && na::distance(&item.position, &position) <= radius + item.size() / 2.0
+            {
+                self.fuses.push((due, index));
+            }
+        }
+        self.blast(position, radius, |item| item.item_type != ItemType::TntBarrel);
+    }
+
+    // 引信烧完的炸药桶原地爆炸；引信烧着时被钩走的已经当场炸过了
+    fn burn_fuses(&mut self) {
+        let tick = self.tick;
+        let (due, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.fuses).into_iter().partition(|&(at, _)| at <= tick);
+        self.fuses = pending;
+        for (_, index) in due {
+            let item = &mut self.items[index];
+            if item.collected {
+                continue;
+            }
+            item.collected = true;
+            let position = item.position;
+            self.barrel_blast(position);
+        }
+    }
+
+    // 友军误伤：钩尖在爆炸半径内的队友钩子绳子被炸断，挂着的物品一起炸没
+    fn blast_ropes(&mut self, position: na::Point2<f32>, radius: f32, player: usize) {
+        let respawn = self.config.rope_respawn_seconds * TICKS_PER_SECOND as u64;
+        for hook in self.hooks.iter_mut() {
+            if hook.player == player || hook.state == HookState::Idle {
+                continue;
+            }
+            if na::distance(&hook.position, &position) <= radius {
+                hook.cut(respawn);
+                self.events.emit(GameEvent::RopeCut { position: hook.position });
+            }
+        }
+    }
+
+    // 出钩角度：摆钩模式下为钩子当前的摆动角度，否则朝向鼠标
+    fn aim_angle(&self, ctx: &Context) -> f32 {
+        if self.config.pendulum {
+            return self.hooks[self.idle_hook().unwrap_or(0)].angle;
+        }
+        let mouse = ctx.mouse.position();
+        let mouse_pos = self.camera.to_world(na::Point2::new(mouse.x, mouse.y));
+        (mouse_pos.y - self.miner.position.y).atan2(mouse_pos.x - self.miner.position.x)
+    }
+
+    // 蓄力值换算成出钩速度倍率
+    fn throw_power(&self) -> f32 {
+        let (min, max) = (self.config.min_throw_power, self.config.max_throw_power);
+        min + (max - min) * self.throw_charge
+    }
+
+    // 当前是否在观看回放
+    fn is_replay(&self) -> bool {
+        self.input.replay().is_some()
+    }
+
+    // 结算画面能否去老虎机：经典、解谜和战役里钱包有钱时
+    fn slots_available(&self) -> bool {
+        self.config.slot_machine
+            && self.game_over
+            && !self.is_replay()
+            && self.replayable()
+            && matches!(self.mode, GameMode::Classic | GameMode::Puzzle)
+            && self.wallet > 0
+    }
+
+    // 这一局能否按输入重放：双人、教学关和沙盒都有回放文件记不下的东西
+    fn replayable(&self) -> bool {
+        self.rival.is_none() && self.tutorial.is_none() && self.mode != GameMode::Sandbox
+    }
+
+    // 主菜单确认
+    fn select_menu_entry(&mut self) {
+        self.director = Director::default();
+        self.bonus_unlocked = false;
+        self.bonus_stage = false;
+        self.tutorial = None;
+        self.campaign = None;
+        self.story = None;
+        self.wallet = 0;
+        self.rock_book = false;
+        self.gem_polish = false;
+        self.hook_tier = 0;
+        self.big_claw = false;
+        self.dual_hook = false;
+        self.fireproof_rope = false;
+        self.ricochet_hook = false;
+        self.insurance = 0;
+        self.next_level_wares.clear();
+        self.mode = match self.menu_selected {
+            1 => GameMode::Endless,
+            2 => GameMode::TimeAttack,
+            3 => GameMode::Daily,
+            4 => GameMode::Puzzle,
+            8 => GameMode::Versus,
+            9 => GameMode::Coop,
+            VERSUS_CPU_ENTRY => GameMode::Versus,
+            11 => GameMode::Sandbox,
+            _ => GameMode::Classic,
+        };
+        self.versus_cpu = self.menu_selected == VERSUS_CPU_ENTRY;
+        match self.menu_selected {
+            0 => {
+                self.level_select = Some(LevelSelect::new(&self.records, self.difficulty));
+                self.scene = Scene::LevelSelect;
+            }
+            1..=4 | 6 | 8..=11 => {
+                if self.menu_selected == 6 {
+                    self.campaign = Some(DebtCampaign::new(&self.config));
+                }
+                self.level = 1;
+                let seed = if self.mode == GameMode::Daily {
+                    self.daily_day = modes::today();
+                    self.level = modes::daily_level(self.daily_day);
+                    modes::daily_seed(self.daily_day)
+                } else {
+                    thread_rng().gen()
+                };
+                self.start_round(seed, Box::new(KeyboardInput::new()));
+                self.scene = Scene::Playing;
+                if self.campaign.is_some() {
+                    self.contract_offer = Some(ContractOffer::roll(self.level, &mut thread_rng()));
+                }
+            }
+            5 => match StoryManifest::load(Path::new(story::STORY_FILE)) {
+                Ok(manifest) => {
+                    self.story = Some(StoryRun::resume(manifest));
+                    self.scene = Scene::Playing;
+                    self.start_story_level();
+                }
+                Err(err) => self.message = Some(format!("Failed to load {}: {}", story::STORY_FILE, err)),
+            },
+            7 => self.scene = Scene::Achievements,
+            _ => self.open_replay(),
+        }
+    }
+
+    // 经典闯关自由模式的普通关卡和本关最佳成绩的幽灵比
+    fn races_ghost(&self) -> bool {
+        self.config.ghost
+            && self.persist
+            && self.mode == GameMode::Classic
+            && self.is_freeplay()
+            && !self.bonus_stage
+            && self.tutorial.is_none()
+    }
+
+    // 新开一关的种子：和幽灵比时沿用幽灵那一局的种子，布局才一样
+    fn level_seed(&self) -> u64 {
+        if !self.races_ghost() {
+            return thread_rng().gen();
+        }
+        match Ghost::load(self.level) {
+            Some(ghost) if ghost.difficulty == self.difficulty => ghost.seed,
+            _ => thread_rng().gen(),
+        }
+    }
+
+    // 从选关地图开始经典闯关的某一关
+    fn start_selected_level(&mut self, level: u32) {
+        self.level_select = None;
+        self.level = level;
+        self.bonus_unlocked = false;
+        self.bonus_stage = false;
+        // 第一次打第一关先进教学关
+        let first_time = self.records.best(tutorial::TUTORIAL_RECORD).is_none();
+        self.tutorial = (self.config.tutorial && level == 1 && first_time).then(Tutorial::new);
+        self.start_round(self.level_seed(), Box::new(KeyboardInput::new()));
+        self.scene = Scene::Playing;
+    }
+
+    // 在选关地图上按 T 重打教学关
+    fn start_tutorial(&mut self) {
+        self.level_select = None;
+        self.level = 1;
+        self.bonus_unlocked = false;
+        self.bonus_stage = false;
+        self.tutorial = Some(Tutorial::new());
+        self.start_round(thread_rng().gen(), Box::new(KeyboardInput::new()));
+        self.scene = Scene::Playing;
+    }
+
+    // 从崩溃快照继续：按快照里的输入重新模拟到崩溃前的那一帧，再交还键盘
+    fn resume_from_crash(&mut self) {
+        let (replay, run) = match self.crash_resume.take() {
+            Some(resume) => resume,
+            None => return,
+        };
+        crash::discard_resume();
+        // 升级、剧情关卡和周目由回放文件头在开局时恢复，钱包和战役进度来自快照
+        self.campaign = run.campaign;
+        self.wallet = run.wallet;
+        self.story = None;
+        if let Some(index) = replay.header.story {
+            match StoryManifest::load(Path::new(story::STORY_FILE)) {
+                Ok(manifest) if index < manifest.levels.len() => {
+                    let mut story = StoryRun::resume(manifest);
+                    story.index = index;
+                    self.story = Some(story);
+                }
+                Ok(_) => self.message = Some(format!("Story level {} is missing from {}", index + 1, story::STORY_FILE)),
+                Err(err) => self.message = Some(format!("Failed to load {}: {}", story::STORY_FILE, err)),
+            }
+        }
+        self.tutorial = None;
+        self.level = replay.header.level;
+        self.scene = Scene::Playing;
+        let target = replay.header.duration;
+        self.start_round(replay.header.seed, Box::new(ReplayPlayer::new(replay.clone())));
+        while self.tick < target && !self.game_over {
+            self.update(1.0 / TICKS_PER_SECOND as f32);
+        }
+
+        let mut recording = replay;
+        recording.header.duration = 0;
+        self.recording = recording;
+        self.input = Box::new(KeyboardInput::new());
+        self.message = Some("Resumed where you left off".to_string());
+    }
+
+    // 通关剧情战役后重置进度，提升声望等级
+    fn prestige_reset(&mut self) {
+        if !StoryProgress::load().completed {
+            self.message = Some("Finish the story campaign to prestige".to_string());
+            return;
+        }
+        self.prestige.level += 1;
+        let saved = self.prestige.save().and_then(|_| StoryProgress::default().save());
+        self.message = Some(match saved {
+            Ok(()) => format!("Prestige {}! Items are heavier and time is shorter - but worth more.", self.prestige.level),
+            Err(err) => format!("Failed to save prestige: {}", err),
+        });
+    }
+
+    // 开始剧情战役的当前关卡，固定布局的关卡替换随机物品
+    fn start_story_level(&mut self) {
+        let (index, level, narration, cutscene, dialogue) = match &self.story {
+            Some(story) => (
+                story.index,
+                story.current().clone(),
+                story.narration(),
+                story.chapter_cutscene(),
+                story.chapter_dialogue(),
+            ),
+            None => return,
+        };
+        self.level = index as u32 + 1;
+        self.start_round(level.seed, Box::new(KeyboardInput::new()));
+        self.setup_story_level(index, &level, self.prestige.clone());
+        self.message = narration;
+        if let Some(name) = dialogue {
+            self.open_dialogue(&name);
+        }
+        if let Some(name) = cutscene {
+            self.play_cutscene(&name, Scene::Playing);
+        }
+    }
+
+    // 剧情关卡在普通开局之上换成关卡文件里的布局、目标和炸药，并按声望调整；
+    // 声望越高，时间越短、物品越沉，价值也越高。录制和回放都走这里，关卡序号和声望写进回放文件头
+    fn setup_story_level(&mut self, index: usize, level: &StoryLevel, prestige: Prestige) {
+        self.objectives = ObjectiveTracker::new(level.objectives.clone(), Some(level.target));
+        self.dynamite += level.dynamite;
+        self.duration_ticks = prestige.duration_ticks(self.duration_ticks);
+        for hook in self.hooks.iter_mut() {
+            hook.heaviness = prestige.heaviness();
+        }
+        if let Some(items) = level.layout_items(self.config.item_size) {
+            self.items = items;
+            gravity::link_supports(&mut self.items);
+        }
+        self.recording.header.story = Some(index);
+        self.recording.header.prestige = prestige.level;
+        self.round_prestige = Some(prestige);
+    }
+
+    // 对话框打开时由它处理按键：上下选择，空格/回车确认
+    fn dialogue_key(&mut self, keycode: KeyCode) -> bool {
+        let dialogue = match self.dialogue.as_mut() {
+            Some(dialogue) => dialogue,
+            None => return false,
+        };
+        match keycode {
+            KeyCode::Up => dialogue.select_previous(),
+            KeyCode::Down => dialogue.select_next(),
+            KeyCode::Space | KeyCode::Return => {
+                dialogue.advance(&self.strings);
+                if dialogue.finished() {
+                    self.dialogue = None;
+                }
+            }
+            _ => (),
+        }
+        true
+    }
+
+    // 打开对话框，脚本缺失或有误时跳过
+    fn open_dialogue(&mut self, name: &str) {
+        match DialogueBox::load(name) {
+            Ok(dialogue) => self.dialogue = Some(dialogue),
+            Err(err) => self.message = Some(format!("Failed to open dialogue {}: {}", name, err)),
+        }
+    }
+
+    // 播放过场动画，播完后切换到 next；脚本缺失或有误时直接切换
+    fn play_cutscene(&mut self, name: &str, next: Scene) {
+        match Cutscene::load(name) {
+            Ok(cutscene) => {
+                self.cutscene = Some(cutscene);
+                self.scene = Scene::Cutscene;
+                self.after_cutscene = next;
+            }
+            Err(err) => {
+                self.message = Some(format!("Failed to play cutscene {}: {}", name, err));
+                self.scene = next;
+            }
+        }
+    }
+
+    fn end_cutscene(&mut self) {
+        self.cutscene = None;
+        self.scene = std::mem::replace(&mut self.after_cutscene, Scene::MainMenu);
+    }
+
+    // 一关结束后继续：自由模式和剧情战役进入下一关或重试，还债战役进入商店
+    fn next_level(&mut self) {
+        if self.is_replay() {
+            return;
+        }
+        if let Some(story) = self.story.as_mut() {
+            // 通关：播放结局后回到主菜单
+            if story.ended {
+                let ending = story.manifest.ending.clone();
+                self.story = None;
+                self.message = Some("Campaign complete! Press P on the menu to prestige".to_string());
+                match ending {
+                    Some(name) => self.play_cutscene(&name, Scene::MainMenu),
+                    None => self.scene = Scene::MainMenu,
+                }
+                return;
+            }
+            story.advance();
+            self.start_story_level();
+            return;
+        }
+        if self.is_freeplay() {
+            // 自由模式：达标进入下一关，未达标重打本关；计时赛总是重跑同一关，成绩才可比。
+            // 奖励关插在两关之间，打完直接进入已经解锁的下一关
+            let after_bonus = std::mem::take(&mut self.bonus_stage);
+            let after_tutorial = self.tutorial.take().is_some();
+            if !after_bonus && !after_tutorial && matches!(self.mode, GameMode::Classic | GameMode::Puzzle | GameMode::Coop) && self.level_cleared() {
+                self.level += 1;
+                self.bonus_stage = std::mem::take(&mut self.bonus_unlocked);
+            }
+            // 每日挑战重玩当天的同一个布局，有幽灵的关卡沿用幽灵的布局
+            let seed = match self.mode {
+                GameMode::Daily => modes::daily_seed(self.daily_day),
+                _ => self.level_seed(),
+            };
+            self.start_round(seed, Box::new(KeyboardInput::new()));
+            return;
+        }
+        if self.campaign.as_ref().map_or(false, |c| c.completed) {
+            self.start_new_game_plus();
+            return;
+        }
+        if self.campaign.as_ref().map_or(true, |c| c.failed) {
+            return;
+        }
+        self.open_shop();
+    }
+
+    // 还债战役通关后开新周目：买下的升级保留，钱包和一次性道具清空，从第一关重新开始
+    fn start_new_game_plus(&mut self) {
+        let campaign = match &self.campaign {
+            Some(campaign) => campaign.new_game_plus(&self.config),
+            None => return,
+        };
+        let plus = campaign.plus;
+        self.campaign = Some(campaign);
+        self.wallet = 0;
+        self.extra_dynamite = 0;
+        self.insurance = 0;
+        self.next_level_wares.clear();
+        self.level = 1;
+        self.start_round(thread_rng().gen(), Box::new(KeyboardInput::new()));
+        self.contract_offer = Some(ContractOffer::roll(self.level, &mut thread_rng()));
+        self.message = Some(format!("New Game Plus {}: tougher mines, but everything is worth more", plus));
+        // 记下走到的最高周目
+        if self.persist && self.records.submit(campaign::NEW_GAME_PLUS_RECORD, plus as i64, true) {
+            if let Err(err) = self.records.save() {
+                self.message = Some(format!("Failed to save records: {}", err));
+            }
+        }
+    }
+
+    // 还债战役关卡之间进入商店
+    fn open_shop(&mut self) {
+        let mut wares = vec![Ware::Dynamite, Ware::StrengthDrink, Ware::LuckyClover, Ware::Magnet, Ware::Insurance];
+        if !self.rock_book {
+            wares.push(Ware::RockBook);
+        }
+        if !self.gem_polish {
+            wares.push(Ware::GemPolish);
+        }
+        if !self.appraiser {
+            wares.push(Ware::Appraiser);
+        }
+        if !self.big_claw {
+            wares.push(Ware::BigClaw);
+        }
+        if !self.dual_hook {
+            wares.push(Ware::DualHook);
+        }
+        if !self.fireproof_rope {
+            wares.push(Ware::FireproofRope);
+        }
+        if !self.ricochet_hook {
+            wares.push(Ware::RicochetHook);
+        }
+        if (self.hook_tier as usize) < HOOK_SPEED_TIERS.len() - 1 {
+            wares.push(Ware::HookSpeed(self.hook_tier + 1));
+        }
+        self.shop = Some(Shop::new(&wares, self.shop_discount));
+        self.shop_discount = 1.0;
+        // 下一关的行情在店里就滚动播出，开局时用同一个种子生成的行情完全一样
+        self.next_seed = thread_rng().gen();
+        self.market = Market::roll(self.next_seed, self.level + 1, self.config.market_volatility);
+        self.scene = Scene::Shop;
+        self.message = None;
+        self.open_dialogue("shopkeeper.txt");
+    }
+
+    // 用钱包里的钱买下选中的商品
+    fn buy_from_shop(&mut self) {
+        let shop = match self.shop.as_mut() {
+            Some(shop) => shop,
+            None => return,
+        };
+        match shop.buy(&mut self.wallet) {
+            Some(Ware::Dynamite) => self.extra_dynamite += 1,
+            Some(Ware::Appraiser) => self.appraiser = true,
+            Some(Ware::RockBook) => self.rock_book = true,
+            Some(Ware::GemPolish) => self.gem_polish = true,
+            Some(Ware::HookSpeed(tier)) => self.hook_tier = tier,
+            Some(Ware::BigClaw) => self.big_claw = true,
+            Some(Ware::DualHook) => self.dual_hook = true,
+            Some(Ware::FireproofRope) => self.fireproof_rope = true,
+            Some(Ware::RicochetHook) => self.ricochet_hook = true,
+            Some(Ware::Insurance) => self.insurance += 1,
+            Some(ware) => self.next_level_wares.push(ware),
+            None => (),
+        }
+    }
+
+    // 离开商店，开始下一关
+    fn leave_shop(&mut self) {
+        self.shop = None;
+        self.dialogue = None;
+        self.scene = Scene::Playing;
+        self.level += 1;
+        self.start_round(self.next_seed, Box::new(KeyboardInput::new()));
+        self.contract_offer = Some(ContractOffer::roll(self.level, &mut thread_rng()));
+    }
+
+    // 接下选中的合约，加入本关附加目标
+    fn accept_contract(&mut self) {
+        let contract = match self.contract_offer.take().and_then(ContractOffer::accept) {
+            Some(contract) => contract,
+            None => return,
+        };
+        let mut objective = contract.objective.clone();
+        objective.label = Some("Contract".to_string());
+        self.objectives.objectives.push(objective);
+        self.contract = Some((self.objectives.objectives.len() - 1, contract));
+    }
+
+    // 关卡结束时兑现合约：完成给回报，失败赔钱
+    fn settle_contract(&mut self) {
+        let (index, contract) = match self.contract.take() {
+            Some(contract) => contract,
+            None => return,
+        };
+        match self.objectives.objectives[index].status {
+            ObjectiveStatus::Done => match contract.payout {
+                Payout::Money(amount) => self.award(amount),
+                Payout::ShopDiscount(factor) => self.shop_discount = factor,
+                Payout::Dynamite(count) => self.extra_dynamite += count,
+            },
+            _ => self.award(-contract.penalty),
+        }
+    }
+
+    // 打开最新的回放文件
+    fn open_replay(&mut self) {
+        let loaded = replay::latest_replay()
+            .ok_or_else(|| "No replay found".to_string())
+            .and_then(|path| load_replay(&path));
+        match loaded {
+            Ok(replay) => {
+                self.analysis = None;
+                self.show_analysis = false;
+                self.level = replay.header.level;
+                let seed = replay.header.seed;
+                self.start_round(seed, Box::new(ReplayPlayer::new(replay)));
+                self.scene = Scene::Playing;
+            }
+            Err(err) => self.message = Some(err),
+        }
+    }
+
+    // 切换回放分析界面，首次打开时离屏模拟整局生成分析数据
+    fn toggle_analysis(&mut self) {
+        if self.analysis.is_none() {
+            if let Some(replay) = self.input.replay() {
+                self.analysis = Some(ReplayAnalysis::build(replay, &self.config));
+            }
+        }
+        self.show_analysis = !self.show_analysis && self.analysis.is_some();
+    }
+
+    // 回放跳转到指定帧：从头重新模拟到目标帧
+    fn seek_replay(&mut self, target: u64) {
+        let replay = match self.input.replay() {
+            Some(replay) => replay.clone(),
+            None => return,
+        };
+        let target = target.min(replay.header.duration);
+        self.start_round(replay.header.seed, Box::new(ReplayPlayer::new(replay)));
+        while self.tick < target && !self.game_over {
+            self.update(1.0 / TICKS_PER_SECOND as f32);
+        }
+    }
+
+    // 导出统计数据
+    fn export_stats(&mut self) {
+        self.message = Some(match self.stats.export() {
+            Ok(dir) => format!("Stats exported to {}", dir.display()),
+            Err(err) => format!("Export failed: {}", err),
+        });
+    }
+
+    // 导出本局回放
+    fn export_replay(&mut self) {
+        self.message = Some(match self.recording.export() {
+            Ok(path) => format!("Replay saved: {}", path.display()),
+            Err(err) => format!("Export failed: {}", err),
+        });
+    }
+
+    // 更新游戏状态
+    fn update(&mut self, dt: f32) {
+        if self.scene == Scene::Cutscene {
+            if self.cutscene.as_mut().map_or(true, |cutscene| cutscene.update()) {
+                self.end_cutscene();
+            }
+            return;
+        }
+        if self.scene == Scene::Slots {
+            if let Some(slots) = self.slots.as_mut() {
+                slots.update(&mut self.wallet);
+            }
+            return;
+        }
+        if self.scene == Scene::Shop {
+            match self.dialogue.as_mut() {
+                Some(dialogue) => dialogue.update(),
+                None => {
+                    if let Some(shop) = self.shop.as_mut() {
+                        shop.update();
+                    }
+                }
+            }
+            self.market.update(dt);
+            return;
+        }
+        if self.scene != Scene::Playing || self.game_over {
+            return;
+        }
+
+        // 对话或合约选择进行中暂停玩法
+        if let Some(dialogue) = self.dialogue.as_mut() {
+            dialogue.update();
+            return;
+        }
+        if self.contract_offer.is_some() {
+            return;
+        }
+
+        // 按住空格时蓄力
+        if self.charging {
+            let step = 1.0 / (self.config.charge_time * TICKS_PER_SECOND as f32);
+            self.throw_charge = (self.throw_charge + step).min(1.0);
+        }
+
+        // 执行输入源提供的本帧命令，非回放输入同时录制下来
+        let replaying = self.is_replay();
+        for action in self.input.poll(self.tick) {
+            if !replaying {
+                self.recording.record(self.tick, action);
+            }
+            // 教学关里还没教到的操作不起作用
+            if let Some(tutorial) = self.tutorial.as_mut() {
+                tutorial.on_action(&action);
+                if !tutorial.allows(&action) {
+                    continue;
+                }
+            }
+            self.apply_action(action);
+        }
+        let board = self.versus_cpu.then(|| self.ai_board());
+        let rival_actions = match self.rival.as_mut() {
+            Some(rival) => {
+                if let Some(board) = board {
+                    rival.input.observe(board);
+                }
+                rival.input.poll(self.tick)
+            }
+            None => Vec::new(),
+        };
+        for action in rival_actions {
+            self.apply_rival_action(action);
+        }
+
+        // 无尽模式：到达检查点时达标则继续，目标分抬高
+        if self.mode == GameMode::Endless && self.ticks_left() == 0 && self.level_score >= self.endless.goal() {
+            self.endless.checkpoint += 1;
+            self.message = Some(format!("Checkpoint passed! Next goal: ${}", self.endless.goal()));
+        }
+
+        // 检查游戏是否结束：时间到、回放放完或者心扣光了
+        if self.ticks_left() == 0 || self.input.finished(self.tick) || self.knocked_out() || self.puzzle_finished() {
+            self.game_over = true;
+            // 解谜模式达标时，剩下的出钩次数折算成奖励分
+            if self.mode == GameMode::Puzzle && self.level_cleared() {
+                self.level_score += self.throws_left as i32 * PUZZLE_THROW_BONUS;
+            }
+            let bonus = self.objectives.finish();
+            if bonus > 0 {
+                self.award(bonus);
+            }
+            self.settle_contract();
+            if !self.is_replay() {
+                self.wallet += self.level_score as i64;
+            }
+            self.recording.header.duration = self.tick;
+            let skipped = self
+                .items
+                .iter()
+                .filter(|item| !item.collected)
+                .map(|item| item.item_type)
+                .collect();
+            self.events.emit(GameEvent::LevelCompleted {
+                level: self.level,
+                score: self.level_score,
+                ticks: self.tick,
+                skipped,
+            });
+            self.dispatch_events();
+            if !self.is_replay() {
+                if self.config.director && self.replayable() {
+                    self.director.finish_level(self.level_score, self.level_target());
+                }
+                self.settle_campaign();
+                self.settle_story();
+            }
+            if self.persist && !self.is_replay() {
+                crash::clear_snapshot();
+                if let Err(err) = self.stats.lifetime.save() {
+                    self.message = Some(format!("Failed to save stats: {}", err));
+                }
+                // 经典模式记录单关最高分，无尽模式记录整局得分，计时赛记录清场用时，每日挑战按天记录得分
+                let daily_key = modes::daily_key(self.daily_day);
+                let record_key = match self.mode {
+                    GameMode::Daily => daily_key.as_str(),
+                    mode => mode.name(),
+                };
+                let record = match self.mode {
+                    _ if !self.is_freeplay() || self.bonus_stage || self.tutorial.is_some() => None,
+                    GameMode::TimeAttack if self.mine_cleared() => {
+                        let millis = modes::ticks_to_millis(self.tick);
+                        self.records
+                            .submit(record_key, millis as i64, false)
+                            .then(|| format!("New {} record: {}", record_key, modes::format_millis(millis)))
+                    }
+                    GameMode::TimeAttack | GameMode::Versus | GameMode::Coop | GameMode::Sandbox => None,
+                    _ => self
+                        .records
+                        .submit(record_key, self.level_score as i64, true)
+                        .then(|| format!("New {} record: ${}", self.mode.name(), self.level_score)),
+                };
+                // 每关只保留最好的星级和最高分，显示在选关地图上
+                let level_best = self.level_stars().is_some()
+                    && self.records.submit(&levels::best_key(self.level, self.config.difficulty), self.level_score as i64, true);
+                let stars = self
+                    .level_stars()
+                    .filter(|&stars| stars > 0 && self.records.submit(&levels::stars_key(self.level, self.config.difficulty), stars as i64, true))
+                    .map(|stars| format!("New best for level {}: {}/{} stars", self.level, stars, levels::MAX_STARS));
+                // 刷新本关最高分时这一局成为新的幽灵
+                if level_best && self.config.ghost {
+                    if let Err(err) = self.ghost_recorder.save(self.level, self.seed, self.config.difficulty) {
+                        self.message = Some(format!("Failed to save ghost: {}", err));
+                    }
+                }
+                if let Some(record) = record.or(stars) {
+                    self.message = match self.records.save() {
+                        Ok(()) => Some(record),
+                        Err(err) => Some(format!("Failed to save records: {}", err)),
+                    };
+                } else if level_best {
+                    if let Err(err) = self.records.save() {
+                        self.message = Some(format!("Failed to save records: {}", err));
+                    }
+                }
+                // 教完后不再自动进入教学关
+                if self.tutorial.as_ref().map_or(false, |tutorial| tutorial.finished())
+                    && self.records.submit(tutorial::TUTORIAL_RECORD, 1, true)
+                {
+                    if let Err(err) = self.records.save() {
+                        self.message = Some(format!("Failed to save records: {}", err));
+                    }
+                }
+                if !self.drill_unlocked && self.stats.lifetime.totals.score >= self.config.drill_unlock_score {
+                    self.drill_unlocked = true;
+                    self.message = Some("Drill unlocked! Press D to use it".to_string());
+                }
+            }
+            // 经典闯关过关时检查隐藏条件，达成了就在下一关之前插入奖励关
+            if self.config.bonus_stages
+                && self.mode == GameMode::Classic
+                && self.is_freeplay()
+                && !self.bonus_stage
+                && self.tutorial.is_none()
+                && !self.is_replay()
+                && self.level_cleared()
+            {
+                if let Some(secret) = self.secrets.found() {
+                    self.bonus_unlocked = true;
+                    self.message = Some(format!("{}! A secret bonus stage awaits", secret));
+                }
+            }
+            return;
+        }
+        self.tick += 1;
+        for item in self.items.iter_mut() {
+            item.drifting = false;
+        }
+
+        let refills = matches!(self.mode, GameMode::Endless | GameMode::Versus);
+        if refills && self.tick % (REFILL_SECONDS * TICKS_PER_SECOND as u64) == 0 {
+            self.replenish_items();
+        }
+
+        // 每秒更新一次崩溃快照
+        if self.persist && !replaying && self.replayable() && self.tick % TICKS_PER_SECOND as u64 == 0 {
+            let run = RunState {
+                wallet: self.wallet,
+                campaign: self.campaign.clone(),
+            };
+            crash::record_snapshot(self.tick, self.level_score, &self.recording, run);
+        }
+
+        // 增益倒计时
+        self.buffs.update();
+        let strength = if self.buffs.has(BuffKind::Strength) { Some(STRENGTH_FACTOR) } else { None };
+        let winch = if self.config.winch_fuel && self.winch_fuel <= 0.0 { self.config.dry_winch_factor } else { 1.0 };
+        for hook in self.hooks.iter_mut() {
+            hook.strength = strength;
+            hook.winch = winch;
+        }
+
+        self.combo.update();
+
+        // 更新钩子，空钩收回时连击清零
+        {
+            profile_scope!("hook");
+            for i in 0..self.hooks.len() {
+                let was_out = self.hooks[i].state != HookState::Idle;
+                self.hooks[i].drag = self.water.drag_at(self.hooks[i].position.y);
+                let origin = self.hook_origin(i);
+                self.hooks[i].update(origin, dt);
+                self.dig_terrain(i);
+                if self.buffs.has(BuffKind::Magnet) {
+                    self.pull_items(i);
+                }
+                let missed = was_out && self.hooks[i].state == HookState::Idle && self.hooks[i].attached_item.is_none();
+                if missed && self.hooks[i].player == 0 {
+                    self.combo.on_miss();
+                }
+            }
+            self.cut_ropes();
+            self.bat_raids();
+            self.burn_ropes();
+            // 镜头跟着伸得最深的钩子
+            let deepest = self.hooks.iter().map(|hook| hook.position.y).fold(self.miner.position.y, f32::max);
+            self.camera.follow(deepest);
+        }
+
+        // 地鼠出场、走动和离场
+        if self.moles.update(self.tick, self.config.screen_width) {
+            self.message = Some("A golden mole is dashing along the bottom - catch it!".to_string());
+        }
+        let sparkles: Vec<_> = self.moles.sparkles().collect();
+        for position in sparkles {
+            self.effects.burst(position, ItemType::GoldenMole.color(), 3);
+        }
+        if let Some(boss) = self.boss.as_mut() {
+            boss.update(self.config.screen_width);
+        }
+        self.saws.update();
+        self.portals.update();
+        self.powerups.update(self.tick);
+
+        // 矿车移动，打滑时车轮下冒火花
+        if self.miner.update(dt, self.carried_weight(0)) {
+            let wheels = na::Point2::new(self.miner.position.x, self.miner.position.y + self.miner.height / 2.0);
+            self.effects.burst(wheels, Color::new(1.0, 0.7, 0.2, 1.0), 2);
+        }
+
+        let rival_load = self.carried_weight(RIVAL);
+        if let Some(rival) = self.rival.as_mut() {
+            rival.miner.update(dt, rival_load);
+        }
+
+        if !replaying {
+            let tip = self.hooks.first().filter(|hook| hook.state != HookState::Idle).map(|hook| hook.position);
+            self.ghost_recorder.record(self.miner.position.x, tip);
+        }
+
+        // 落石：砸中矿工扣一颗心并晕一会儿
+        self.miner.stun = self.miner.stun.saturating_sub(1);
+        if self.rock_fall.update(self.tick, self.miner.rect(), self.config.screen_width) {
+            self.miner.hit(self.config.stun_seconds * TICKS_PER_SECOND as u64);
+            self.events.emit(GameEvent::MinerHit {
+                hearts: self.miner.hearts,
+                position: self.miner.position,
+            });
+        }
+
+        // 检查钩子与物品的碰撞
+        {
+            profile_scope!("collision");
+            let was_attached: Vec<bool> = self.hooks.iter().map(|hook| hook.attached_item.is_some()).collect();
+            self.claim_items();
+            for (i, was_attached) in was_attached.into_iter().enumerate() {
+                self.check_hook_collision(i, was_attached);
+            }
+        }
+
+        // 体力：钩子都空闲时恢复，拖拽重物时额外消耗
+        if self.config.stamina {
+            if self.hooks.iter().all(|hook| hook.state == HookState::Idle) {
+                self.miner.change_stamina(self.config.stamina_regen * dt);
+            }
+            for hook in &self.hooks {
+                if let (HookState::Retracting, Some(item_idx)) = (&hook.state, hook.attached_item) {
+                    let weight = self.items[item_idx].weight();
+                    self.miner.change_stamina(-self.config.stamina_haul_drain * weight * dt);
+                }
+            }
+        }
+
+        // 绞盘燃料：钩子来回走多远就烧多少
+        if self.config.winch_fuel {
+            let travelled: f32 = self.hooks.iter().map(|hook| hook.travelled).sum();
+            self.winch_fuel = (self.winch_fuel - travelled * self.config.winch_fuel_per_pixel).max(0.0);
+        }
+
+        // 缆绳油：钩子伸出多深就消耗多少
+        if self.config.cable_oil {
+            for hook in self.hooks.iter().filter(|hook| hook.state == HookState::Thrown) {
+                let used = hook.extend_speed * self.config.cable_oil_per_pixel;
+                self.cable_oil = (self.cable_oil - used).max(0.0);
+            }
+        }
+
+        // 钻头：钻到的物品在钻头回到地面时按折扣结算
+        {
+            profile_scope!("drill");
+            let (hits, finished) = self.drill.update(&mut self.items);
+            for i in hits {
+                let item = &self.items[i];
+                self.events.emit(GameEvent::ItemDrilled {
+                    item_type: item.item_type,
+                    position: item.position,
+                });
+            }
+            if finished {
+                let collected = std::mem::take(&mut self.drill.collected);
+                for i in collected {
+                    let item = &self.items[i];
+                    if item.item_type == ItemType::MysteryBag {
+                        self.open_mystery_bag(i, self.miner.position, 0);
+                        continue;
+                    }
+                    let value = (self.resolve_value(i) as f32 * self.config.drill_value_factor) as i32;
+                    let value = self.insure(0, value);
+                    let item = &self.items[i];
+                    self.level_score = (self.level_score + value).max(0);
+                    self.events.emit(GameEvent::ItemBanked {
+                        player: 0,
+                        item_type: item.item_type,
+                        value,
+                        purity: item.purity(),
+                        position: self.miner.position,
+                    });
+                }
+            }
+        }
+
+        // 地震：物品被震到新位置，重新看谁压在谁上面
+        if self.quakes.update(self.tick, &mut self.items, &self.config) {
+            gravity::link_supports(&mut self.items);
+            self.camera.shake(SHAKE_TICKS);
+            self.background.invalidate();
+            self.message = Some("Earthquake! The mine has shifted".to_string());
+        }
+
+        // 下面的物品没了，上面的物品掉下来；水下轻的物品随水漂动，传送带上的物品被运走。
+        // 动着的物品每帧单独绘制，静态背景只在哪些物品在动发生变化时重绘
+        self.water.update(self.tick, &mut self.items);
+        self.conveyors.update(&mut self.items);
+        gravity::settle(&mut self.items, self.config.mine_depth);
+
+        // 飞行中的炸药
+        {
+            profile_scope!("projectiles");
+            let mut landed = Vec::new();
+            let items = &self.items;
+            self.projectiles.retain_mut(|projectile| match projectile.update(dt, items) {
+                Some(point) => {
+                    landed.push(point);
+                    false
+                }
+                None => true,
+            });
+            for point in landed {
+                self.explode(point, self.config.dynamite_radius);
+            }
+        }
+
+        // 连锁爆炸的炸药桶
+        self.burn_fuses();
+
+        // 更新粒子和飘字
+        {
+            profile_scope!("particles");
+            self.effects.update(dt);
+            self.market.update(dt);
+        }
+
+        // 如果钩子收回且有附着的物品，增加分数
+        profile_scope!("scoring");
+        for i in 0..self.hooks.len() {
+            self.bank_catch(i);
+        }
+
+        self.dispatch_events();
+        self.objectives.update(self.level_score, self.ticks_left());
+
+        let view = self.plugin_view();
+        self.plugins.update(&view, dt);
+    }
+
+    // 钩子抓物品：同一帧几个钩子碰到同一件物品时，钩尖离得最近的抓住，其余的继续往前
+    fn claim_items(&mut self) {
+        let mut claims: Vec<(usize, usize, f32)> = self
+            .hooks
+            .iter()
+            .enumerate()
+            .filter_map(|(i, hook)| hook.touching(&self.items, &self.terrain).map(|(item, distance)| (i, item, distance)))
+            .collect();
+        claims.sort_by(|a, b| a.2.total_cmp(&b.2));
+        for (i, index, _) in claims {
+            if !self.items[index].collected {
+                self.hooks[i].grab(index, &mut self.items[index]);
+            }
+        }
+    }
+
+    // 第 i 个钩子与地鼠、道具和金猪的碰撞，这一帧新抓到东西时发出事件
+    fn check_hook_collision(&mut self, i: usize, was_attached: bool) {
+        // 地鼠会走动，不在物品列表里，抓到后才变成物品
+        if self.hooks[i].state == HookState::Thrown && self.hooks[i].attached_item.is_none() {
+            if let Some(mole) = self.moles.catch(self.hooks[i].position) {
+                let item_type = if mole.golden {
+                    ItemType::GoldenMole
+                } else if mole.carries_diamond {
+                    ItemType::DiamondMole
+                } else {
+                    ItemType::Mole
+                };
+                let mut item = Item::new(item_type, mole.position.x, mole.position.y, self.config.item_size);
+                self.hooks[i].grab(self.items.len(), &mut item);
+                self.items.push(item);
+            }
+        }
+        // 钩中叼着东西的蝙蝠，把物品抢回来
+        if self.hooks[i].state == HookState::Thrown && self.hooks[i].attached_item.is_none() {
+            if let Some(index) = self.bats.catch(self.hooks[i].position) {
+                self.hooks[i].grab(index, &mut self.items[index]);
+            }
+        }
+        // 漂浮道具同样抓到后才变成物品
+        if self.hooks[i].state == HookState::Thrown && self.hooks[i].attached_item.is_none() {
+            if let Some(powerup) = self.powerups.catch(self.hooks[i].position) {
+                let position = powerup.position;
+                let mut item = Item::new(powerup.item_type, position.x, position.y, self.config.item_size);
+                self.hooks[i].grab(self.items.len(), &mut item);
+                self.items.push(item);
+            }
+        }
+        // 金猪要钩中好几次：没抓住时钩子空手而回
+        if self.hooks[i].state == HookState::Thrown && self.hooks[i].attached_item.is_none() {
+            let position = self.hooks[i].position;
+            match self.boss.as_mut().and_then(|boss| boss.hit(position)) {
+                Some(BossHit::Wounded { health }) => {
+                    self.hooks[i].state = HookState::Retracting;
+                    self.events.emit(GameEvent::BossHit { health, position });
+                }
+                Some(BossHit::Captured) => {
+                    self.boss = None;
+                    let mut item = Item::new(ItemType::GoldenPig, position.x, position.y, self.config.item_size);
+                    self.hooks[i].grab(self.items.len(), &mut item);
+                    self.items.push(item);
+                }
+                None => (),
+            }
+        }
+        if !was_attached {
+            if let Some(item) = self.hooks[i].attached_item.and_then(|index| self.items.get(index)) {
+                let barrel = item.item_type == ItemType::TntBarrel;
+                let rock = item.item_type == ItemType::Rock;
+                self.events.emit(GameEvent::ItemGrabbed {
+                    player: self.hooks[i].player,
+                    item_type: item.item_type,
+                    position: item.position,
+                });
+                if barrel {
+                    self.detonate_barrel(i);
+                } else if rock && self.buffs.has(BuffKind::RockBreaker) {
+                    self.shatter_rock(i);
+                }
+            }
+        }
+    }
+
+    // 第 i 个钩子收回且附着物品时结算
+    fn bank_catch(&mut self, i: usize) {
+        if self.hooks[i].state == HookState::Idle && self.hooks[i].attached_item.is_some() {
+            if let Some(item_idx) = self.hooks[i].attached_item {
+                if item_idx < self.items.len() {
+                    let item = &self.items[item_idx];
+                    if item.item_type == ItemType::OilCan {
+                        self.cable_oil = (self.cable_oil + self.config.oil_can_refill).min(self.config.max_cable_oil);
+                    }
+                    if item.item_type == ItemType::FuelCan {
+                        self.winch_fuel = (self.winch_fuel + self.config.fuel_can_refill).min(self.config.max_winch_fuel);
+                    }
+                    let (player, origin) = (self.hooks[i].player, self.hook_origin(i));
+                    // 连击只算玩家一的
+                    if player == 0 {
+                        self.combo.on_catch(item.item_type);
+                    }
+                    if item.item_type == ItemType::MysteryBag {
+                        self.open_mystery_bag(item_idx, origin, player);
+                    } else {
+                        let value = self.resolve_value(item_idx);
+                        let value = self.insure(player, value);
+                        self.credit(player, value);
+                        let item = &self.items[item_idx];
+                        self.events.emit(GameEvent::ItemBanked {
+                            player,
+                            item_type: item.item_type,
+                            value,
+                            purity: item.purity(),
+                            position: origin,
+                        });
+                    }
+                }
+                self.hooks[i].attached_item = None;
+            }
+        }
+    }
+
+    // 伸出的空钩碰到泥土削掉一层，没凿穿就弹回来
+    fn dig_terrain(&mut self, i: usize) {
+        let hook = &mut self.hooks[i];
+        if hook.state != HookState::Thrown || hook.attached_item.is_some() {
+            return;
+        }
+        if self.terrain.dig(hook.position) == Some(Dig::Eroded) {
+            hook.state = HookState::Retracting;
+        }
+    }
+
+    // 拉回物品的结算价值：钥匙收进口袋，宝箱有钥匙才打得开，时钟给本关加时，道具带来增益
+    fn resolve_value(&mut self, item_idx: usize) -> i32 {
+        let item = &self.items[item_idx];
+        match item.item_type {
+            ItemType::Key => {
+                self.keys += 1;
+                0
+            }
+            ItemType::Clock => {
+                self.bonus_ticks += CLOCK_SECONDS * TICKS_PER_SECOND as u64;
+                self.message = Some(format!("+{} seconds!", CLOCK_SECONDS));
+                0
+            }
+            ItemType::MagnetPickup | ItemType::StrengthPickup | ItemType::DoublePickup | ItemType::RockBreakerPickup => {
+                let kind = match item.item_type {
+                    ItemType::MagnetPickup => BuffKind::Magnet,
+                    ItemType::StrengthPickup => BuffKind::Strength,
+                    ItemType::RockBreakerPickup => BuffKind::RockBreaker,
+                    _ => BuffKind::Double,
+                };
+                self.buffs.add(kind, Some(POWERUP_SECONDS * TICKS_PER_SECOND as u64));
+                0
+            }
+            ItemType::Chest if self.keys == 0 => CHEST_JUNK_VALUE,
+            ItemType::Chest => {
+                self.keys -= 1;
+                self.bank_value(item)
+            }
+            _ => self.bank_value(item),
+        }
+    }
+
+    // 锯片割断碰到的绳子：钩子上的物品丢失，钩子过一会儿才能重新出
+    fn cut_ropes(&mut self) {
+        let respawn = self.config.rope_respawn_seconds * TICKS_PER_SECOND as u64;
+        for i in 0..self.hooks.len() {
+            if self.hooks[i].state == HookState::Idle {
+                continue;
+            }
+            if self.hooks[i].insured {
+                continue;
+            }
+            // 反弹过或钻过传送门的绳子按实际走过的几段检查，不用起点到钩尖的直线
+            let strands = self.hooks[i].rope(self.hook_origin(i));
+            if let Some(position) = strands.iter().find_map(|strand| self.saws.cuts(strand)) {
+                if self.claim_insurance(self.hooks[i].player) {
+                    self.hooks[i].insured = true;
+                    continue;
+                }
+                self.hooks[i].cut(respawn);
+                if self.hooks[i].player == 0 {
+                    self.combo.on_miss();
+                }
+                self.events.emit(GameEvent::RopeCut { position });
+            }
+        }
+    }
+
+    // 蝙蝠扑向正在收回的钩子，碰到挂着的物品就叼走，钩子空手收回；叼着的物品跟着蝙蝠飞
+    fn bat_raids(&mut self) {
+        let loads: Vec<_> = self
+            .hooks
+            .iter()
+            .filter(|hook| hook.state == HookState::Retracting && hook.attached_item.is_some())
+            .map(|hook| hook.position)
+            .collect();
+        self.bats.update(&loads);
+        for i in 0..self.hooks.len() {
+            let hook = &self.hooks[i];
+            let index = match hook.attached_item {
+                Some(index) if hook.state == HookState::Retracting => index,
+                _ => continue,
+            };
+            let item = &self.items[index];
+            if self.bats.steal(hook.position, index, item.size() / 2.0) {
+                self.message = Some(format!("A bat stole your {}! Hook it to get it back", item.item_type.name()));
+                let hook = &mut self.hooks[i];
+                hook.attached_item = None;
+                hook.load_weight = 1.0;
+                hook.burn = 0;
+            }
+        }
+        let cargo: Vec<_> = self.bats.cargo().collect();
+        for (index, position) in cargo {
+            self.items[index].position = position;
+        }
+    }
+
+    // 拖着金水收钩时绳子慢慢烧着，没有防火绳的话烧够时间就断，金水掉回去
+    fn burn_ropes(&mut self) {
+        let respawn = self.config.rope_respawn_seconds * TICKS_PER_SECOND as u64;
+        for i in 0..self.hooks.len() {
+            let hook = &mut self.hooks[i];
+            let molten = hook.attached_item.map_or(false, |index| self.items[index].item_type == ItemType::MoltenGold);
+            if hook.fireproof || hook.state != HookState::Retracting || !molten {
+                continue;
+            }
+            hook.burn += 1;
+            if hook.burn >= BURN_SECONDS * TICKS_PER_SECOND as u64 {
+                // 保险赔一根新绳子，重新开始烧
+                if self.claim_insurance(self.hooks[i].player) {
+                    self.hooks[i].burn = 0;
+                    continue;
+                }
+                let hook = &mut self.hooks[i];
+                let position = hook.position;
+                if let Some(index) = hook.cut(respawn) {
+                    let item = &mut self.items[index];
+                    item.collected = false;
+                    item.position = position;
+                    item.falling = true;
+                    self.background.invalidate();
+                }
+                if self.hooks[i].player == 0 {
+                    self.combo.on_miss();
+                }
+                self.events.emit(GameEvent::RopeCut { position });
+                self.message = Some("The molten gold burned through the rope!".to_string());
+            }
+        }
+    }
+
+    // 磁铁：钩子伸出且还空着时，把范围内的物品往钩尖拉一点
+    fn pull_items(&mut self, i: usize) {
+        let hook = &self.hooks[i];
+        if hook.state != HookState::Thrown || hook.attached_item.is_some() {
+            return;
+        }
+        let tip = hook.position;
+        for item in self.items.iter_mut().filter(|item| !item.collected) {
+            let offset = tip - item.position;
+            let distance = offset.norm();
+            if distance > 0.0 && distance < self.config.magnet_radius {
+                item.position += offset * (self.config.magnet_pull.min(distance) / distance);
+                item.drifting = true;
+            }
+        }
+    }
+
+    // 揭晓神秘袋的内容，钱记给拉回它的玩家
+    fn open_mystery_bag(&mut self, item_idx: usize, position: na::Point2<f32>, player: usize) {
+        let reward = mystery::roll(&mut mystery::bag_rng(self.seed, self.tick, item_idx), &self.buffs);
+        match reward {
+            BagReward::Money(amount) | BagReward::Dud(amount) => self.credit(player, amount),
+            BagReward::Dynamite => self.dynamite += 1,
+            BagReward::Strength => {
+                let ticks = BAG_STRENGTH_SECONDS * TICKS_PER_SECOND as u64;
+                self.buffs.add(BuffKind::Strength, Some(ticks));
+            }
+        }
+        self.events.emit(GameEvent::MysteryBagOpened { reward, position });
+    }
+
+    // 奖励（或罚款）直接进出钱包，不计入本关得分；看回放和拖动进度时钱包不动
+    fn award(&mut self, amount: i64) {
+        if !self.is_replay() {
+            self.wallet += amount;
+        }
+    }
+
+    // 战役关卡结算：到期从钱包还款
+    fn settle_campaign(&mut self) {
+        let campaign = match self.campaign.as_mut() {
+            Some(campaign) => campaign,
+            None => return,
+        };
+        self.message = match campaign.settle(self.level, &mut self.wallet) {
+            Settlement::Continue => None,
+            Settlement::PaymentMade { amount, beat } => Some(format!("Paid ${}. {}", amount, beat)),
+            Settlement::PaymentMissed { amount } => {
+                Some(format!("Couldn't pay ${} - the landlord takes the mine.", amount))
+            }
+            Settlement::DeedEarned { amount } => {
+                Some(format!("Paid ${}. The deed is yours! Enter: New Game Plus", amount))
+            }
+        };
+    }
+
+    // 剧情关卡结算：达到目标分解锁下一关
+    fn settle_story(&mut self) {
+        let story = match self.story.as_mut() {
+            Some(story) => story,
+            None => return,
+        };
+        let target = story.current().target;
+        if self.level_score < target {
+            self.message = Some(format!("Target ${} missed - Enter: retry", target));
+            return;
+        }
+        let has_next = story.complete();
+        if self.persist {
+            if let Err(err) = story.progress.save() {
+                self.message = Some(format!("Failed to save progress: {}", err));
+                return;
+            }
+        }
+        self.message = Some(if has_next {
+            "Level cleared! Enter: next level".to_string()
+        } else {
+            "The mine is yours. The End.".to_string()
+        });
+    }
+
+    // 帧末分发本帧事件
+    fn dispatch_events(&mut self) {
+        profile_scope!("events");
+        let view = self.plugin_view();
+        // 回放、沙盒、教学关和双人局都不算正式成绩
+        let counted = !self.is_replay() && self.replayable();
+        for event in self.events.drain() {
+            self.effects.on_event(&event);
+            self.sfx.on_event(&event);
+            self.background.on_event(&event);
+            self.objectives.on_event(&event);
+            self.secrets.on_event(&event);
+            if let Some(tutorial) = self.tutorial.as_mut() {
+                tutorial.on_event(&event);
+            }
+            // 不算正式成绩时不解锁成就，也不计入累计统计和自适应难度
+            if counted {
+                self.stats.on_event(&event);
+                self.achievements.on_event(&event);
+                self.director.on_event(&event);
+            }
+            self.plugins.event(&view, &event);
+        }
+        if self.persist && self.achievements.unsaved() {
+            if let Err(err) = self.achievements.save() {
+                self.message = Some(format!("Failed to save achievements: {}", err));
+            }
+        }
+    }
+
+    // 绘制游戏
+    fn draw(&mut self, ctx: &mut Context, graphics: &mut graphics::GraphicsContext) -> GameResult {
+        profile_scope!("draw");
+        let (screen_w, screen_h) = (self.config.screen_width, self.config.screen_height);
+        graphics::clear(ctx, Color::new(0.0, 0.2, 0.4, 1.0)); // 深蓝色背景
+
+        if self.scene == Scene::MainMenu {
+            self.draw_menu(ctx)?;
+            graphics::present(ctx)?;
+            return Ok(());
+        }
+
+        if self.scene == Scene::Achievements {
+            self.achievements.draw_list(ctx, screen_w, screen_h)?;
+            graphics::present(ctx)?;
+            return Ok(());
+        }
+
+        if self.scene == Scene::LevelSelect {
+            if let Some(level_select) = &self.level_select {
+                level_select.draw(ctx, &self.records, screen_w, screen_h)?;
+            }
+            graphics::present(ctx)?;
+            return Ok(());
+        }
+
+        if self.scene == Scene::Slots {
+            if let Some(slots) = &self.slots {
+                slots.draw(ctx, self.wallet, screen_w, screen_h)?;
+            }
+            graphics::present(ctx)?;
+            return Ok(());
+        }
+
+        if self.scene == Scene::Shop {
+            if let Some(shop) = &self.shop {
+                shop.draw(ctx, self.wallet, screen_w, screen_h)?;
+            }
+            if self.config.market_volatility > 0.0 {
+                self.market.draw_ticker(ctx, screen_w, screen_h)?;
+            }
+            if let Some(dialogue) = self.dialogue.as_mut() {
+                dialogue.draw(ctx, &self.strings, screen_w, screen_h)?;
+            }
+            graphics::present(ctx)?;
+            return Ok(());
+        }
+
+        if self.scene == Scene::Cutscene {
+            graphics::clear(ctx, Color::BLACK);
+            if let Some(cutscene) = self.cutscene.as_mut() {
+                cutscene.draw(ctx, screen_w, screen_h)?;
+            }
+            graphics::present(ctx)?;
+            return Ok(());
+        }
+
+        // 矿洞里的东西按世界坐标绘制，HUD 之前恢复屏幕坐标
+        self.camera.push(ctx)?;
+
+        // 绘制缓存的静态背景（地层、装饰、静止物品），动着的物品画在上面
+        {
+            profile_scope!("draw_background");
+            self.background.draw(ctx, &self.items)?;
+            for item in self.items.iter().filter(|item| !item.collected && item.moving()) {
+                item.draw(ctx)?;
+            }
+        }
+
+        // 泥土层盖在物品上面
+        self.terrain.draw(ctx, self.camera.y, screen_h)?;
+
+        // 水面和气泡
+        self.water.draw(ctx)?;
+
+        // 最佳成绩的幽灵画在矿工后面
+        if let Some(ghost) = &self.ghost {
+            ghost.draw(ctx, self.tick, &self.miner)?;
+        }
+
+        // 绘制矿工，双人对战时玩家二是蓝色的
+        self.miner.draw(ctx, Color::new(0.8, 0.5, 0.3, 1.0))?; // 棕色矿工
+        if let Some(rival) = &self.rival {
+            rival.miner.draw(ctx, Color::new(0.3, 0.5, 0.85, 1.0))?;
+        }
+
+        // 每个钩子分别绘制
+        for (i, hook) in self.hooks.iter().enumerate() {
+            let origin = self.hook_origin(i);
+            // 摆钩模式下空闲的钩子挂在矿工下方一小段绳子上摆动
+            if hook.ready() && hook.swing.is_some() {
+                let start = origin;
+                let end = na::Point2::new(
+                    start.x + hook.angle.cos() * IDLE_ROPE_LENGTH,
+                    start.y + hook.angle.sin() * IDLE_ROPE_LENGTH,
+                );
+                graphics::line(ctx, graphics::DrawParam::default(), &[start, end], 2.0, self.prestige.rope_color())?;
+                let claw = hook.claw;
+                let hook_rect = Rect::new(end.x - claw, end.y - claw, claw * 2.0, claw * 2.0);
+                graphics::rectangle(
+                    ctx,
+                    graphics::DrawParam::default().dest(hook_rect.point()),
+                    &hook_rect,
+                    Color::new(0.7, 0.7, 0.7, 1.0),
+                )?;
+            }
+
+            // 绘制钩子
+            if hook.length > 0.0 {
+                let end = na::Point2::new(hook.position.x, hook.position.y);
+
+                // 绘制绳子，有风时是弯的；被金水烧着时发红
+                let rope_color = if hook.burn > 0 && hook.attached_item.is_some() && !hook.fireproof {
+                    Color::new(1.0, 0.35, 0.05, 1.0)
+                } else {
+                    self.prestige.rope_color() // 声望解锁的绳子颜色
+                };
+                for strand in hook.rope(origin) {
+                    graphics::line(ctx, graphics::DrawParam::default(), &strand, 2.0, rope_color)?;
+                }
+
+                // 绘制钩子
+                let claw = hook.claw;
+                let hook_rect = Rect::new(
+                    end.x - claw,
+                    end.y - claw,
+                    claw * 2.0,
+                    claw * 2.0,
+                );
+                graphics::rectangle(
+                    ctx,
+                    graphics::DrawParam::default().dest(hook_rect.point()),
+                    &hook_rect,
+                    Color::new(0.7, 0.7, 0.7, 1.0), // 灰色钩子
+                )?;
+
+                // 如果钩子附着了物品，绘制物品
+                if let Some(item_idx) = hook.attached_item {
+                    if item_idx < self.items.len() {
+                        let item = &self.items[item_idx];
+                        let item_size = item.size();
+                        let item_rect = Rect::new(
+                            end.x - item_size / 2.0,
+                            end.y - item_size / 2.0,
+                            item_size,
+                            item_size,
+                        );
+                        graphics::rectangle(
+                            ctx,
+                            graphics::DrawParam::default().dest(item_rect.point()),
+                            &item_rect,
+                            item.color(),
+                        )?;
+                        if let Some(tie) = item.bag_tie() {
+                            let tie = Rect::new(end.x - tie.w / 2.0, item_rect.y + 4.0, tie.w, tie.h);
+                            graphics::rectangle(ctx, graphics::DrawParam::default().dest(tie.point()), &tie, BAG_TIE_COLOR)?;
+                        }
+                    }
+                }
+            }
+        }
+
+        // 鉴定师：悬停显示准确价值
+        if self.appraiser {
+            self.draw_appraisal(ctx)?;
+        }
+
+        // 钻头
+        self.drill.draw(ctx, &self.items)?;
+
+        // 地鼠
+        self.moles.draw(ctx)?;
+
+        // 漂浮道具
+        self.powerups.draw(ctx)?;
+
+        // 锯片、传送门、蝙蝠和落石
+        self.saws.draw(ctx)?;
+        self.portals.draw(ctx)?;
+        self.bats.draw(ctx)?;
+        for (index, position) in self.bats.cargo() {
+            let item = &self.items[index];
+            let size = item.size();
+            let rect = Rect::new(position.x - size / 2.0, position.y - size / 2.0, size, size);
+            graphics::rectangle(ctx, graphics::DrawParam::default().dest(rect.point()), &rect, item.color())?;
+        }
+        self.conveyors.draw(ctx)?;
+        self.rock_fall.draw(ctx)?;
+
+        // 首领关的金猪
+        if let Some(boss) = &self.boss {
+            boss.draw(ctx)?;
+        }
+
+        // 飞行中的炸药
+        for projectile in &self.projectiles {
+            projectile.draw(ctx)?;
+        }
+
+        // 引信正在烧的炸药桶顶上闪着火花
+        if (self.tick / 4) % 2 == 0 {
+            for &(_, index) in &self.fuses {
+                let item = &self.items[index];
+                let spark = Rect::new(item.position.x - 3.0, item.position.y - item.size() / 2.0 - 8.0, 6.0, 6.0);
+                graphics::rectangle(ctx, graphics::DrawParam::default().dest(spark.point()), &spark, FUSE_COLOR)?;
+            }
+        }
+
+        // 蓄力条，显示在矿工下方
+        if self.config.charge_throw && self.idle_hook().is_some() {
+            let meter = Rect::new(
+                self.miner.position.x - self.miner.width / 2.0,
+                self.miner.position.y + self.miner.height / 2.0 + 4.0,
+                self.miner.width,
+                6.0,
+            );
+            let color = Color::new(1.0, 1.0 - self.throw_charge, 0.0, 1.0); // 越满越红
+            draw_bar(ctx, meter, self.throw_charge, color)?;
+        }
+
+        // 绘制粒子和飘字
+        {
+            profile_scope!("draw_effects");
+            self.effects.draw(ctx)?;
+        }
+        // 回放分析的出钩线跟着镜头走
+        if self.show_analysis {
+            if let Some(analysis) = &self.analysis {
+                analysis.draw_throws(ctx, self.tick)?;
+            }
+        }
+        self.camera.pop(ctx)?;
+
+        // 绘制分数和时间
+        profile_scope!("draw_hud");
+        let time_left_seconds = self.ticks_left() / TICKS_PER_SECOND as u64;
+
+        // 双倍生效时分数闪烁，屏幕上方显示倒计时
+        let double = self.buffs.remaining(BuffKind::Double);
+        let score_color = if double.is_some() && (self.tick / 10) % 2 == 0 {
+            Color::new(1.0, 0.84, 0.0, 1.0)
+        } else {
+            Color::WHITE
+        };
+        if let Some(remaining) = double {
+            let countdown = format!("x2 SCORE  {}s", remaining.div_ceil(TICKS_PER_SECOND as u64));
+            draw_centered_text(ctx, screen_w, &countdown, 22.0, Color::new(1.0, 0.84, 0.0, 1.0), 90.0)?;
+        }
+        let score_text = Text::new(TextFragment::new(format!("Score: {}", self.level_score))
+            .color(score_color)
+            .font_size(24));
+        graphics::draw(
+            ctx,
+            &score_text,
+            graphics::DrawParam::default().dest(na::Point2::new(10.0, 10.0)),
+        )?;
+
+        // 钱包：之前各关攒下的钱
+        let wallet_text = Text::new(TextFragment::new(format!("Wallet: ${}", self.wallet))
+            .color(Color::new(1.0, 0.84, 0.0, 1.0))
+            .font_size(18));
+        graphics::draw(
+            ctx,
+            &wallet_text,
+            graphics::DrawParam::default().dest(na::Point2::new(screen_w - 300.0, 14.0)),
+        )?;
+
+        // 体力条
+        if self.config.stamina {
+            let bar = Rect::new(10.0, 40.0, 120.0, 8.0);
+            let fraction = self.miner.stamina / self.miner.max_stamina;
+            let color = if self.miner.stamina < self.config.stamina_throw_cost {
+                Color::new(0.9, 0.2, 0.2, 1.0) // 不够出一次钩
+            } else {
+                Color::new(0.2, 0.8, 0.3, 1.0)
+            };
+            draw_bar(ctx, bar, fraction, color)?;
+        }
+
+        // 矿工的心，被砸晕时变暗
+        for i in 0..self.config.miner_hearts {
+            let heart = Rect::new(140.0 + i as f32 * 16.0, 38.0, 12.0, 12.0);
+            let color = if i >= self.miner.hearts {
+                Color::new(0.3, 0.3, 0.3, 1.0)
+            } else if self.miner.stun > 0 {
+                Color::new(0.6, 0.2, 0.25, 1.0)
+            } else {
+                Color::new(0.9, 0.15, 0.25, 1.0)
+            };
+            graphics::rectangle(ctx, graphics::DrawParam::default().dest(heart.point()), &heart, color)?;
+        }
+
+        // 钻头冷却
+        if self.drill_unlocked {
+            let bar = Rect::new(10.0, 64.0, 120.0, 8.0);
+            draw_bar(ctx, bar, self.drill.readiness(), Color::new(0.6, 0.6, 0.7, 1.0))?;
+        }
+
+        // 缆绳油量
+        if self.config.cable_oil {
+            let bar = Rect::new(10.0, 52.0, 120.0, 8.0);
+            let fraction = self.cable_oil / self.config.max_cable_oil;
+            draw_bar(ctx, bar, fraction, ItemType::OilCan.color())?;
+        }
+
+        // 绞盘燃料，烧干时整条变红；放在钻头冷却下面一行，窄一些免得压到 y = 80 的关卡目标
+        if self.config.winch_fuel {
+            let bar = Rect::new(10.0, 74.0, 120.0, 4.0);
+            let fraction = self.winch_fuel / self.config.max_winch_fuel;
+            let color = if self.winch_fuel <= 0.0 { Color::RED } else { ItemType::FuelCan.color() };
+            draw_bar(ctx, bar, fraction, color)?;
+        }
+
+        let dynamite_text = Text::new(TextFragment::new(format!("Dynamite: {}", self.dynamite))
+            .color(Color::new(0.85, 0.1, 0.1, 1.0))
+            .font_size(18));
+        graphics::draw(
+            ctx,
+            &dynamite_text,
+            graphics::DrawParam::default().dest(na::Point2::new(150.0, 14.0)),
+        )?;
+
+        if self.insurance > 0 {
+            let insurance_text = Text::new(TextFragment::new(format!("Insurance: {}", self.insurance))
+                .color(Color::new(0.4, 0.8, 1.0, 1.0))
+                .font_size(16));
+            graphics::draw(ctx, &insurance_text, graphics::DrawParam::default().dest(na::Point2::new(150.0, 76.0)))?;
+        }
+
+        if self.keys > 0 {
+            let keys_text = Text::new(TextFragment::new(format!("Keys: {}", self.keys))
+                .color(ItemType::Key.color())
+                .font_size(16));
+            graphics::draw(ctx, &keys_text, graphics::DrawParam::default().dest(na::Point2::new(150.0, 56.0)))?;
+        }
+
+        // 增益图标
+        self.buffs.draw(ctx, 280.0, 12.0)?;
+
+        // 连击
+        self.combo.draw(ctx, screen_w - 190.0, 44.0)?;
+
+        // 风向和风力，箭头越多风越大
+        if self.wind != 0.0 && self.config.max_wind > 0.0 {
+            let arrows = ((self.wind.abs() / self.config.max_wind * 3.0).ceil() as usize).max(1);
+            let arrow = if self.wind > 0.0 { ">" } else { "<" };
+            let wind_text = Text::new(TextFragment::new(format!("Wind {}", arrow.repeat(arrows)))
+                .color(Color::new(0.8, 0.9, 1.0, 1.0))
+                .font_size(16));
+            graphics::draw(ctx, &wind_text, graphics::DrawParam::default().dest(na::Point2::new(screen_w - 190.0, 70.0)))?;
+        }
+
+        // 剧情关卡目标
+        if let Some(story) = &self.story {
+            let prestige = match self.prestige.level {
+                0 => String::new(),
+                level => format!("   Prestige {}", level),
+            };
+            let goal_text = Text::new(TextFragment::new(format!(
+                "Level {}   Target: ${}{}",
+                self.level,
+                story.current().target,
+                prestige,
+            ))
+            .color(Color::new(1.0, 0.84, 0.0, 1.0))
+            .font_size(16));
+            graphics::draw(
+                ctx,
+                &goal_text,
+                graphics::DrawParam::default().dest(na::Point2::new(10.0, 80.0)),
+            )?;
+        }
+
+        // 自由模式关卡目标
+        if self.is_freeplay() {
+            let target = self.level_target().unwrap_or(0);
+            let goal = match self.mode {
+                GameMode::Classic if self.tutorial.is_some() => "Tutorial - learn the ropes".to_string(),
+                GameMode::Classic if self.bonus_stage => {
+                    format!("Bonus Stage - {}   No target, just gold!", self.world.theme().title)
+                }
+                GameMode::Classic => {
+                    format!("Level {} - {}   Target: ${}", self.level, self.world.theme().title, target)
+                }
+                GameMode::Endless => format!(
+                    "Checkpoint {}   Goal: ${}   Best: ${}",
+                    self.endless.checkpoint + 1,
+                    target,
+                    self.records.best(GameMode::Endless.name()).unwrap_or(0)
+                ),
+                GameMode::Puzzle => format!(
+                    "Puzzle {}   Target: ${}   Throws left: {}",
+                    self.level, target, self.throws_left
+                ),
+                GameMode::Daily => format!(
+                    "Daily Challenge #{}   Best today: ${}",
+                    self.daily_day,
+                    self.records.best(&modes::daily_key(self.daily_day)).unwrap_or(0)
+                ),
+                GameMode::Versus if self.versus_cpu => format!(
+                    "You: ${}   CPU ({}): ${}",
+                    self.level_score,
+                    self.ai_skill.label(),
+                    self.rival.as_ref().map_or(0, |rival| rival.score)
+                ),
+                GameMode::Versus => format!(
+                    "Player 1 (arrows): ${}   Player 2 (A/D/S): ${}",
+                    self.level_score,
+                    self.rival.as_ref().map_or(0, |rival| rival.score)
+                ),
+                GameMode::Coop => format!(
+                    "Co-op Level {} - {}   Team target: ${}",
+                    self.level,
+                    self.world.theme().title,
+                    target
+                ),
+                GameMode::Sandbox => format!(
+                    "Sandbox - placing {}   Click: place   Tab: next item   C: clear   Esc: menu",
+                    self.sandbox.current().name()
+                ),
+                GameMode::TimeAttack => format!(
+                    "Items left: {}   Best: {}",
+                    self.items_left(),
+                    self.records
+                        .best(GameMode::TimeAttack.name())
+                        .map_or("-".to_string(), |millis| modes::format_millis(millis as u64))
+                ),
+            };
+            let goal_text = Text::new(TextFragment::new(goal)
+                .color(Color::new(1.0, 0.84, 0.0, 1.0))
+                .font_size(16));
+            graphics::draw(
+                ctx,
+                &goal_text,
+                graphics::DrawParam::default().dest(na::Point2::new(10.0, 80.0)),
+            )?;
+        }
+
+        // 附加目标
+        if !self.objectives.is_empty() {
+            self.objectives.draw(ctx, 10.0, 100.0)?;
+        }
+
+        // 战役钱包和下一笔欠款
+        if let Some(campaign) = &self.campaign {
+            let plus = if campaign.plus > 0 { format!("NG+{}   ", campaign.plus) } else { String::new() };
+            let debt_text = Text::new(TextFragment::new(format!(
+                "{}Level {}   Wallet: ${}   Due: ${} in {} level(s)",
+                plus,
+                self.level,
+                self.wallet,
+                campaign.next_payment(),
+                campaign.levels_until_due(self.level),
+            ))
+            .color(Color::new(1.0, 0.84, 0.0, 1.0))
+            .font_size(16));
+            graphics::draw(
+                ctx,
+                &debt_text,
+                graphics::DrawParam::default().dest(na::Point2::new(10.0, 80.0)),
+            )?;
+        }
+
+        // 教学关当前这一步的提示
+        if let Some(tutorial) = &self.tutorial {
+            let prompt = tutorial.prompt(self.config.pendulum);
+            let prompt = match tutorial.progress() {
+                Some((step, steps)) => format!("Tutorial {}/{}: {}", step, steps, prompt),
+                None => prompt.to_string(),
+            };
+            draw_centered_text(ctx, screen_w, &prompt, 22.0, Color::new(1.0, 0.84, 0.0, 1.0), 120.0)?;
+        }
+
+        // 计时赛显示精确到毫秒的已用时间，其他模式显示剩余秒数
+        let (time, time_x) = match self.mode {
+            GameMode::TimeAttack => (format!("Time: {}", modes::format_millis(modes::ticks_to_millis(self.tick))), 190.0),
+            _ if self.tutorial.is_some() || self.mode == GameMode::Sandbox => ("Time: --".to_string(), 120.0),
+            _ => (format!("Time: {}s", time_left_seconds), 120.0),
+        };
+        let time_text = Text::new(TextFragment::new(time)
+            .color(Color::WHITE)
+            .font_size(24));
+        graphics::draw(
+            ctx,
+            &time_text,
+            graphics::DrawParam::default().dest(na::Point2::new(screen_w - time_x, 10.0)),
+        )?;
+
+        // 当日行情跑马灯
+        if self.config.market_volatility > 0.0 {
+            self.market.draw_ticker(ctx, screen_w, screen_h)?;
+        }
+
+        // 如果游戏结束，绘制游戏结束界面
+        if self.game_over {
+            // 自由模式按是否达标显示过关或失败
+            let (title, title_color) = if self.knocked_out() {
+                ("Knocked Out!", Color::RED)
+            } else if !self.is_freeplay() {
+                ("Game Over!", Color::RED)
+            } else if self.mode == GameMode::Endless {
+                ("Run Over", Color::RED)
+            } else if self.mode == GameMode::Puzzle && !self.level_cleared() {
+                ("Out of Throws", Color::RED)
+            } else if self.mode == GameMode::Daily {
+                ("Daily Challenge Over", Color::new(1.0, 0.84, 0.0, 1.0))
+            } else if let Some(rival) = self.rival.as_ref().filter(|_| self.mode == GameMode::Versus) {
+                (versus::verdict(self.level_score, rival.score, self.versus_cpu), Color::new(1.0, 0.84, 0.0, 1.0))
+            } else if self.mode == GameMode::TimeAttack && !self.mine_cleared() {
+                ("Out of Time", Color::RED)
+            } else if self.level_cleared() {
+                (if self.tutorial.is_some() { "Tutorial Complete!" } else { "Level Cleared!" }, Color::new(1.0, 0.84, 0.0, 1.0))
+            } else {
+                ("Level Failed", Color::RED)
+            };
+            if let Some(stars) = self.level_stars() {
+                draw_stars(ctx, screen_w / 2.0, screen_h / 2.0 - 80.0, 16.0, stars)?;
+            }
+            let game_over_text = Text::new(TextFragment::new(title)
+                .color(title_color)
+                .font_size(48));
+            let game_over_rect = game_over_text.dimensions(ctx)?;
+            graphics::draw(
+                ctx,
+                &game_over_text,
+                graphics::DrawParam::default().dest(na::Point2::new(
+                    screen_w / 2.0 - game_over_rect.w / 2.0,
+                    screen_h / 2.0 - 50.0,
+                )),
+            )?;
+
+            let final_score = match self.mode {
+                GameMode::TimeAttack if self.mine_cleared() => {
+                    format!("Cleared in {}", modes::format_millis(modes::ticks_to_millis(self.tick)))
+                }
+                GameMode::Versus => {
+                    format!("Player 1: ${}   Player 2: ${}", self.level_score, self.rival.as_ref().map_or(0, |rival| rival.score))
+                }
+                _ => format!("Level Score: {}   Wallet: ${}", self.level_score, self.wallet),
+            };
+            let final_score_text = Text::new(TextFragment::new(final_score)
+                .color(Color::WHITE)
+                .font_size(32));
+            let final_score_rect = final_score_text.dimensions(ctx)?;
+            graphics::draw(
+                ctx,
+                &final_score_text,
+                graphics::DrawParam::default().dest(na::Point2::new(
+                    screen_w / 2.0 - final_score_rect.w / 2.0,
+                    screen_h / 2.0 + 10.0,
+                )),
+            )?;
+
+            let hint = if self.is_replay() {
+                "Replay finished - Esc: menu"
+            } else if self.mode == GameMode::Versus {
+                "Enter: rematch   Esc: menu"
+            } else if self.tutorial.is_some() {
+                "Enter: start level 1   Esc: menu"
+            } else if self.mode == GameMode::Coop && !self.level_cleared() {
+                "Enter: retry   Esc: menu"
+            } else if self.mode == GameMode::Coop {
+                "Enter: next level   Esc: menu"
+            } else if !matches!(self.mode, GameMode::Classic | GameMode::Puzzle) {
+                "Enter: new run   S: export replay   E: export stats   Esc: menu"
+            } else if self.is_freeplay() && !self.level_cleared() {
+                "Enter: retry   S: export replay   E: export stats   Esc: menu"
+            } else if self.story.is_some() || self.is_freeplay() {
+                "Enter: next level   S: export replay   E: export stats   Esc: menu"
+            } else if self.campaign.as_ref().map_or(false, |c| !c.failed) {
+                "Enter: shop   S: export replay   E: export stats   Esc: menu"
+            } else {
+                "S: export replay   E: export stats   Esc: menu"
+            };
+            draw_centered_text(ctx, screen_w, hint, 20.0, Color::WHITE, screen_h / 2.0 + 60.0)?;
+
+            // 附加目标的完成情况
+            if !self.objectives.is_empty() {
+                let summary = self.objectives.summary();
+                draw_centered_text(ctx, screen_w, &summary, 20.0, Color::new(0.3, 0.9, 0.3, 1.0), screen_h / 2.0 + 95.0)?;
+            }
+            if self.slots_available() {
+                let slots = "G: try your luck at the slot machine";
+                draw_centered_text(ctx, screen_w, slots, 18.0, Color::new(1.0, 0.84, 0.0, 1.0), screen_h / 2.0 + 125.0)?;
+            }
+        }
+
+        if self.is_replay() && !self.game_over {
+            let replay_text = Text::new(TextFragment::new("REPLAY")
+                .color(Color::RED)
+                .font_size(24));
+            graphics::draw(
+                ctx,
+                &replay_text,
+                graphics::DrawParam::default().dest(na::Point2::new(screen_w / 2.0 - 40.0, 10.0)),
+            )?;
+        }
+
+        if self.show_analysis {
+            if let Some(analysis) = &self.analysis {
+                analysis.draw(ctx, self.tick)?;
+            }
+        }
+
+        if let Some(dialogue) = self.dialogue.as_mut() {
+            dialogue.draw(ctx, &self.strings, screen_w, screen_h)?;
+        } else if let Some(offer) = &self.contract_offer {
+            offer.draw(ctx, screen_w, screen_h)?;
+        } else if let Some(message) = &self.message {
+            draw_centered_text(ctx, screen_w, message, 18.0, Color::WHITE, screen_h - 40.0)?;
+        }
+
+        self.achievements.draw_toasts(ctx, screen_w)?;
+
+        let view = self.plugin_view();
+        self.plugins.draw_overlay(ctx, &view)?;
+
+        if profiler::enabled() {
+            profiler::draw_overlay(ctx, screen_w)?;
+            diagnostics::draw_overlay(ctx, self)?;
+        }
+
+        graphics::present(ctx)?;
+        Ok(())
+    }
+
+    // 鼠标悬停物品的鉴定价值
+    fn draw_appraisal(&self, ctx: &mut Context) -> GameResult {
+        let mouse = ctx.mouse.position();
+        let mouse = self.camera.to_world(na::Point2::new(mouse.x, mouse.y));
+        let hovered = self.items.iter().find(|item| {
+            let half = item.size() / 2.0;
+            !item.collected
+                && (item.position.x - mouse.x).abs() < half
+                && (item.position.y - mouse.y).abs() < half
+        });
+        if let Some(item) = hovered {
+            let text = Text::new(TextFragment::new(format!("{}: ${}", item.item_type.name(), item.value(&self.value_context())))
+                .color(Color::WHITE)
+                .font_size(16));
+            graphics::draw(
+                ctx,
+                &text,
+                graphics::DrawParam::default().dest(na::Point2::new(mouse.x + 12.0, mouse.y - 20.0)),
+            )?;
+        }
+        Ok(())
+    }
+
+    // 绘制主菜单
+    fn draw_menu(&mut self, ctx: &mut Context) -> GameResult {
+        let (screen_w, screen_h) = (self.config.screen_width, self.config.screen_height);
+        draw_centered_text(ctx, screen_w, "Gold Miner", 48.0, Color::new(1.0, 0.84, 0.0, 1.0), 150.0)?;
+        if self.prestige.level > 0 {
+            let title = format!("Prestige {}", self.prestige.level);
+            draw_centered_text(ctx, screen_w, &title, 20.0, self.prestige.rope_color(), 205.0)?;
+        }
+
+        for (i, entry) in MENU_ENTRIES.iter().enumerate() {
+            let color = if i == self.menu_selected {
+                Color::new(1.0, 0.84, 0.0, 1.0)
+            } else {
+                Color::WHITE
+            };
+            let y = 228.0 + i as f32 * 20.0;
+            if i == VERSUS_CPU_ENTRY {
+                let label = format!("{}: {}", entry, self.ai_skill.label());
+                draw_centered_text(ctx, screen_w, &label, 22.0, color, y)?;
+            } else {
+                draw_centered_text(ctx, screen_w, entry, 22.0, color, y)?;
+            }
+        }
+        let difficulty = format!("<  Difficulty: {}  >", self.difficulty.label());
+        let difficulty_y = 232.0 + MENU_ENTRIES.len() as f32 * 20.0;
+        draw_centered_text(ctx, screen_w, &difficulty, 18.0, Color::new(0.7, 0.85, 1.0, 1.0), difficulty_y)?;
+
+        // 上次崩溃的快照
+        if let Some((replay, _)) = &self.crash_resume {
+            let panel = Rect::new(60.0, screen_h / 2.0 - 60.0, screen_w - 120.0, 120.0);
+            graphics::rectangle(
+                ctx,
+                graphics::DrawParam::default().dest(panel.point()),
+                &panel,
+                Color::new(0.1, 0.05, 0.05, 0.95),
+            )?;
+            let seconds = replay.header.duration / TICKS_PER_SECOND as u64;
+            let prompt = format!("Sorry - the game crashed last time (level {}, {}s in).", replay.header.level, seconds);
+            draw_centered_text(ctx, screen_w, &prompt, 20.0, Color::WHITE, panel.y + 25.0)?;
+            draw_centered_text(ctx, screen_w, "Y: resume   N: discard", 20.0, Color::new(1.0, 0.84, 0.0, 1.0), panel.y + 70.0)?;
+        }
+
+        // 各模式的最佳纪录
+        let best = |mode: GameMode| self.records.best(mode.name()).map_or("-".to_string(), |value| format!("${}", value));
+        let fastest = self.records.best(GameMode::TimeAttack.name()).map_or("-".to_string(), |millis| modes::format_millis(millis as u64));
+        let daily = self.records.best(&modes::daily_key(modes::today())).map_or("-".to_string(), |value| format!("${}", value));
+        let records = format!(
+            "Best  Classic: {}   Endless: {}   Time Attack: {}   Today: {}",
+            best(GameMode::Classic),
+            best(GameMode::Endless),
+            fastest,
+            daily
+        );
+        draw_centered_text(ctx, screen_w, &records, 16.0, Color::new(1.0, 0.84, 0.0, 1.0), screen_h - 95.0)?;
+
+        // 遥测开关状态
+        let telemetry = if self.config.telemetry {
+            "Anonymous telemetry: ON  (T to disable)"
+        } else {
+            "Anonymous telemetry: OFF  (T to enable and help balancing)"
+        };
+        draw_centered_text(ctx, screen_w, telemetry, 14.0, Color::new(0.7, 0.7, 0.7, 1.0), screen_h - 70.0)?;
+
+        if let Some(message) = &self.message {
+            draw_centered_text(ctx, screen_w, message, 18.0, Color::WHITE, screen_h - 40.0)?;
+        }
+        Ok(())
+    }
+}
+
+// 水平居中绘制一行文字
+fn draw_centered_text(ctx: &mut Context, screen_w: f32, text: &str, size: f32, color: Color, y: f32) -> GameResult {
+    let text = Text::new(TextFragment::new(text)
+        .color(color)
+        .font_size(size));
+    let rect = text.dimensions(ctx)?;
+    graphics::draw(
+        ctx,
+        &text,
+        graphics::DrawParam::default().dest(na::Point2::new(screen_w / 2.0 - rect.w / 2.0, y)),
+    )
+}
+
+// 绘制进度条：深色底框加按比例填充
+fn draw_bar(ctx: &mut Context, rect: Rect, fraction: f32, color: Color) -> GameResult {
+    graphics::rectangle(
+        ctx,
+        graphics::DrawParam::default().dest(rect.point()),
+        &rect,
+        Color::new(0.2, 0.2, 0.2, 0.8),
+    )?;
+    let fraction = fraction.max(0.0).min(1.0);
+    if fraction > 0.0 {
+        let fill = Rect::new(rect.x, rect.y, rect.w * fraction, rect.h);
+        graphics::rectangle(ctx, graphics::DrawParam::default().dest(fill.point()), &fill, color)?;
+    }
+    Ok(())
+}
+
+// 以 (center_x, y) 为中心画一排星级，得到的星涂金色，其余为灰色
+fn draw_stars(ctx: &mut Context, center_x: f32, y: f32, radius: f32, earned: u32) -> GameResult {
+    let spacing = radius * 2.5;
+    let first_x = center_x - spacing * (levels::MAX_STARS - 1) as f32 / 2.0;
+    for i in 0..levels::MAX_STARS {
+        let center = na::Point2::new(first_x + spacing * i as f32, y);
+        // 五角星的十个顶点，内外半径交替，首尾相接
+        let points: Vec<na::Point2<f32>> = (0..=10)
+            .map(|k| {
+                let angle = -std::f32::consts::FRAC_PI_2 + k as f32 * std::f32::consts::PI / 5.0;
+                let r = if k % 2 == 0 { radius } else { radius * 0.45 };
+                na::Point2::new(center.x + r * angle.cos(), center.y + r * angle.sin())
+            })
+            .collect();
+        let color = if i < earned {
+            Color::new(1.0, 0.84, 0.0, 1.0)
+        } else {
+            Color::new(0.4, 0.4, 0.4, 1.0)
+        };
+        graphics::line(ctx, graphics::DrawParam::default(), &points, 3.0, color)?;
+    }
+    Ok(())
+}
+
+// 读取回放文件，错误转为提示文字
+fn load_replay(path: &Path) -> Result<Replay, String> {
+    Replay::load(path).map_err(|err| format!("Failed to open replay: {}", err))
+}
+
+impl EventHandler for GameState {
+    fn update(&mut self, ctx: &mut Context, _dt: f32) -> GameResult {
+        profiler::new_frame();
+        profile_scope!("update");
+        // 固定步长更新，保证回放与录制时逐帧一致
+        while ctx.time.check_update_time(TICKS_PER_SECOND) {
+            self.update(1.0 / TICKS_PER_SECOND as f32);
+            self.achievements.update(1.0 / TICKS_PER_SECOND as f32);
+        }
+        self.sfx.play_pending(ctx);
+        // 插件的后台任务失败时提示玩家
+        if let Some(message) = self.plugins.take_message() {
+            self.message = Some(message);
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, ctx: &mut Context, graphics: &mut graphics::GraphicsContext) -> GameResult {
+        self.draw(ctx, graphics)
+    }
+
+    fn key_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        keycode: KeyCode,
+        _keymods: KeyMods,
+        _repeat: bool,
+    ) -> GameResult {
+        // F3 随时开关性能分析
+        if keycode == KeyCode::F3 {
+            profiler::toggle();
+            return Ok(());
+        }
+
+        // 上次崩溃的快照：Y 继续，N 放弃
+        if self.scene == Scene::MainMenu && self.crash_resume.is_some() {
+            match keycode {
+                KeyCode::Y => self.resume_from_crash(),
+                KeyCode::N | KeyCode::Escape => {
+                    self.crash_resume = None;
+                    crash::discard_resume();
+                }
+                _ => (),
+            }
+            return Ok(());
+        }
+
+        if self.scene == Scene::MainMenu {
+            match keycode {
+                KeyCode::Up => {
+                    self.menu_selected = (self.menu_selected + MENU_ENTRIES.len() - 1) % MENU_ENTRIES.len();
+                }
+                KeyCode::Down => {
+                    self.menu_selected = (self.menu_selected + 1) % MENU_ENTRIES.len();
+                }
+                KeyCode::Left if self.menu_selected == VERSUS_CPU_ENTRY => self.ai_skill = self.ai_skill.easier(),
+                KeyCode::Right if self.menu_selected == VERSUS_CPU_ENTRY => self.ai_skill = self.ai_skill.harder(),
+                KeyCode::Left => self.difficulty = self.difficulty.easier(),
+                KeyCode::Right => self.difficulty = self.difficulty.harder(),
+                KeyCode::Return | KeyCode::Space => self.select_menu_entry(),
+                KeyCode::P => self.prestige_reset(),
+                KeyCode::T => self.toggle_telemetry(),
+                _ => (),
+            }
+            return Ok(());
+        }
+
+        if self.scene == Scene::Achievements {
+            if matches!(keycode, KeyCode::Escape | KeyCode::Return | KeyCode::Space) {
+                self.scene = Scene::MainMenu;
+            }
+            return Ok(());
+        }
+
+        // 选关地图：方向键沿小路移动，回车开始
+        if self.scene == Scene::LevelSelect {
+            if let Some(level_select) = self.level_select.as_mut() {
+                match keycode {
+                    KeyCode::Left => level_select.step(-1),
+                    KeyCode::Right => level_select.step(1),
+                    KeyCode::Up => level_select.step_row(false),
+                    KeyCode::Down => level_select.step_row(true),
+                    KeyCode::Return | KeyCode::Space => {
+                        let level = level_select.selected;
+                        self.start_selected_level(level);
+                    }
+                    KeyCode::T => self.start_tutorial(),
+                    KeyCode::Escape => {
+                        self.level_select = None;
+                        self.scene = Scene::MainMenu;
+                    }
+                    _ => (),
+                }
+            }
+            return Ok(());
+        }
+
+        // 老虎机：左右调押注，空格开转，转完才能离开
+        if self.scene == Scene::Slots {
+            if let Some(slots) = self.slots.as_mut() {
+                match keycode {
+                    KeyCode::Left | KeyCode::Down => slots.lower(self.wallet),
+                    KeyCode::Right | KeyCode::Up => slots.raise(self.wallet),
+                    KeyCode::Space | KeyCode::Return => slots.spin(&mut self.wallet),
+                    KeyCode::Escape if !slots.spinning => {
+                        self.slots = None;
+                        self.scene = Scene::Playing;
+                    }
+                    _ => (),
+                }
+            }
+            return Ok(());
+        }
+
+        // 商店：先和店主说完话，再挑选、还价、购买
+        if self.scene == Scene::Shop {
+            if self.dialogue_key(keycode) {
+                return Ok(());
+            }
+            let haggling = self.shop.as_ref().map_or(false, |shop| shop.haggle.is_some());
+            match keycode {
+                KeyCode::Escape => self.leave_shop(),
+                KeyCode::Return => self.buy_from_shop(),
+                _ => {
+                    if let Some(shop) = self.shop.as_mut() {
+                        match keycode {
+                            KeyCode::Up => shop.select_previous(),
+                            KeyCode::Down => shop.select_next(),
+                            KeyCode::H => shop.haggle(),
+                            KeyCode::Space if haggling => shop.haggle(),
+                            _ => (),
+                        }
+                    }
+                }
+            }
+            return Ok(());
+        }
+
+        // 过场动画：空格/回车下一张，Esc 跳过
+        if self.scene == Scene::Cutscene {
+            match keycode {
+                KeyCode::Space | KeyCode::Return => {
+                    if let Some(cutscene) = self.cutscene.as_mut() {
+                        cutscene.next_slide();
+                        if cutscene.finished() {
+                            self.end_cutscene();
+                        }
+                    }
+                }
+                KeyCode::Escape => self.end_cutscene(),
+                _ => (),
+            }
+            return Ok(());
+        }
+
+        if keycode == KeyCode::Escape && (self.game_over || self.is_replay() || self.mode == GameMode::Sandbox) {
+            crash::clear_snapshot();
+            self.scene = Scene::MainMenu;
+            self.input = Box::new(KeyboardInput::new());
+            self.analysis = None;
+            self.show_analysis = false;
+            self.message = None;
+            return Ok(());
+        }
+
+        // 回放分析：Tab 切换，左右方向键前后跳转 5 秒
+        if self.is_replay() {
+            let step = 5 * TICKS_PER_SECOND as u64;
+            match keycode {
+                KeyCode::Tab => self.toggle_analysis(),
+                KeyCode::Left if self.show_analysis => self.seek_replay(self.tick.saturating_sub(step)),
+                KeyCode::Right if self.show_analysis => self.seek_replay(self.tick + step),
+                _ => (),
+            }
+            return Ok(());
+        }
+
+        if self.game_over {
+            match keycode {
+                KeyCode::G if self.slots_available() => {
+                    self.slots = Some(SlotMachine::new(self.wallet, thread_rng().gen()));
+                    self.scene = Scene::Slots;
+                }
+                KeyCode::S | KeyCode::E if !self.replayable() => (),
+                KeyCode::S => self.export_replay(),
+                KeyCode::E => self.export_stats(),
+                KeyCode::Return => self.next_level(),
+                _ => (),
+            }
+            return Ok(());
+        }
+
+        if self.dialogue_key(keycode) {
+            return Ok(());
+        }
+
+        // 合约选择：上下选择，回车确认
+        if let Some(offer) = self.contract_offer.as_mut() {
+            match keycode {
+                KeyCode::Up => offer.select_previous(),
+                KeyCode::Down => offer.select_next(),
+                KeyCode::Return | KeyCode::Space => self.accept_contract(),
+                _ => (),
+            }
+            return Ok(());
+        }
+
+        // 沙盒：Tab 换物品，C 清空场地上的物品
+        if self.mode == GameMode::Sandbox {
+            match keycode {
+                KeyCode::Tab => {
+                    self.sandbox.next();
+                    return Ok(());
+                }
+                KeyCode::C => {
+                    for item in self.items.iter_mut() {
+                        item.collected = true;
+                    }
+                    self.background.invalidate();
+                    return Ok(());
+                }
+                _ => (),
+            }
+        }
+
+        // 双人对战：A/D/S 交给玩家二
+        if let Some(rival) = self.rival.as_mut() {
+            let angle = self.hooks.iter().find(|hook| hook.player == RIVAL).map_or(0.0, |hook| hook.angle);
+            if let Some(action) = versus::key_action(keycode, angle) {
+                if matches!(action, InputAction::MoveLeft | InputAction::MoveRight) {
+                    rival.steering.press(action);
+                }
+                rival.input.push_local(action);
+                return Ok(());
+            }
+        }
+
+        match keycode {
+            KeyCode::Left => {
+                self.steering.press(InputAction::MoveLeft);
+                self.input.push_local(InputAction::MoveLeft);
+            }
+            KeyCode::Right => {
+                self.steering.press(InputAction::MoveRight);
+                self.input.push_local(InputAction::MoveRight);
+            }
+            KeyCode::D => {
+                self.input.push_local(InputAction::Drill);
+            }
+            KeyCode::Space if self.config.charge_throw => {
+                // 蓄力模式：按下开始蓄力，松开时出钩
+                if self.idle_hook().is_some() {
+                    self.charging = true;
+                }
+            }
+            KeyCode::Up => {
+                self.input.push_local(InputAction::BlastCatch);
+            }
+            KeyCode::Space => {
+                // 计算钩子发射角度（摆动角度或鼠标位置）
+                let angle = self.aim_angle(_ctx);
+                self.input.push_local(InputAction::Throw { angle, power: 1.0 });
+            }
+            _ => (),
+        }
+
+        Ok(())
+    }
+
+    fn key_up_event(&mut self, ctx: &mut Context, keycode: KeyCode, _keymods: KeyMods) -> GameResult {
+        // 松开当前踩着的方向键，矿车开始滑行（还按着另一个方向时换过去）
+        let direction = match keycode {
+            KeyCode::Left => Some(InputAction::MoveLeft),
+            KeyCode::Right => Some(InputAction::MoveRight),
+            _ => None,
+        };
+        if let Some(action) = direction.and_then(|direction| self.steering.release(direction)) {
+            if self.scene == Scene::Playing && !self.is_replay() {
+                self.input.push_local(action);
+            }
+        }
+        let playing = self.scene == Scene::Playing;
+        if let Some(rival) = self.rival.as_mut().filter(|_| playing) {
+            let direction = match keycode {
+                KeyCode::A => Some(InputAction::MoveLeft),
+                KeyCode::D => Some(InputAction::MoveRight),
+                _ => None,
+            };
+            if let Some(action) = direction.and_then(|direction| rival.steering.release(direction)) {
+                rival.input.push_local(action);
+            }
+        }
+
+        if keycode == KeyCode::Space && self.charging {
+            self.charging = false;
+            let angle = self.aim_angle(ctx);
+            let power = self.throw_power();
+            self.input.push_local(InputAction::Throw { angle, power });
+            self.throw_charge = 0.0;
+        }
+        Ok(())
+    }
+
+    fn mouse_wheel_event(&mut self, _ctx: &mut Context, _x: f32, y: f32) -> GameResult {
+        // 蓄力模式下滚轮直接调节力度
+        if self.config.charge_throw && self.scene == Scene::Playing && !self.is_replay() {
+            self.throw_charge = (self.throw_charge + y * 0.1).max(0.0).min(1.0);
+        }
+        Ok(())
+    }
+
+    fn mouse_button_down_event(
+        &mut self,
+        _ctx: &mut Context,
+        button: MouseButton,
+        x: f32,
+        y: f32,
+    ) -> GameResult {
+        // 右键向鼠标位置抛炸药
+        if button == MouseButton::Right && self.scene == Scene::Playing && !self.game_over && !self.is_replay() {
+            let target = self.camera.to_world(na::Point2::new(x, y));
+            self.input.push_local(InputAction::LobDynamite { x: target.x, y: target.y });
+            return Ok(());
+        }
+
+        // 沙盒里左键在鼠标处放物品
+        if button == MouseButton::Left && self.scene == Scene::Playing && self.mode == GameMode::Sandbox && !self.game_over {
+            let position = self.camera.to_world(na::Point2::new(x, y));
+            let item = self.sandbox.spawn(position, &self.config);
+            self.items.push(item);
+            self.background.invalidate();
+            return Ok(());
+        }
+
+        // 点击选关地图上已解锁的关卡
+        if button == MouseButton::Left && self.scene == Scene::LevelSelect {
+            let screen_w = self.config.screen_width;
+            if let Some(level) = self.level_select.as_ref().and_then(|l| l.level_at(x, y, screen_w)) {
+                self.start_selected_level(level);
+            }
+            return Ok(());
+        }
+
+        // 点击进度条跳转回放
+        if self.show_analysis {
+            if let Some(target) = self.analysis.as_ref().and_then(|a| a.scrubber_tick(x, y)) {
+                self.seek_replay(target);
+            }
+        }
+        Ok(())
+    }
+}
 
 fn main() -> GameResult {
+    crash::install();
+    let (config, warning) = GameConfig::load_or_default();
+
+    // --simulate <局数>：不开窗口，用机器人跑模拟
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(pos) = args.iter().position(|arg| arg == "--simulate") {
+        let episodes = args.get(pos + 1).and_then(|n| n.parse().ok()).unwrap_or(10);
+        if let Some(warning) = &warning {
+            println!("{}", warning);
+        }
+        sim::run_bot_episodes(config, episodes);
+        return Ok(());
+    }
+
     let cb = ggez::ContextBuilder::new("gold_miner", "ggez")
         .window_setup(ggez::conf::WindowSetup::default().title("黄金矿工"))
-        .window_mode(ggez::conf::WindowMode::default().dimensions(SCREEN_WIDTH, SCREEN_HEIGHT));
+        .window_mode(ggez::conf::WindowMode::default().dimensions(config.screen_width, config.screen_height));
 
     let (mut ctx, event_loop) = cb.build()?;
-    let mut state = GameState::new(&mut ctx)?;
+    let mut state = GameState::new(&mut ctx, config)?;
+    if let Some(warning) = warning {
+        state.message = Some(warning);
+    }
 
     event::run(ctx, event_loop, state)
 }
@@ -0,0 +1,77 @@
+// 行情：每关各类物品的收购价在基准价上下浮动（今天金价涨两成、石头一文不值），
+// 由种子和关卡决定，回放时行情与录制时一致
+use ggez::{
+    graphics::{self, Color, Text, TextFragment},
+    nalgebra as na, Context, GameResult,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{BagSize, ItemType};
+
+// 参与行情的物品（油桶不计价）
+const TRADED: [ItemType; 4] = [ItemType::Gold(BagSize::Medium), ItemType::Silver, ItemType::Diamond, ItemType::Rock];
+const TICKER_SPEED: f32 = 60.0; // 跑马灯每秒滚动的像素
+
+#[derive(Debug, Clone)]
+pub struct Market {
+    prices: Vec<(ItemType, f32)>, // 价格倍率，1.0 为基准价
+    ticker_offset: f32,
+}
+
+impl Market {
+    // 按种子和关卡生成当天行情，volatility 为最大浮动比例
+    pub fn roll(seed: u64, level: u32, volatility: f32) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed ^ (level as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+        let prices = TRADED
+            .iter()
+            .map(|&item_type| {
+                let factor = if volatility > 0.0 {
+                    rng.gen_range(1.0 - volatility..=1.0 + volatility)
+                } else {
+                    1.0
+                };
+                (item_type, (factor * 20.0).round() / 20.0) // 取整到 5%，方便看行情
+            })
+            .collect();
+        Market { prices, ticker_offset: 0.0 }
+    }
+
+    // 某类物品的价格倍率
+    pub fn price(&self, item_type: ItemType) -> f32 {
+        self.prices
+            .iter()
+            .find(|(t, _)| t.same_kind(item_type))
+            .map_or(1.0, |(_, factor)| *factor)
+    }
+
+    // 行情文字，例如 "Gold +20%   Rock -35%"
+    pub fn headline(&self) -> String {
+        self.prices
+            .iter()
+            .map(|(item_type, factor)| {
+                let change = ((factor - 1.0) * 100.0).round() as i32;
+                format!("{} {:+}%", item_type.name(), change)
+            })
+            .collect::<Vec<_>>()
+            .join("   ")
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.ticker_offset += TICKER_SPEED * dt;
+    }
+
+    // 在屏幕底部绘制滚动行情
+    pub fn draw_ticker(&self, ctx: &mut Context, screen_width: f32, screen_height: f32) -> GameResult {
+        let line = format!("MARKET  {}", self.headline());
+        let span = screen_width + line.len() as f32 * 8.0;
+        let x = screen_width - self.ticker_offset % span;
+        let text = Text::new(TextFragment::new(line)
+            .color(Color::new(1.0, 0.9, 0.4, 1.0))
+            .font_size(16));
+        graphics::draw(
+            ctx,
+            &text,
+            graphics::DrawParam::default().dest(na::Point2::new(x, screen_height - 22.0)),
+        )
+    }
+}
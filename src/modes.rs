@@ -0,0 +1,123 @@
+// 玩法模式：经典闯关（自由模式、还债战役、剧情战役都属于经典）之外的独立模式
+//
+// 无尽模式：计时不停，每 60 秒一个检查点，累计得分要达到逐步抬高的目标，
+// 没达到则本局结束；场上的物品不够时定期补充
+//
+// 计时赛：没有目标分，把场上的物品全部拉完即结束，成绩是用时（毫秒），越快越好
+//
+// 解谜模式：每关只给固定几次出钩，用完之前要达到目标分，达标时剩下的每一钩折算成奖励分
+//
+// 每日挑战：种子由当天日期（UTC）决定，同一天所有玩家拿到完全相同的布局，纪录按天分开保存
+//
+// 双人对战：两名玩家在同一片矿场里抢东西，得分分开计算，时间到后比高低，不计纪录
+//
+// 双人合作：两名玩家一起挖，得分合在一起冲同一个目标分，达标进入下一关，不计纪录
+//
+// 沙盒练习：不计时，没有目标分和危险物，可以在鼠标处随意放物品练习出钩，不计纪录
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::TICKS_PER_SECOND;
+
+pub const CHECKPOINT_SECONDS: u64 = 60;
+pub const REFILL_SECONDS: u64 = 10; // 每隔多久检查一次是否需要补充物品
+pub const TIME_ATTACK_LIMIT_SECONDS: u64 = 300; // 计时赛的时间上限，超时算没跑完
+const FIRST_GOAL: i32 = 600;
+const GOAL_GROWTH: i32 = 200; // 每个检查点目标分增量的增长
+pub const PUZZLE_THROW_BONUS: i32 = 100; // 解谜模式每剩一钩的奖励分
+const DAILY_LEVELS: u64 = 9; // 每日挑战在前几关的布局参数里轮换
+const DAILY_SALT: u64 = 0x676f_6c64_6d69_6e65;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GameMode {
+    #[default]
+    Classic,
+    Endless,
+    TimeAttack,
+    Daily,
+    Puzzle,
+    Versus,
+    Coop,
+    Sandbox,
+}
+
+impl GameMode {
+    // 回放文件和纪录文件里使用的名称
+    pub fn name(self) -> &'static str {
+        match self {
+            GameMode::Classic => "classic",
+            GameMode::Endless => "endless",
+            GameMode::TimeAttack => "time_attack",
+            GameMode::Daily => "daily",
+            GameMode::Puzzle => "puzzle",
+            GameMode::Versus => "versus",
+            GameMode::Coop => "coop",
+            GameMode::Sandbox => "sandbox",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<GameMode> {
+        match name {
+            "classic" => Some(GameMode::Classic),
+            "endless" => Some(GameMode::Endless),
+            "time_attack" => Some(GameMode::TimeAttack),
+            "daily" => Some(GameMode::Daily),
+            "puzzle" => Some(GameMode::Puzzle),
+            "versus" => Some(GameMode::Versus),
+            "coop" => Some(GameMode::Coop),
+            "sandbox" => Some(GameMode::Sandbox),
+            _ => None,
+        }
+    }
+}
+
+// 帧数换算成毫秒
+pub fn ticks_to_millis(ticks: u64) -> u64 {
+    ticks * 1000 / TICKS_PER_SECOND as u64
+}
+
+// 用时显示为 秒.毫秒，例如 83.250s
+pub fn format_millis(millis: u64) -> String {
+    format!("{}.{:03}s", millis / 1000, millis % 1000)
+}
+
+// 今天是从 1970-01-01 起的第几天（UTC），每日挑战的编号
+pub fn today() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() / 86_400)
+        .unwrap_or(0)
+}
+
+// 每日挑战的种子，只由日期决定
+pub fn daily_seed(day: u64) -> u64 {
+    day.wrapping_mul(0x9e37_79b9_7f4a_7c15) ^ DAILY_SALT
+}
+
+// 每日挑战使用第几关的时长、目标和物品数量
+pub fn daily_level(day: u64) -> u32 {
+    1 + (day % DAILY_LEVELS) as u32
+}
+
+// 每日挑战纪录在纪录文件里的键，每天一个
+pub fn daily_key(day: u64) -> String {
+    format!("daily_{}", day)
+}
+
+// 无尽模式的进度
+#[derive(Debug, Clone, Default)]
+pub struct EndlessRun {
+    pub checkpoint: u32, // 已通过的检查点数
+}
+
+impl EndlessRun {
+    // 下一个检查点要求的累计得分：600、1400、2400……
+    pub fn goal(&self) -> i32 {
+        let n = self.checkpoint as i32 + 1;
+        FIRST_GOAL * n + GOAL_GROWTH * n * (n - 1) / 2
+    }
+
+    // 下一个检查点所在的帧
+    pub fn deadline(&self) -> u64 {
+        (self.checkpoint as u64 + 1) * CHECKPOINT_SECONDS * TICKS_PER_SECOND as u64
+    }
+}
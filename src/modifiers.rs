@@ -0,0 +1,67 @@
+// 结算修正：物品拉回时，在增益后的价值上依次套用当前生效的修正（行情、石头图鉴、宝石抛光、声望……），
+// 新的修正只需实现 ScoreModifier 并在 GameState::score_modifiers 里加入
+use crate::market::Market;
+use crate::prestige::Prestige;
+use crate::ItemType;
+
+const ROCK_BOOK_FACTOR: f32 = 3.0;
+const GEM_POLISH_FACTOR: f32 = 1.5;
+const DOUBLE_FACTOR: f32 = 2.0;
+
+pub trait ScoreModifier {
+    fn apply(&self, item_type: ItemType, value: f32) -> f32;
+}
+
+// 依次套用所有修正
+pub fn apply_all(modifiers: &[&dyn ScoreModifier], item_type: ItemType, value: f32) -> f32 {
+    modifiers.iter().fold(value, |value, modifier| modifier.apply(item_type, value))
+}
+
+// 当日行情
+impl ScoreModifier for Market {
+    fn apply(&self, item_type: ItemType, value: f32) -> f32 {
+        value * self.price(item_type)
+    }
+}
+
+// 声望和还债战役新周目的价值加成
+impl ScoreModifier for Prestige {
+    fn apply(&self, _item_type: ItemType, value: f32) -> f32 {
+        value * self.value_bonus()
+    }
+}
+
+// 石头收藏图鉴：石头价值翻三倍
+pub struct RockBook;
+
+impl ScoreModifier for RockBook {
+    fn apply(&self, item_type: ItemType, value: f32) -> f32 {
+        if item_type == ItemType::Rock {
+            value * ROCK_BOOK_FACTOR
+        } else {
+            value
+        }
+    }
+}
+
+// 宝石抛光：钻石等宝石价值提高五成
+pub struct GemPolish;
+
+impl ScoreModifier for GemPolish {
+    fn apply(&self, item_type: ItemType, value: f32) -> f32 {
+        if item_type.is_gem() {
+            value * GEM_POLISH_FACTOR
+        } else {
+            value
+        }
+    }
+}
+
+// 双倍道具：限时内拉回的所有物品价值翻倍
+pub struct DoubleScore;
+
+impl ScoreModifier for DoubleScore {
+    fn apply(&self, _item_type: ItemType, value: f32) -> f32 {
+        value * DOUBLE_FACTOR
+    }
+}
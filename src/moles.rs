@@ -0,0 +1,157 @@
+// 地鼠：在矿洞里横穿而过的小动物，走走停停，有的叼着钻石。
+// 出场时间、位置和方向在开局时按种子排好，回放时完全一致；走出屏幕后消失。
+// 每隔几关还会有一只金色地鼠贴着矿底飞奔而过，只出场一次，约 5 秒跑出屏幕，抓到有一大笔奖金
+use ggez::{
+    graphics::{self, Color, Rect},
+    nalgebra as na, Context, GameResult,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::config::GameConfig;
+use crate::parallel;
+use crate::TICKS_PER_SECOND;
+
+const MOLE_WIDTH: f32 = 28.0;
+const MOLE_HEIGHT: f32 = 16.0;
+const WALK_TICKS: u64 = 90; // 每走这么多帧停下来张望一次
+const PAUSE_TICKS: u64 = 30;
+const GOLDEN_SECONDS: f32 = 5.0; // 金地鼠横穿屏幕所用的时间
+const GOLDEN_SPARKLE_TICKS: u64 = 6; // 金地鼠每隔几帧洒一次金光
+
+#[derive(Debug, Clone)]
+pub struct Mole {
+    pub position: na::Point2<f32>,
+    pub velocity: f32, // 每帧水平移动的像素，负数向左
+    pub carries_diamond: bool,
+    pub golden: bool, // 金地鼠：一路狂奔不停顿
+    age: u64,
+}
+
+impl Mole {
+    // 走走停停：每段行走之后停顿片刻
+    fn walking(&self) -> bool {
+        self.golden || self.age % (WALK_TICKS + PAUSE_TICKS) < WALK_TICKS
+    }
+
+    fn rect(&self) -> Rect {
+        Rect::new(
+            self.position.x - MOLE_WIDTH / 2.0,
+            self.position.y - MOLE_HEIGHT / 2.0,
+            MOLE_WIDTH,
+            MOLE_HEIGHT,
+        )
+    }
+}
+
+// 本关的全部地鼠
+#[derive(Debug, Clone, Default)]
+pub struct MoleWarren {
+    pub moles: Vec<Mole>,
+    schedule: Vec<(u64, Mole)>, // 尚未出场的地鼠及其出场帧，按帧号倒序
+}
+
+impl MoleWarren {
+    // 按种子排好本关地鼠的出场。ordinary 为 false 时（没有地鼠的世界）只可能出现金地鼠
+    pub fn roll(seed: u64, config: &GameConfig, level: u32, ordinary: bool) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed.wrapping_add(0x6d6f6c65));
+        let profile = config.profile();
+        let duration = profile.time_limit(config.game_duration).as_secs() * TICKS_PER_SECOND as u64;
+        let count = if ordinary { profile.hazards(config.mole_count) } else { 0 };
+        let mut schedule: Vec<(u64, Mole)> = (0..count)
+            .map(|_| {
+                let from_left = rng.gen_bool(0.5);
+                let x = if from_left { -MOLE_WIDTH } else { config.screen_width + MOLE_WIDTH };
+                let y = rng.gen_range(config.mine_depth * 0.4..config.mine_depth - MOLE_HEIGHT);
+                let speed = config.mole_speed * rng.gen_range(0.8..1.2);
+                let mole = Mole {
+                    position: na::Point2::new(x, y),
+                    velocity: if from_left { speed } else { -speed },
+                    carries_diamond: rng.gen::<f32>() < config.mole_diamond_chance,
+                    golden: false,
+                    age: 0,
+                };
+                (rng.gen_range(0..duration.max(1) * 2 / 3), mole)
+            })
+            .collect();
+        // 金地鼠另用一个随机数流，不影响普通地鼠的排布；放在前半局出场，保证跑完之前关卡不会结束
+        if config.golden_mole_interval > 0 && level % config.golden_mole_interval == 0 {
+            let mut rng = StdRng::seed_from_u64(seed.wrapping_add(0x676f6c64));
+            let from_left = rng.gen_bool(0.5);
+            let x = if from_left { -MOLE_WIDTH } else { config.screen_width + MOLE_WIDTH };
+            let speed = (config.screen_width + MOLE_WIDTH * 2.0) / (GOLDEN_SECONDS * TICKS_PER_SECOND as f32);
+            let mole = Mole {
+                position: na::Point2::new(x, config.mine_depth - MOLE_HEIGHT),
+                velocity: if from_left { speed } else { -speed },
+                carries_diamond: false,
+                golden: true,
+                age: 0,
+            };
+            schedule.push((rng.gen_range(duration / 10..duration.max(10) / 2), mole));
+        }
+        schedule.sort_by(|a, b| b.0.cmp(&a.0));
+        MoleWarren {
+            moles: Vec::new(),
+            schedule,
+        }
+    }
+
+    // 每帧：到点的地鼠出场，其余的继续走，走出屏幕的消失。金地鼠出场时返回 true
+    pub fn update(&mut self, tick: u64, screen_width: f32) -> bool {
+        let mut golden = false;
+        while self.schedule.last().map_or(false, |(at, _)| *at <= tick) {
+            if let Some((_, mole)) = self.schedule.pop() {
+                golden |= mole.golden;
+                self.moles.push(mole);
+            }
+        }
+        parallel::for_each_mut(&mut self.moles, |_, mole| {
+            if mole.walking() {
+                mole.position.x += mole.velocity;
+            }
+            mole.age += 1;
+        });
+        self.moles.retain(|mole| {
+            let left = mole.velocity < 0.0 && mole.position.x < -MOLE_WIDTH;
+            let right = mole.velocity > 0.0 && mole.position.x > screen_width + MOLE_WIDTH;
+            !(left || right)
+        });
+        golden
+    }
+
+    // 正在飞奔的金地鼠该洒金光的位置
+    pub fn sparkles(&self) -> impl Iterator<Item = na::Point2<f32>> + '_ {
+        self.moles
+            .iter()
+            .filter(|mole| mole.golden && mole.age % GOLDEN_SPARKLE_TICKS == 0)
+            .map(|mole| mole.position)
+    }
+
+    // 钩子碰到地鼠时把它抓走
+    pub fn catch(&mut self, point: na::Point2<f32>) -> Option<Mole> {
+        let index = self.moles.iter().position(|mole| mole.rect().contains(point))?;
+        Some(self.moles.remove(index))
+    }
+
+    pub fn draw(&self, ctx: &mut Context) -> GameResult {
+        for mole in &self.moles {
+            let body = mole.rect();
+            let fur = if mole.golden { Color::new(1.0, 0.8, 0.1, 1.0) } else { Color::new(0.45, 0.35, 0.3, 1.0) };
+            graphics::rectangle(ctx, graphics::DrawParam::default().dest(body.point()), &body, fur)?;
+
+            // 行走时两只脚交替
+            let step = if mole.walking() && (mole.age / 8) % 2 == 0 { 4.0 } else { 0.0 };
+            for foot_x in [body.x + 4.0 + step, body.x + body.w - 8.0 - step] {
+                let foot = Rect::new(foot_x, body.y + body.h, 4.0, 3.0);
+                graphics::rectangle(ctx, graphics::DrawParam::default().dest(foot.point()), &foot, Color::new(0.3, 0.2, 0.2, 1.0))?;
+            }
+
+            // 叼着的钻石画在朝向的一侧
+            if mole.carries_diamond {
+                let front = if mole.velocity > 0.0 { body.x + body.w } else { body.x - 8.0 };
+                let gem = Rect::new(front, mole.position.y - 4.0, 8.0, 8.0);
+                graphics::rectangle(ctx, graphics::DrawParam::default().dest(gem.point()), &gem, Color::new(0.0, 1.0, 1.0, 1.0))?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+// 神秘袋：拉回到矿工手里才揭晓内容——可能是一笔钱、一根炸药、一瓶大力水，也可能几乎什么都没有。
+// 开袋用的随机数由种子、帧号和物品序号决定，回放时开出同样的东西
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::buffs::{BuffKind, Buffs};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BagReward {
+    Money(i32),
+    Dynamite,
+    Strength, // 一段时间内拖物品更快
+    Dud(i32), // 几乎什么都没有
+}
+
+impl BagReward {
+    // 计入得分的钱
+    pub fn money(self) -> i32 {
+        match self {
+            BagReward::Money(amount) | BagReward::Dud(amount) => amount,
+            BagReward::Dynamite | BagReward::Strength => 0,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BagReward::Money(_) => "Cash!",
+            BagReward::Dynamite => "Dynamite!",
+            BagReward::Strength => "Strength drink!",
+            BagReward::Dud(_) => "Just some pebbles...",
+        }
+    }
+}
+
+// 开袋用的随机数
+pub fn bag_rng(seed: u64, tick: u64, item_index: usize) -> StdRng {
+    StdRng::seed_from_u64(seed ^ tick.rotate_left(32) ^ item_index as u64)
+}
+
+// 奖励表：各类奖励的权重和钱的范围
+struct Odds {
+    money: u32,
+    dynamite: u32,
+    strength: u32,
+    dud: u32,
+    money_range: (i32, i32),
+}
+
+const NORMAL_ODDS: Odds = Odds {
+    money: 45,
+    dynamite: 20,
+    strength: 15,
+    dud: 20,
+    money_range: (50, 400),
+};
+
+// 带着幸运草时空袋少见，钱也更多
+const LUCKY_ODDS: Odds = Odds {
+    money: 55,
+    dynamite: 20,
+    strength: 20,
+    dud: 5,
+    money_range: (150, 600),
+};
+
+// 按当前增益选奖励表开袋
+pub fn roll(rng: &mut impl Rng, buffs: &Buffs) -> BagReward {
+    let odds = if buffs.has(BuffKind::Luck) { &LUCKY_ODDS } else { &NORMAL_ODDS };
+    let mut pick = rng.gen_range(0..odds.money + odds.dynamite + odds.strength + odds.dud);
+    if pick < odds.money {
+        return BagReward::Money(rng.gen_range(odds.money_range.0..=odds.money_range.1));
+    }
+    pick -= odds.money;
+    if pick < odds.dynamite {
+        return BagReward::Dynamite;
+    }
+    pick -= odds.dynamite;
+    if pick < odds.strength {
+        return BagReward::Strength;
+    }
+    BagReward::Dud(rng.gen_range(1..=10))
+}
@@ -0,0 +1,211 @@
+// 关卡附加目标："抓 3 颗钻石"、"剩 20 秒时达标"、"一块石头都不碰"等，
+// 剧情关卡写在关卡文件里，自由模式和还债战役每关随机抽几个，根据玩法事件实时判定，完成后奖励额外的钱
+use ggez::{
+    graphics::{self, Color, Text, TextFragment},
+    nalgebra as na, Context, GameResult,
+};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+use crate::events::{EventSubscriber, GameEvent};
+use crate::{BagSize, ItemType, TICKS_PER_SECOND};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObjectiveKind {
+    Collect { item_type: ItemType, count: u32 }, // 拉回若干件某类物品
+    Avoid { item_type: ItemType },               // 整关不抓某类物品
+    FinishEarly { seconds: u64 },                // 剩余时间不少于若干秒时达到目标分
+    ReachScore { amount: i32 },                  // 得分达到若干
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ObjectiveStatus {
+    Pending,
+    Done,
+    Failed,
+}
+
+#[derive(Debug, Clone)]
+pub struct Objective {
+    pub kind: ObjectiveKind,
+    pub reward: i64,
+    pub progress: u32,
+    pub status: ObjectiveStatus,
+    pub label: Option<String>, // 有标注时 HUD 上显示标注而不是奖励金额
+}
+
+impl Objective {
+    pub fn new(kind: ObjectiveKind, reward: i64) -> Self {
+        Objective {
+            kind,
+            reward,
+            progress: 0,
+            status: ObjectiveStatus::Pending,
+            label: None,
+        }
+    }
+
+    // 解析 "collect Diamond 3 200"、"avoid Rock 150"、"early 20 300"、"score 1000 100"，
+    // 最后一个数为奖励
+    pub fn parse(text: &str) -> Result<Self, &'static str> {
+        let parts: Vec<&str> = text.split_whitespace().collect();
+        let number = |i: usize| parts.get(i).and_then(|v| v.parse::<i64>().ok()).ok_or("目标参数格式错误");
+        let item = |i: usize| parts.get(i).and_then(|v| ItemType::from_name(v)).ok_or("未知的物品类型");
+        let (kind, reward) = match parts.first().copied() {
+            Some("collect") => (
+                ObjectiveKind::Collect {
+                    item_type: item(1)?,
+                    count: number(2)? as u32,
+                },
+                number(3)?,
+            ),
+            Some("avoid") => (ObjectiveKind::Avoid { item_type: item(1)? }, number(2)?),
+            Some("early") => (ObjectiveKind::FinishEarly { seconds: number(1)? as u64 }, number(2)?),
+            Some("score") => (ObjectiveKind::ReachScore { amount: number(1)? as i32 }, number(2)?),
+            _ => return Err("未知的目标类型"),
+        };
+        Ok(Objective::new(kind, reward))
+    }
+
+    pub fn description(&self) -> String {
+        match &self.kind {
+            ObjectiveKind::Collect { item_type, count } => {
+                format!("Collect {} {}/{}", item_type.name(), self.progress.min(*count), count)
+            }
+            ObjectiveKind::Avoid { item_type } => format!("Never grab a {}", item_type.name()),
+            ObjectiveKind::FinishEarly { seconds } => format!("Hit the target with {}s left", seconds),
+            ObjectiveKind::ReachScore { amount } => format!("Bank ${}", amount),
+        }
+    }
+}
+
+// 随机抽 1 到 3 个互不相同的附加目标，关卡越后奖励越高
+pub fn roll(rng: &mut StdRng, level: u32) -> Vec<Objective> {
+    let scale = 1 + level as i64 / 3;
+    let mut pool = vec![
+        Objective::new(
+            ObjectiveKind::Collect {
+                item_type: ItemType::Diamond,
+                count: rng.gen_range(1..=2),
+            },
+            200 * scale,
+        ),
+        Objective::new(
+            ObjectiveKind::Collect {
+                item_type: ItemType::Gold(BagSize::Medium),
+                count: rng.gen_range(3..=5),
+            },
+            150 * scale,
+        ),
+        Objective::new(ObjectiveKind::Avoid { item_type: ItemType::Rock }, 100 * scale),
+        Objective::new(ObjectiveKind::Avoid { item_type: ItemType::Skull }, 100 * scale),
+        Objective::new(ObjectiveKind::FinishEarly { seconds: 20 }, 250 * scale),
+    ];
+    pool.shuffle(rng);
+    pool.truncate(rng.gen_range(1..=3));
+    pool
+}
+
+// 本关的全部附加目标
+#[derive(Debug, Clone, Default)]
+pub struct ObjectiveTracker {
+    pub objectives: Vec<Objective>,
+    target: Option<i32>, // 关卡目标分，FinishEarly 用
+}
+
+impl ObjectiveTracker {
+    pub fn new(objectives: Vec<Objective>, target: Option<i32>) -> Self {
+        ObjectiveTracker { objectives, target }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objectives.is_empty()
+    }
+
+    // 结算界面的一行总结：完成了几个、拿到多少奖励
+    pub fn summary(&self) -> String {
+        let done: Vec<&Objective> = self.objectives.iter().filter(|o| o.status == ObjectiveStatus::Done).collect();
+        let reward: i64 = done.iter().map(|o| o.reward).sum();
+        format!("Objectives {}/{} complete   Bonus: ${}", done.len(), self.objectives.len(), reward)
+    }
+
+    // 每帧根据分数和剩余时间更新
+    pub fn update(&mut self, score: i32, ticks_left: u64) {
+        let target = self.target;
+        for objective in self.objectives.iter_mut().filter(|o| o.status == ObjectiveStatus::Pending) {
+            match objective.kind {
+                ObjectiveKind::FinishEarly { seconds } => {
+                    let deadline = seconds * TICKS_PER_SECOND as u64;
+                    if ticks_left < deadline {
+                        objective.status = ObjectiveStatus::Failed;
+                    } else if target.map_or(false, |target| score >= target) {
+                        objective.status = ObjectiveStatus::Done;
+                    }
+                }
+                ObjectiveKind::ReachScore { amount } if score >= amount => objective.status = ObjectiveStatus::Done,
+                _ => (),
+            }
+        }
+    }
+
+    // 关卡结束：仍未失败的"不碰"类目标算完成，返回奖励总额
+    pub fn finish(&mut self) -> i64 {
+        for objective in self.objectives.iter_mut() {
+            if objective.status == ObjectiveStatus::Pending {
+                objective.status = match objective.kind {
+                    ObjectiveKind::Avoid { .. } => ObjectiveStatus::Done,
+                    _ => ObjectiveStatus::Failed,
+                };
+            }
+        }
+        self.objectives
+            .iter()
+            .filter(|o| o.status == ObjectiveStatus::Done)
+            .map(|o| o.reward)
+            .sum()
+    }
+
+    // HUD 上的目标列表
+    pub fn draw(&self, ctx: &mut Context, x: f32, y: f32) -> GameResult {
+        for (i, objective) in self.objectives.iter().enumerate() {
+            let (mark, color) = match objective.status {
+                ObjectiveStatus::Pending => ("[ ]", Color::WHITE),
+                ObjectiveStatus::Done => ("[x]", Color::new(0.3, 0.9, 0.3, 1.0)),
+                ObjectiveStatus::Failed => ("[-]", Color::new(0.6, 0.6, 0.6, 1.0)),
+            };
+            let suffix = match &objective.label {
+                Some(label) => format!("({})", label),
+                None => format!("+${}", objective.reward),
+            };
+            let text = Text::new(TextFragment::new(format!("{} {}  {}", mark, objective.description(), suffix))
+                .color(color)
+                .font_size(14));
+            graphics::draw(ctx, &text, graphics::DrawParam::default().dest(na::Point2::new(x, y + i as f32 * 18.0)))?;
+        }
+        Ok(())
+    }
+}
+
+impl EventSubscriber for ObjectiveTracker {
+    fn on_event(&mut self, event: &GameEvent) {
+        for objective in self.objectives.iter_mut().filter(|o| o.status == ObjectiveStatus::Pending) {
+            match (&objective.kind, event) {
+                (ObjectiveKind::Collect { item_type, count }, GameEvent::ItemBanked { player: 0, item_type: banked, .. })
+                    if item_type.same_kind(*banked) =>
+                {
+                    objective.progress += 1;
+                    if objective.progress >= *count {
+                        objective.status = ObjectiveStatus::Done;
+                    }
+                }
+                (
+                    ObjectiveKind::Avoid { item_type },
+                    GameEvent::ItemGrabbed { player: 0, item_type: grabbed, .. }
+                    | GameEvent::ItemDrilled { item_type: grabbed, .. },
+                ) if item_type.same_kind(*grabbed) => {
+                    objective.status = ObjectiveStatus::Failed;
+                }
+                _ => (),
+            }
+        }
+    }
+}
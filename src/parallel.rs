@@ -0,0 +1,19 @@
+// 逐个实体的并行更新：无尽模式里物品、地鼠、蝙蝠很多时，只改自身的运动和动画步骤交给 rayon；
+// 碰撞、抓取和计分仍在主线程按顺序做，保证回放逐帧一致
+use rayon::prelude::*;
+
+// 实体超过该数量时才并行更新，数量少时线程调度反而更慢
+pub const PARALLEL_THRESHOLD: usize = 256;
+
+// 对每个实体（带下标）执行 f；f 只能读共享数据、改这一个实体，所以并行与顺序执行的结果相同
+pub fn for_each_mut<T, F>(entities: &mut [T], f: F)
+where
+    T: Send,
+    F: Fn(usize, &mut T) + Sync + Send,
+{
+    if entities.len() >= PARALLEL_THRESHOLD {
+        entities.par_iter_mut().enumerate().for_each(|(i, entity)| f(i, entity));
+    } else {
+        entities.iter_mut().enumerate().for_each(|(i, entity)| f(i, entity));
+    }
+}
@@ -0,0 +1,140 @@
+// 玩法插件：成就、遥测、速通计时、模组等通过生命周期钩子接入，不需要改动 GameState
+use ggez::{
+    graphics::{self, Color, Text, TextFragment},
+    nalgebra as na, Context, GameResult,
+};
+
+use crate::events::GameEvent;
+use crate::TICKS_PER_SECOND;
+
+// 插件可读取的游戏状态快照
+#[derive(Debug, Clone, Copy)]
+pub struct PluginView {
+    pub seed: u64,
+    pub level: u32,
+    pub tick: u64,
+    pub score: i32,
+    pub target: Option<i32>, // 本关目标分
+    pub replaying: bool,
+    pub screen_width: f32,
+    pub screen_height: f32,
+}
+
+// 插件接口，所有钩子都有默认空实现
+pub trait GamePlugin {
+    fn name(&self) -> &'static str;
+
+    // 新一局（关卡）开始
+    fn on_level_start(&mut self, _view: &PluginView) {}
+
+    // 每个模拟帧结束后调用
+    fn on_update(&mut self, _view: &PluginView, _dt: f32) {}
+
+    // 收到游戏事件
+    fn on_event(&mut self, _view: &PluginView, _event: &GameEvent) {}
+
+    // 在 HUD 之上绘制插件自己的内容
+    fn on_draw_overlay(&mut self, _ctx: &mut Context, _view: &PluginView) -> GameResult {
+        Ok(())
+    }
+
+    // 取走插件要提示给玩家的消息（如后台任务失败）
+    fn take_message(&mut self) -> Option<String> {
+        None
+    }
+}
+
+// 已注册的插件，按注册顺序调用
+#[derive(Default)]
+pub struct PluginRegistry {
+    plugins: Vec<Box<dyn GamePlugin>>,
+}
+
+impl PluginRegistry {
+    pub fn new() -> Self {
+        PluginRegistry::default()
+    }
+
+    pub fn register(&mut self, plugin: Box<dyn GamePlugin>) {
+        self.plugins.push(plugin);
+    }
+
+    // 按名字移除插件，返回是否移除了
+    pub fn unregister(&mut self, name: &str) -> bool {
+        let before = self.plugins.len();
+        self.plugins.retain(|plugin| plugin.name() != name);
+        self.plugins.len() != before
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        self.plugins.iter().map(|plugin| plugin.name()).collect()
+    }
+
+    pub fn level_start(&mut self, view: &PluginView) {
+        for plugin in self.plugins.iter_mut() {
+            plugin.on_level_start(view);
+        }
+    }
+
+    pub fn update(&mut self, view: &PluginView, dt: f32) {
+        for plugin in self.plugins.iter_mut() {
+            plugin.on_update(view, dt);
+        }
+    }
+
+    pub fn event(&mut self, view: &PluginView, event: &GameEvent) {
+        for plugin in self.plugins.iter_mut() {
+            plugin.on_event(view, event);
+        }
+    }
+
+    pub fn draw_overlay(&mut self, ctx: &mut Context, view: &PluginView) -> GameResult {
+        for plugin in self.plugins.iter_mut() {
+            plugin.on_draw_overlay(ctx, view)?;
+        }
+        Ok(())
+    }
+
+    pub fn take_message(&mut self) -> Option<String> {
+        self.plugins.iter_mut().find_map(|plugin| plugin.take_message())
+    }
+}
+
+// 速通计时插件：显示本局用时和最近一次得分的时间点
+#[derive(Debug, Default)]
+pub struct SpeedrunTimer {
+    last_split: Option<u64>,
+}
+
+impl GamePlugin for SpeedrunTimer {
+    fn name(&self) -> &'static str {
+        "speedrun-timer"
+    }
+
+    fn on_level_start(&mut self, _view: &PluginView) {
+        self.last_split = None;
+    }
+
+    fn on_event(&mut self, view: &PluginView, event: &GameEvent) {
+        if let GameEvent::ItemBanked { player: 0, .. } = event {
+            self.last_split = Some(view.tick);
+        }
+    }
+
+    fn on_draw_overlay(&mut self, ctx: &mut Context, view: &PluginView) -> GameResult {
+        let mut line = format!("RTA {}", format_ticks(view.tick));
+        if let Some(split) = self.last_split {
+            line.push_str(&format!("  last split {}", format_ticks(split)));
+        }
+        let text = Text::new(TextFragment::new(line)
+            .color(Color::new(1.0, 1.0, 1.0, 0.7))
+            .font_size(14));
+        graphics::draw(ctx, &text, graphics::DrawParam::default().dest(na::Point2::new(view.screen_width - 260.0, 40.0)))
+    }
+}
+
+// 帧数格式化为 分:秒.百分秒
+fn format_ticks(ticks: u64) -> String {
+    let hundredths = ticks * 100 / TICKS_PER_SECOND as u64;
+    format!("{}:{:02}.{:02}", hundredths / 6000, hundredths / 100 % 60, hundredths % 100)
+}
@@ -0,0 +1,64 @@
+// 传送门：部分关卡里有一对相连的圆环，分在矿洞左右两半。伸出的钩子钻进其中一个，
+// 就从另一个钻出来，沿原来的方向接着伸，可以绕开挡路的石头打花式钩。位置按种子生成，回放时完全一致
+use ggez::{
+    graphics::{self, Color},
+    nalgebra as na, Context, GameResult,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::config::GameConfig;
+
+pub const PORTAL_RADIUS: f32 = 22.0;
+const RING_SEGMENTS: usize = 16;
+const COLORS: [Color; 2] = [Color::new(1.0, 0.55, 0.1, 1.0), Color::new(0.2, 0.6, 1.0, 1.0)];
+
+#[derive(Debug, Clone, Default)]
+pub struct Portals {
+    ends: Option<[na::Point2<f32>; 2]>,
+    spin: f32, // 圆环转动的角度，只用于绘制
+}
+
+impl Portals {
+    pub fn roll(seed: u64, config: &GameConfig) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed.wrapping_add(0x706f7274));
+        if rng.gen::<f32>() >= config.portal_chance {
+            return Portals::default();
+        }
+        let half = config.screen_width / 2.0;
+        let mut end = |min_x: f32| {
+            let x = rng.gen_range(min_x + PORTAL_RADIUS * 2.0..min_x + half - PORTAL_RADIUS * 2.0);
+            let y = rng.gen_range(config.mine_depth * 0.3..config.mine_depth * 0.8);
+            na::Point2::new(x, y)
+        };
+        Portals {
+            ends: Some([end(0.0), end(half)]),
+            spin: 0.0,
+        }
+    }
+
+    // 两个圆环的圆心，本关没有传送门时为 None
+    pub fn ends(&self) -> Option<[na::Point2<f32>; 2]> {
+        self.ends
+    }
+
+    pub fn update(&mut self) {
+        self.spin += 0.05;
+    }
+
+    pub fn draw(&self, ctx: &mut Context) -> GameResult {
+        let ends = match self.ends {
+            Some(ends) => ends,
+            None => return Ok(()),
+        };
+        for (center, color) in ends.iter().zip(COLORS) {
+            let ring: Vec<_> = (0..=RING_SEGMENTS)
+                .map(|k| {
+                    let angle = self.spin + k as f32 * std::f32::consts::TAU / RING_SEGMENTS as f32;
+                    na::Point2::new(center.x + angle.cos() * PORTAL_RADIUS, center.y + angle.sin() * PORTAL_RADIUS)
+                })
+                .collect();
+            graphics::line(ctx, graphics::DrawParam::default(), &ring, 4.0, color)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,120 @@
+// 漂浮道具：关卡进行中每隔一段时间，矿洞上层会有一个道具从一侧漂到另一侧，钩中拉回后获得对应的增益。
+// 道具不在物品列表里，被钩中后才变成物品；出现的时间、高度和种类按种子决定，回放时完全一致
+use ggez::{
+    graphics::{self, Color, Rect, Text, TextFragment},
+    nalgebra as na, Context, GameResult,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::config::GameConfig;
+use crate::{ItemType, TICKS_PER_SECOND};
+
+const SIZE: f32 = 22.0;
+const FLOAT_TOP: f32 = 120.0; // 道具漂过的高度范围
+const FLOAT_BOTTOM: f32 = 260.0;
+const BOB_HEIGHT: f32 = 6.0; // 上下浮动的幅度
+
+// 可能出现的道具
+const KINDS: [ItemType; 4] = [
+    ItemType::MagnetPickup,
+    ItemType::StrengthPickup,
+    ItemType::DoublePickup,
+    ItemType::RockBreakerPickup,
+];
+
+#[derive(Debug, Clone)]
+pub struct PowerUp {
+    pub item_type: ItemType,
+    pub position: na::Point2<f32>,
+    velocity: f32, // 每帧水平移动的像素，负数向左
+    base_y: f32,
+    age: u64,
+}
+
+impl PowerUp {
+    fn rect(&self) -> Rect {
+        Rect::new(self.position.x - SIZE / 2.0, self.position.y - SIZE / 2.0, SIZE, SIZE)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PowerUps {
+    pub floating: Vec<PowerUp>,
+    rng: StdRng,
+    interval: u64, // 每隔多少帧放出一个，0 为不出现
+    speed: f32,
+    width: f32,
+}
+
+impl Default for PowerUps {
+    fn default() -> Self {
+        PowerUps {
+            floating: Vec::new(),
+            rng: StdRng::seed_from_u64(0),
+            interval: 0,
+            speed: 0.0,
+            width: 0.0,
+        }
+    }
+}
+
+impl PowerUps {
+    pub fn new(seed: u64, config: &GameConfig) -> Self {
+        PowerUps {
+            floating: Vec::new(),
+            rng: StdRng::seed_from_u64(seed.wrapping_add(0x706f7765)),
+            interval: config.powerup_seconds * TICKS_PER_SECOND as u64,
+            speed: config.powerup_speed,
+            width: config.screen_width,
+        }
+    }
+
+    // 每帧：到点放出新道具，漂浮中的道具移动，漂出屏幕的消失
+    pub fn update(&mut self, tick: u64) {
+        if self.interval > 0 && tick > 0 && tick % self.interval == 0 {
+            let item_type = KINDS[self.rng.gen_range(0..KINDS.len())];
+            let from_left = self.rng.gen_bool(0.5);
+            let x = if from_left { -SIZE } else { self.width + SIZE };
+            let y = self.rng.gen_range(FLOAT_TOP..FLOAT_BOTTOM);
+            self.floating.push(PowerUp {
+                item_type,
+                position: na::Point2::new(x, y),
+                velocity: if from_left { self.speed } else { -self.speed },
+                base_y: y,
+                age: 0,
+            });
+        }
+
+        for powerup in self.floating.iter_mut() {
+            powerup.age += 1;
+            powerup.position.x += powerup.velocity;
+            powerup.position.y = powerup.base_y + (powerup.age as f32 * 0.08).sin() * BOB_HEIGHT;
+        }
+        let width = self.width;
+        self.floating
+            .retain(|powerup| powerup.position.x > -SIZE * 2.0 && powerup.position.x < width + SIZE * 2.0);
+    }
+
+    // 钩子碰到道具时把它摘下来交给钩子
+    pub fn catch(&mut self, point: na::Point2<f32>) -> Option<PowerUp> {
+        let index = self.floating.iter().position(|powerup| powerup.rect().contains(point))?;
+        Some(self.floating.remove(index))
+    }
+
+    // 道具画成带字母的方块，和 HUD 上的增益图标对应
+    pub fn draw(&self, ctx: &mut Context) -> GameResult {
+        for powerup in &self.floating {
+            let body = powerup.rect();
+            graphics::rectangle(ctx, graphics::DrawParam::default().dest(body.point()), &body, powerup.item_type.color())?;
+            let letter = match powerup.item_type {
+                ItemType::MagnetPickup => "M",
+                ItemType::DoublePickup => "2",
+                ItemType::RockBreakerPickup => "R",
+                _ => "S",
+            };
+            let text = Text::new(TextFragment::new(letter).color(Color::WHITE).font_size(16));
+            graphics::draw(ctx, &text, graphics::DrawParam::default().dest(na::Point2::new(body.x + 6.0, body.y + 3.0)))?;
+        }
+        Ok(())
+    }
+}
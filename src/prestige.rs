@@ -0,0 +1,77 @@
+// 声望（二周目）：通关剧情战役后可以重置进度提升声望等级。
+// 每级带来少量永久的价值加成和新的绳子外观，但剧情关卡里的物品更沉、时间更短。
+// 还债战役的新周目也算在这里：每个周目同样提高价值，由战役或回放文件头决定，不存盘
+use ggez::graphics::Color;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use crate::storage;
+
+const PRESTIGE_FILE: &str = "prestige.txt";
+const VALUE_BONUS_PER_LEVEL: f32 = 0.05;
+const VALUE_BONUS_PER_PLUS: f32 = 0.25; // 还债战役每个新周目的价值加成
+const HEAVINESS_PER_LEVEL: f32 = 0.15;
+const TIME_CUT_PER_LEVEL: f32 = 0.1;
+const MIN_TIME_FACTOR: f32 = 0.5;
+
+#[derive(Debug, Clone, Default)]
+pub struct Prestige {
+    pub level: u32,
+    pub new_game_plus: u32, // 还债战役的新周目次数
+}
+
+impl Prestige {
+    fn path() -> PathBuf {
+        storage::user_data_dir().join(PRESTIGE_FILE)
+    }
+
+    // 读取声望等级，文件不存在或损坏时为 0
+    pub fn load() -> Self {
+        let level = fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|text| text.trim().strip_prefix("level = ").and_then(|v| v.parse().ok()))
+            .unwrap_or(0);
+        Prestige { level, new_game_plus: 0 }
+    }
+
+    // 还债战役第 plus 个新周目的价值加成
+    pub fn new_game_plus(plus: u32) -> Self {
+        Prestige {
+            level: 0,
+            new_game_plus: plus,
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        fs::create_dir_all(storage::user_data_dir())?;
+        let mut out = fs::File::create(Self::path())?;
+        writeln!(out, "level = {}", self.level)
+    }
+
+    // 拉回物品时的价值倍率
+    pub fn value_bonus(&self) -> f32 {
+        1.0 + VALUE_BONUS_PER_LEVEL * self.level as f32 + VALUE_BONUS_PER_PLUS * self.new_game_plus as f32
+    }
+
+    // 拖拽物品时收钩变慢的倍率
+    pub fn heaviness(&self) -> f32 {
+        1.0 + HEAVINESS_PER_LEVEL * self.level as f32
+    }
+
+    // 缩短后的关卡时长
+    pub fn duration_ticks(&self, base: u64) -> u64 {
+        let factor = (1.0 - TIME_CUT_PER_LEVEL * self.level as f32).max(MIN_TIME_FACTOR);
+        (base as f32 * factor) as u64
+    }
+
+    // 每级解锁的绳子颜色
+    pub fn rope_color(&self) -> Color {
+        match self.level {
+            0 => Color::new(0.5, 0.3, 0.1, 1.0),  // 棕色
+            1 => Color::new(0.75, 0.75, 0.8, 1.0), // 银色
+            2 => Color::new(1.0, 0.84, 0.0, 1.0),  // 金色
+            _ => Color::new(0.4, 0.9, 1.0, 1.0),   // 钻石蓝
+        }
+    }
+}
@@ -0,0 +1,183 @@
+// 帧性能分析：用 profile_scope! 标记代码段，按帧收集耗时并绘制火焰图
+use ggez::{
+    graphics::{self, Color, Rect, Text, TextFragment},
+    nalgebra as na, Context, GameResult,
+};
+use std::cell::RefCell;
+use std::time::Instant;
+
+// 火焰图区域
+const GRAPH_X: f32 = 10.0;
+const GRAPH_Y: f32 = 60.0;
+const ROW_HEIGHT: f32 = 16.0;
+const FRAME_BUDGET_US: u64 = 16_667; // 60 FPS 一帧的时间预算
+
+// 一个计时段
+#[derive(Debug, Clone)]
+pub struct ScopeRecord {
+    pub name: &'static str,
+    pub depth: usize,
+    pub start_us: u64,
+    pub duration_us: u64,
+}
+
+// 一帧的全部计时段
+#[derive(Debug, Clone, Default)]
+pub struct FrameRecord {
+    pub scopes: Vec<ScopeRecord>,
+    pub duration_us: u64,
+}
+
+struct ProfilerState {
+    enabled: bool,
+    frame_start: Instant,
+    depth: usize,
+    current: Vec<ScopeRecord>,
+    latest: FrameRecord,
+}
+
+thread_local! {
+    static PROFILER: RefCell<ProfilerState> = RefCell::new(ProfilerState {
+        enabled: false,
+        frame_start: Instant::now(),
+        depth: 0,
+        current: Vec::new(),
+        latest: FrameRecord::default(),
+    });
+}
+
+// 计时段守卫，离开作用域时记录耗时
+pub struct ScopeGuard {
+    index: Option<usize>,
+    start: Instant,
+}
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        if let Some(index) = self.index {
+            let elapsed = self.start.elapsed().as_micros() as u64;
+            PROFILER.with(|p| {
+                let mut p = p.borrow_mut();
+                p.depth = p.depth.saturating_sub(1);
+                if let Some(scope) = p.current.get_mut(index) {
+                    scope.duration_us = elapsed;
+                }
+            });
+        }
+    }
+}
+
+// 开始一个计时段（未开启分析时几乎没有开销）
+pub fn begin_scope(name: &'static str) -> ScopeGuard {
+    let start = Instant::now();
+    let index = PROFILER.with(|p| {
+        let mut p = p.borrow_mut();
+        if !p.enabled {
+            return None;
+        }
+        let record = ScopeRecord {
+            name,
+            depth: p.depth,
+            start_us: (start - p.frame_start).as_micros() as u64,
+            duration_us: 0,
+        };
+        p.depth += 1;
+        p.current.push(record);
+        Some(p.current.len() - 1)
+    });
+    ScopeGuard { index, start }
+}
+
+// 在当前作用域内计时
+#[macro_export]
+macro_rules! profile_scope {
+    ($name:expr) => {
+        let _profile_guard = $crate::profiler::begin_scope($name);
+    };
+}
+
+// 开始新的一帧，上一帧的数据用于显示
+pub fn new_frame() {
+    PROFILER.with(|p| {
+        let mut p = p.borrow_mut();
+        if p.enabled {
+            let scopes = std::mem::take(&mut p.current);
+            p.latest = FrameRecord {
+                scopes,
+                duration_us: p.frame_start.elapsed().as_micros() as u64,
+            };
+        }
+        p.frame_start = Instant::now();
+        p.depth = 0;
+    });
+}
+
+// 运行时开关
+pub fn toggle() -> bool {
+    PROFILER.with(|p| {
+        let mut p = p.borrow_mut();
+        p.enabled = !p.enabled;
+        p.current.clear();
+        p.latest = FrameRecord::default();
+        p.enabled
+    })
+}
+
+pub fn enabled() -> bool {
+    PROFILER.with(|p| p.borrow().enabled)
+}
+
+// 最近一帧的数据
+pub fn latest_frame() -> FrameRecord {
+    PROFILER.with(|p| p.borrow().latest.clone())
+}
+
+// 绘制火焰图
+pub fn draw_overlay(ctx: &mut Context, screen_width: f32) -> GameResult {
+    let frame = latest_frame();
+    let graph_width = screen_width - GRAPH_X * 2.0;
+    let span = frame.duration_us.max(FRAME_BUDGET_US) as f32;
+
+    let title = Text::new(TextFragment::new(format!(
+        "Frame: {:.2} ms",
+        frame.duration_us as f32 / 1000.0
+    ))
+    .color(Color::WHITE)
+    .font_size(14));
+    graphics::draw(ctx, &title, graphics::DrawParam::default().dest(na::Point2::new(GRAPH_X, GRAPH_Y - 18.0)))?;
+
+    for scope in &frame.scopes {
+        let x = GRAPH_X + graph_width * scope.start_us as f32 / span;
+        let w = (graph_width * scope.duration_us as f32 / span).max(1.0);
+        let y = GRAPH_Y + scope.depth as f32 * ROW_HEIGHT;
+        let bar = Rect::new(x, y, w, ROW_HEIGHT - 2.0);
+        graphics::rectangle(
+            ctx,
+            graphics::DrawParam::default().dest(bar.point()),
+            &bar,
+            scope_color(scope.name),
+        )?;
+
+        // 足够宽时写上名称和耗时
+        if w > 60.0 {
+            let label = Text::new(TextFragment::new(format!(
+                "{} {:.2}ms",
+                scope.name,
+                scope.duration_us as f32 / 1000.0
+            ))
+            .color(Color::BLACK)
+            .font_size(12));
+            graphics::draw(ctx, &label, graphics::DrawParam::default().dest(na::Point2::new(x + 2.0, y + 1.0)))?;
+        }
+    }
+    Ok(())
+}
+
+// 根据名称生成稳定的颜色，方便区分各段
+fn scope_color(name: &str) -> Color {
+    let hash = name.bytes().fold(7u32, |h, b| h.wrapping_mul(31).wrapping_add(b as u32));
+    let r = 0.5 + (hash & 0xff) as f32 / 512.0;
+    let g = 0.5 + ((hash >> 8) & 0xff) as f32 / 512.0;
+    let b = 0.5 + ((hash >> 16) & 0xff) as f32 / 512.0;
+    Color::new(r, g, b, 0.85)
+}
@@ -0,0 +1,71 @@
+// 抛掷的炸药：按鼠标位置计算抛物线，落地（或碰到物品）时爆炸
+use ggez::{
+    graphics::{self, Color, Rect},
+    nalgebra as na, Context, GameResult,
+};
+
+use crate::{Item, TICKS_PER_SECOND};
+
+const GRAVITY: f32 = 400.0;
+const FLIGHT_SPEED: f32 = 300.0; // 决定飞行时间的平均速度
+const DYNAMITE_SIZE: f32 = 10.0;
+
+#[derive(Debug, Clone)]
+pub struct DynamiteProjectile {
+    pub position: na::Point2<f32>,
+    velocity: na::Vector2<f32>,
+    ticks_left: u64,
+}
+
+impl DynamiteProjectile {
+    // 从 origin 抛向 target，选取初速度使其正好在飞行结束时落在目标点
+    pub fn lob(origin: na::Point2<f32>, target: na::Point2<f32>) -> Self {
+        let distance = na::distance(&origin, &target);
+        let flight_time = (distance / FLIGHT_SPEED).max(0.3);
+        let ticks = (flight_time * TICKS_PER_SECOND as f32).round().max(1.0);
+        let flight_time = ticks / TICKS_PER_SECOND as f32;
+        let velocity = na::Vector2::new(
+            (target.x - origin.x) / flight_time,
+            (target.y - origin.y - 0.5 * GRAVITY * flight_time * flight_time) / flight_time,
+        );
+        DynamiteProjectile {
+            position: origin,
+            velocity,
+            ticks_left: ticks as u64,
+        }
+    }
+
+    // 推进一帧，落地或撞到物品时返回爆炸点
+    pub fn update(&mut self, dt: f32, items: &[Item]) -> Option<na::Point2<f32>> {
+        self.velocity.y += GRAVITY * dt;
+        self.position += self.velocity * dt;
+        self.ticks_left = self.ticks_left.saturating_sub(1);
+
+        let hit_item = items.iter().any(|item| {
+            let half = item.size() / 2.0;
+            !item.collected
+                && (item.position.x - self.position.x).abs() < half
+                && (item.position.y - self.position.y).abs() < half
+        });
+        if hit_item || self.ticks_left == 0 {
+            Some(self.position)
+        } else {
+            None
+        }
+    }
+
+    pub fn draw(&self, ctx: &mut Context) -> GameResult {
+        let rect = Rect::new(
+            self.position.x - DYNAMITE_SIZE / 2.0,
+            self.position.y - DYNAMITE_SIZE / 2.0,
+            DYNAMITE_SIZE,
+            DYNAMITE_SIZE,
+        );
+        graphics::rectangle(
+            ctx,
+            graphics::DrawParam::default().dest(rect.point()),
+            &rect,
+            Color::new(0.85, 0.1, 0.1, 1.0), // 红色炸药
+        )
+    }
+}
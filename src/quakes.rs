@@ -0,0 +1,56 @@
+// 地震：关卡进行中偶尔发生地震，屏幕晃动，场上还没拉回的物品被震到附近的新位置。
+// 发生的时间和物品的位移都用按种子初始化的随机数，回放时完全一致
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::config::GameConfig;
+use crate::Item;
+use crate::TICKS_PER_SECOND;
+
+pub const SHAKE_TICKS: u64 = 45; // 屏幕晃动的帧数
+const DISPLACEMENT: f32 = 80.0; // 物品最多被震开的距离
+const SURFACE: f32 = 100.0; // 物品不会被震到这个深度以上
+
+#[derive(Debug, Clone)]
+pub struct Earthquakes {
+    rng: StdRng,
+    interval: u64, // 平均每隔多少帧一次，0 为不发生
+    next: u64,
+}
+
+impl Default for Earthquakes {
+    fn default() -> Self {
+        Earthquakes {
+            rng: StdRng::seed_from_u64(0),
+            interval: 0,
+            next: 0,
+        }
+    }
+}
+
+impl Earthquakes {
+    pub fn new(seed: u64, config: &GameConfig) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed.wrapping_add(0x71756b65));
+        let interval = config.profile().interval(config.quake_seconds) * TICKS_PER_SECOND as u64;
+        let next = if interval > 0 { rng.gen_range(interval / 2..=interval * 3 / 2) } else { 0 };
+        Earthquakes { rng, interval, next }
+    }
+
+    // 每帧检查是否到了下一次地震，到了就把物品震开并返回 true
+    pub fn update(&mut self, tick: u64, items: &mut [Item], config: &GameConfig) -> bool {
+        if self.interval == 0 || tick < self.next {
+            return false;
+        }
+        self.next = tick + self.rng.gen_range(self.interval / 2..=self.interval * 3 / 2);
+
+        for item in items.iter_mut().filter(|item| !item.collected) {
+            let half = item.size() / 2.0;
+            let dx = self.rng.gen_range(-DISPLACEMENT..DISPLACEMENT);
+            let dy = self.rng.gen_range(-DISPLACEMENT..DISPLACEMENT);
+            item.position.x = (item.position.x + dx).clamp(half, config.screen_width - half);
+            item.position.y = (item.position.y + dy).clamp(SURFACE, config.mine_depth - half);
+            item.falling = false;
+            item.velocity = 0.0;
+        }
+        true
+    }
+}
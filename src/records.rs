@@ -0,0 +1,57 @@
+// 各模式的最佳纪录，保存在用户数据目录下的 records.txt，每行一个 `键 = 值`
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use crate::storage;
+
+const RECORDS_FILE: &str = "records.txt";
+
+#[derive(Debug, Clone, Default)]
+pub struct Records {
+    best: BTreeMap<String, i64>,
+}
+
+impl Records {
+    fn path() -> PathBuf {
+        storage::user_data_dir().join(RECORDS_FILE)
+    }
+
+    // 读取纪录，文件不存在或某行损坏时跳过
+    pub fn load() -> Self {
+        let text = fs::read_to_string(Self::path()).unwrap_or_default();
+        let best = text
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .filter_map(|(key, value)| Some((key.trim().to_string(), value.trim().parse().ok()?)))
+            .collect();
+        Records { best }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        fs::create_dir_all(storage::user_data_dir())?;
+        let mut out = fs::File::create(Self::path())?;
+        for (key, value) in &self.best {
+            writeln!(out, "{} = {}", key, value)?;
+        }
+        Ok(())
+    }
+
+    pub fn best(&self, key: &str) -> Option<i64> {
+        self.best.get(key).copied()
+    }
+
+    // 提交一次成绩，刷新纪录时返回 true；higher_is_better 为 false 时越小越好（例如用时）
+    pub fn submit(&mut self, key: &str, value: i64, higher_is_better: bool) -> bool {
+        let improved = match self.best(key) {
+            Some(best) if higher_is_better => value > best,
+            Some(best) => value < best,
+            None => true,
+        };
+        if improved {
+            self.best.insert(key.to_string(), value);
+        }
+        improved
+    }
+}
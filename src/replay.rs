@@ -0,0 +1,399 @@
+// 回放文件（.gmr）的读写与播放
+//
+// 文件格式为纯文本：
+//   GMR <版本>
+//   seed <种子>
+//   level <关卡>
+//   player <玩家名>
+//   date <UNIX 秒>
+//   duration <总帧数>
+//   target <目标分>      可选，生成布局时保证的目标分
+//   mode <模式>          可选，缺省为 classic
+//   difficulty <难度>    可选，缺省为 normal
+//   nudge <调整量>       可选，自适应难度给本关的调整，缺省为 0
+//   bonus true           可选，本局是秘密奖励关
+//   plus <周目>          可选，还债战役的新周目次数，缺省为 0
+//   hook_tier <档位>     可选，买到的钩速升级档位，缺省为 0
+//   insurance <份数>     可选，开局时手里的保险
+//   dynamite <个数>      可选，商店买下、本关开局时发放的炸药
+//   upgrades <名称...>   可选，开局带着的升级和道具，见 UPGRADE_NAMES
+//   story <序号>         可选，剧情战役的关卡序号（从 0 开始），按 story.txt 换成固定布局
+//   prestige <等级>      可选，剧情关卡录制时的声望等级，缺省为 0
+//   inputs
+//   <帧号> L            向左移动
+//   <帧号> R            向右移动
+//   <帧号> C            松开方向键滑行
+//   <帧号> T <角度> [力度]  发射钩子（力度缺省为 1.0）
+//   <帧号> D            使用钻头
+//   <帧号> X <x> <y>    向目标点抛出炸药
+//   <帧号> B            炸掉正在拖回的物品
+use std::fs;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::difficulty::Difficulty;
+use crate::input::InputAction;
+use crate::modes::GameMode;
+
+// 生成规则或输入的含义一变，旧回放就会重放走样，所以每次都要加一；版本不符的文件拒绝加载
+//   2  物品的稀有度和价值随深度变化
+//   3  偶尔生成成片的金矿脉和银矿脉
+//   4  L/R 让矿车持续加速直到 C，不再是一步一格
+//   5  金块分成小、中、大三种，生成表随之改变
+//   6  文件头记录开局带着的升级和道具
+//   7  文件头记录剧情关卡和声望
+pub const REPLAY_VERSION: u32 = 7;
+pub const REPLAY_DIR: &str = "replays";
+pub const REPLAY_EXTENSION: &str = "gmr";
+const REPLAY_MAGIC: &str = "GMR";
+const UPGRADE_NAMES: [&str; 10] = [
+    "big_claw",
+    "dual_hook",
+    "fireproof_rope",
+    "ricochet_hook",
+    "rock_book",
+    "gem_polish",
+    "strength",
+    "luck",
+    "magnet",
+    "drill",
+];
+
+// 带帧号的输入
+#[derive(Debug, Clone, Copy)]
+pub struct ReplayInput {
+    pub tick: u64,
+    pub action: InputAction,
+}
+
+// 开局时带进本关的升级和道具。商店买的东西不在种子里，回放按它重建钩子、道具栏和增益
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Loadout {
+    pub hook_tier: u8,
+    pub insurance: u32,
+    pub dynamite: u32,
+    pub big_claw: bool,
+    pub dual_hook: bool,
+    pub fireproof_rope: bool,
+    pub ricochet_hook: bool,
+    pub rock_book: bool,
+    pub gem_polish: bool,
+    pub strength: bool, // 下一关生效的大力水
+    pub luck: bool,     // 下一关生效的幸运草
+    pub magnet: bool,   // 下一关生效的磁铁
+    pub drill: bool,    // 钻头已解锁
+}
+
+impl Loadout {
+    fn flag(&mut self, name: &str) -> Option<&mut bool> {
+        match name {
+            "big_claw" => Some(&mut self.big_claw),
+            "dual_hook" => Some(&mut self.dual_hook),
+            "fireproof_rope" => Some(&mut self.fireproof_rope),
+            "ricochet_hook" => Some(&mut self.ricochet_hook),
+            "rock_book" => Some(&mut self.rock_book),
+            "gem_polish" => Some(&mut self.gem_polish),
+            "strength" => Some(&mut self.strength),
+            "luck" => Some(&mut self.luck),
+            "magnet" => Some(&mut self.magnet),
+            "drill" => Some(&mut self.drill),
+            _ => None,
+        }
+    }
+
+    // 已有的升级和道具的名称
+    fn upgrades(&self) -> Vec<&'static str> {
+        let mut copy = self.clone();
+        UPGRADE_NAMES.into_iter().filter(|name| copy.flag(name).map_or(false, |flag| *flag)).collect()
+    }
+}
+
+// 回放文件头
+#[derive(Debug, Clone)]
+pub struct ReplayHeader {
+    pub version: u32,
+    pub seed: u64,
+    pub level: u32,
+    pub player: String,
+    pub date: u64,
+    pub duration: u64,
+    pub target: Option<i32>,
+    pub mode: GameMode,
+    pub difficulty: Difficulty,
+    pub nudge: f32,
+    pub bonus: bool,
+    pub new_game_plus: u32,
+    pub loadout: Loadout,
+    pub story: Option<usize>,
+    pub prestige: u32,
+}
+
+// 一局游戏的完整回放
+#[derive(Debug, Clone)]
+pub struct Replay {
+    pub header: ReplayHeader,
+    pub inputs: Vec<ReplayInput>,
+}
+
+impl Replay {
+    // 开始录制新回放
+    pub fn new(seed: u64, level: u32, player: &str) -> Self {
+        let date = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        Replay {
+            header: ReplayHeader {
+                version: REPLAY_VERSION,
+                seed,
+                level,
+                player: player.to_string(),
+                date,
+                duration: 0,
+                target: None,
+                mode: GameMode::Classic,
+                difficulty: Difficulty::Normal,
+                nudge: 0.0,
+                bonus: false,
+                new_game_plus: 0,
+                loadout: Loadout::default(),
+                story: None,
+                prestige: 0,
+            },
+            inputs: Vec::new(),
+        }
+    }
+
+    // 记录一次输入
+    pub fn record(&mut self, tick: u64, action: InputAction) {
+        self.inputs.push(ReplayInput { tick, action });
+    }
+
+    // 写入文件
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut out = BufWriter::new(fs::File::create(path)?);
+        writeln!(out, "{} {}", REPLAY_MAGIC, self.header.version)?;
+        writeln!(out, "seed {}", self.header.seed)?;
+        writeln!(out, "level {}", self.header.level)?;
+        writeln!(out, "player {}", self.header.player)?;
+        writeln!(out, "date {}", self.header.date)?;
+        writeln!(out, "duration {}", self.header.duration)?;
+        if let Some(target) = self.header.target {
+            writeln!(out, "target {}", target)?;
+        }
+        if self.header.mode != GameMode::Classic {
+            writeln!(out, "mode {}", self.header.mode.name())?;
+        }
+        if self.header.difficulty != Difficulty::Normal {
+            writeln!(out, "difficulty {}", self.header.difficulty.name())?;
+        }
+        if self.header.nudge != 0.0 {
+            writeln!(out, "nudge {}", self.header.nudge)?;
+        }
+        if self.header.bonus {
+            writeln!(out, "bonus true")?;
+        }
+        if self.header.new_game_plus > 0 {
+            writeln!(out, "plus {}", self.header.new_game_plus)?;
+        }
+        let loadout = &self.header.loadout;
+        if loadout.hook_tier > 0 {
+            writeln!(out, "hook_tier {}", loadout.hook_tier)?;
+        }
+        if loadout.insurance > 0 {
+            writeln!(out, "insurance {}", loadout.insurance)?;
+        }
+        if loadout.dynamite > 0 {
+            writeln!(out, "dynamite {}", loadout.dynamite)?;
+        }
+        let upgrades = loadout.upgrades();
+        if !upgrades.is_empty() {
+            writeln!(out, "upgrades {}", upgrades.join(" "))?;
+        }
+        if let Some(story) = self.header.story {
+            writeln!(out, "story {}", story)?;
+        }
+        if self.header.prestige > 0 {
+            writeln!(out, "prestige {}", self.header.prestige)?;
+        }
+        writeln!(out, "inputs")?;
+        for input in &self.inputs {
+            match input.action {
+                InputAction::MoveLeft => writeln!(out, "{} L", input.tick)?,
+                InputAction::MoveRight => writeln!(out, "{} R", input.tick)?,
+                InputAction::Coast => writeln!(out, "{} C", input.tick)?,
+                InputAction::Throw { angle, power } => writeln!(out, "{} T {} {}", input.tick, angle, power)?,
+                InputAction::Drill => writeln!(out, "{} D", input.tick)?,
+                InputAction::LobDynamite { x, y } => writeln!(out, "{} X {} {}", input.tick, x, y)?,
+                InputAction::BlastCatch => writeln!(out, "{} B", input.tick)?,
+            }
+        }
+        out.flush()
+    }
+
+    // 导出到回放目录，返回文件路径
+    pub fn export(&self) -> io::Result<PathBuf> {
+        fs::create_dir_all(REPLAY_DIR)?;
+        let file_name = format!(
+            "{}_{}.{}",
+            self.header.date, self.header.seed, REPLAY_EXTENSION
+        );
+        let path = Path::new(REPLAY_DIR).join(file_name);
+        self.save(&path)?;
+        Ok(path)
+    }
+
+    // 从文件读取
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let reader = BufReader::new(fs::File::open(path)?);
+        let mut lines = reader.lines();
+
+        let magic_line = lines.next().ok_or_else(|| invalid("文件为空"))??;
+        let version = match magic_line.split_once(' ') {
+            Some((REPLAY_MAGIC, v)) => v.trim().parse::<u32>().map_err(|_| invalid("版本号无效"))?,
+            _ => return Err(invalid("不是回放文件")),
+        };
+        if version > REPLAY_VERSION {
+            return Err(invalid("回放版本过新"));
+        }
+        if version < REPLAY_VERSION {
+            return Err(invalid("回放版本过旧，无法按当前规则重放"));
+        }
+
+        let mut header = ReplayHeader {
+            version,
+            seed: 0,
+            level: 1,
+            player: String::new(),
+            date: 0,
+            duration: 0,
+            target: None,
+            mode: GameMode::Classic,
+            difficulty: Difficulty::Normal,
+            nudge: 0.0,
+            bonus: false,
+            new_game_plus: 0,
+            loadout: Loadout::default(),
+            story: None,
+            prestige: 0,
+        };
+
+        // 读取文件头，直到 inputs 行
+        for line in lines.by_ref() {
+            let line = line?;
+            if line == "inputs" {
+                break;
+            }
+            let (key, value) = line.split_once(' ').unwrap_or((line.as_str(), ""));
+            match key {
+                "seed" => header.seed = parse_field(value)?,
+                "level" => header.level = parse_field(value)?,
+                "player" => header.player = value.to_string(),
+                "date" => header.date = parse_field(value)?,
+                "duration" => header.duration = parse_field(value)?,
+                "target" => header.target = Some(parse_field(value)?),
+                "mode" => header.mode = GameMode::from_name(value).ok_or_else(|| invalid("未知的模式"))?,
+                "difficulty" => header.difficulty = Difficulty::from_name(value).ok_or_else(|| invalid("未知的难度"))?,
+                "nudge" => header.nudge = parse_field(value)?,
+                "bonus" => header.bonus = parse_field(value)?,
+                "plus" => header.new_game_plus = parse_field(value)?,
+                "hook_tier" => header.loadout.hook_tier = parse_field(value)?,
+                "insurance" => header.loadout.insurance = parse_field(value)?,
+                "dynamite" => header.loadout.dynamite = parse_field(value)?,
+                "story" => header.story = Some(parse_field(value)?),
+                "prestige" => header.prestige = parse_field(value)?,
+                "upgrades" => {
+                    for name in value.split_whitespace() {
+                        *header.loadout.flag(name).ok_or_else(|| invalid("未知的升级"))? = true;
+                    }
+                }
+                _ => (), // 忽略未知字段，方便以后扩展
+            }
+        }
+
+        // 读取输入
+        let mut inputs = Vec::new();
+        for line in lines {
+            let line = line?;
+            let mut parts = line.split_whitespace();
+            let tick = match parts.next() {
+                Some(t) => parse_field(t)?,
+                None => continue,
+            };
+            let action = match parts.next() {
+                Some("L") => InputAction::MoveLeft,
+                Some("R") => InputAction::MoveRight,
+                Some("C") => InputAction::Coast,
+                Some("T") => {
+                    let angle = parse_field(parts.next().unwrap_or(""))?;
+                    let power = match parts.next() {
+                        Some(power) => parse_field(power)?,
+                        None => 1.0,
+                    };
+                    InputAction::Throw { angle, power }
+                }
+                Some("D") => InputAction::Drill,
+                Some("X") => InputAction::LobDynamite {
+                    x: parse_field(parts.next().unwrap_or(""))?,
+                    y: parse_field(parts.next().unwrap_or(""))?,
+                },
+                Some("B") => InputAction::BlastCatch,
+                _ => return Err(invalid("未知的输入类型")),
+            };
+            inputs.push(ReplayInput { tick, action });
+        }
+
+        Ok(Replay { header, inputs })
+    }
+}
+
+// 回放播放器，按帧号吐出输入
+#[derive(Debug)]
+pub struct ReplayPlayer {
+    pub replay: Replay,
+    cursor: usize,
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay) -> Self {
+        ReplayPlayer { replay, cursor: 0 }
+    }
+
+    // 取出某一帧的所有输入
+    pub fn actions_at(&mut self, tick: u64) -> Vec<InputAction> {
+        let mut actions = Vec::new();
+        while let Some(input) = self.replay.inputs.get(self.cursor) {
+            if input.tick > tick {
+                break;
+            }
+            actions.push(input.action);
+            self.cursor += 1;
+        }
+        actions
+    }
+
+    // 回放是否已经播完
+    pub fn finished(&self, tick: u64) -> bool {
+        tick >= self.replay.header.duration
+    }
+}
+
+// 查找回放目录中最新的回放文件
+pub fn latest_replay() -> Option<PathBuf> {
+    fs::read_dir(REPLAY_DIR)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == REPLAY_EXTENSION))
+        .max_by_key(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+}
+
+fn parse_field<T: std::str::FromStr>(value: &str) -> io::Result<T> {
+    value.trim().parse().map_err(|_| invalid("字段格式错误"))
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
@@ -0,0 +1,106 @@
+// 落石：矿洞顶上时不时掉下石头，砸向矿工所在的那一排。每块落石先在地面投下阴影提醒，
+// 砸中矿工扣一颗心并让他晕一会儿。掉落的位置和时间用按种子初始化的随机数，回放时完全一致
+use ggez::{
+    graphics::{self, Color, Rect},
+    nalgebra as na, Context, GameResult,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::config::GameConfig;
+use crate::TICKS_PER_SECOND;
+
+const ROCK_SIZE: f32 = 18.0;
+const FALL_SPEED: f32 = 2.0; // 每帧下落的像素
+const DROP_HEIGHT: f32 = -60.0; // 从屏幕顶端以上开始掉
+const AIM_SPREAD: f32 = 120.0; // 落点在矿工附近随机偏移的范围
+
+#[derive(Debug, Clone)]
+pub struct FallingRock {
+    pub position: na::Point2<f32>,
+}
+
+impl FallingRock {
+    fn rect(&self) -> Rect {
+        Rect::new(
+            self.position.x - ROCK_SIZE / 2.0,
+            self.position.y - ROCK_SIZE / 2.0,
+            ROCK_SIZE,
+            ROCK_SIZE,
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RockFall {
+    pub rocks: Vec<FallingRock>,
+    rng: StdRng,
+    interval: u64, // 平均每隔多少帧掉一块，0 为不掉
+    next_drop: u64,
+    ground: f32, // 落石砸到地面的高度
+}
+
+impl Default for RockFall {
+    fn default() -> Self {
+        RockFall {
+            rocks: Vec::new(),
+            rng: StdRng::seed_from_u64(0),
+            interval: 0,
+            next_drop: 0,
+            ground: 0.0,
+        }
+    }
+}
+
+impl RockFall {
+    pub fn new(seed: u64, config: &GameConfig, ground: f32) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed.wrapping_add(0x726f636b));
+        let interval = config.profile().interval(config.rock_fall_seconds) * TICKS_PER_SECOND as u64;
+        let next_drop = if interval > 0 { rng.gen_range(interval / 2..=interval * 3 / 2) } else { 0 };
+        RockFall {
+            rocks: Vec::new(),
+            rng,
+            interval,
+            next_drop,
+            ground,
+        }
+    }
+
+    // 每帧：到点朝矿工附近掉一块石头，已有的落石下落；砸中矿工时返回 true
+    pub fn update(&mut self, tick: u64, miner: Rect, screen_width: f32) -> bool {
+        if self.interval > 0 && tick >= self.next_drop {
+            let center = miner.x + miner.w / 2.0;
+            let x = (center + self.rng.gen_range(-AIM_SPREAD..AIM_SPREAD)).clamp(ROCK_SIZE, screen_width - ROCK_SIZE);
+            self.rocks.push(FallingRock {
+                position: na::Point2::new(x, DROP_HEIGHT),
+            });
+            self.next_drop = tick + self.rng.gen_range(self.interval / 2..=self.interval * 3 / 2);
+        }
+
+        let mut hit = false;
+        for rock in self.rocks.iter_mut() {
+            rock.position.y += FALL_SPEED;
+        }
+        self.rocks.retain(|rock| {
+            if rock.rect().overlaps(&miner) {
+                hit = true;
+                return false;
+            }
+            rock.position.y < self.ground
+        });
+        hit
+    }
+
+    pub fn draw(&self, ctx: &mut Context) -> GameResult {
+        for rock in &self.rocks {
+            // 地面上的阴影，越接近越大
+            let closeness = ((rock.position.y - DROP_HEIGHT) / (self.ground - DROP_HEIGHT)).clamp(0.0, 1.0);
+            let shadow_w = ROCK_SIZE * (0.4 + 0.6 * closeness);
+            let shadow = Rect::new(rock.position.x - shadow_w / 2.0, self.ground - 3.0, shadow_w, 3.0);
+            graphics::rectangle(ctx, graphics::DrawParam::default().dest(shadow.point()), &shadow, Color::new(0.0, 0.0, 0.0, 0.5))?;
+
+            let body = rock.rect();
+            graphics::rectangle(ctx, graphics::DrawParam::default().dest(body.point()), &body, Color::new(0.45, 0.42, 0.4, 1.0))?;
+        }
+        Ok(())
+    }
+}
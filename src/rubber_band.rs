@@ -0,0 +1,48 @@
+// 橡皮筋平衡：对战时每波补充物品，落后一方那半边的物品有一定概率升一档品质，
+// 差距越大概率越高，让休闲对局保持胶着；竞技对局用配置项 rubber_band 关闭。
+// 左半边是玩家一，右半边是玩家二
+use rand::Rng;
+
+use crate::config::GameConfig;
+use crate::Item;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+// 分数落后的一方，打平时为 None
+pub fn trailing_side(left_score: i32, right_score: i32) -> Option<Side> {
+    match left_score.cmp(&right_score) {
+        std::cmp::Ordering::Less => Some(Side::Left),
+        std::cmp::Ordering::Greater => Some(Side::Right),
+        std::cmp::Ordering::Equal => None,
+    }
+}
+
+// 对新一波物品做补偿，返回升档的数量
+pub fn apply(items: &mut [Item], scores: (i32, i32), config: &GameConfig, rng: &mut impl Rng) -> usize {
+    if !config.rubber_band {
+        return 0;
+    }
+    let side = match trailing_side(scores.0, scores.1) {
+        Some(side) => side,
+        None => return 0,
+    };
+    let deficit = (scores.0 - scores.1).abs() as f32;
+    let chance = (deficit / config.rubber_band_full_deficit).min(1.0) * config.rubber_band_strength;
+    let middle = config.screen_width / 2.0;
+
+    let mut upgraded = 0;
+    for item in items.iter_mut().filter(|item| !item.collected) {
+        let on_side = match side {
+            Side::Left => item.position.x < middle,
+            Side::Right => item.position.x >= middle,
+        };
+        if on_side && rng.gen::<f32>() < chance && item.upgrade() {
+            upgraded += 1;
+        }
+    }
+    upgraded
+}
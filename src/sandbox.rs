@@ -0,0 +1,54 @@
+// 沙盒练习：不计时、没有目标分和危险物，也不记纪录和回放。
+// 左键在鼠标处放下当前选中的物品，Tab 换下一种，C 清空场地，用来练习出钩或试验新物品的手感
+use ggez::nalgebra as na;
+
+use crate::config::GameConfig;
+use crate::{BagSize, Item, ItemType};
+
+// 可以放的物品，按 Tab 依次切换
+const PALETTE: [ItemType; 24] = [
+    ItemType::Gold(BagSize::Small),
+    ItemType::Gold(BagSize::Medium),
+    ItemType::Gold(BagSize::Large),
+    ItemType::Silver,
+    ItemType::Diamond,
+    ItemType::Rock,
+    ItemType::OilCan,
+    ItemType::MysteryBag,
+    ItemType::Mole,
+    ItemType::DiamondMole,
+    ItemType::GoldenMole,
+    ItemType::TntBarrel,
+    ItemType::GoldenPig,
+    ItemType::Key,
+    ItemType::Chest,
+    ItemType::Skull,
+    ItemType::Bone,
+    ItemType::Clock,
+    ItemType::FuelCan,
+    ItemType::MoltenGold,
+    ItemType::MagnetPickup,
+    ItemType::StrengthPickup,
+    ItemType::DoublePickup,
+    ItemType::RockBreakerPickup,
+];
+
+#[derive(Debug, Clone, Default)]
+pub struct Sandbox {
+    selected: usize,
+}
+
+impl Sandbox {
+    pub fn current(&self) -> ItemType {
+        PALETTE[self.selected]
+    }
+
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1) % PALETTE.len();
+    }
+
+    // 在 position 处放一件当前选中的物品
+    pub fn spawn(&self, position: na::Point2<f32>, config: &GameConfig) -> Item {
+        Item::new(self.current(), position.x, position.y, config.item_size)
+    }
+}
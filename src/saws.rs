@@ -0,0 +1,100 @@
+// 锯片：在矿洞深处来回移动的圆锯，伸出去的绳子碰到锯片就会被割断，
+// 钩子上的东西丢失，钩子要过一会儿才能重新装好。位置和速度按种子生成，回放时完全一致
+use ggez::{
+    graphics::{self, Color, Rect},
+    nalgebra as na, Context, GameResult,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::config::GameConfig;
+
+const PATROL_WIDTH: f32 = 200.0; // 锯片来回移动的范围
+
+#[derive(Debug, Clone)]
+pub struct Saw {
+    pub center: na::Point2<f32>,
+    velocity: f32, // 每帧水平移动的像素
+    min_x: f32,
+    max_x: f32,
+    spin: f32, // 转动的角度，只用于绘制
+}
+
+// 本关的全部锯片
+#[derive(Debug, Clone, Default)]
+pub struct SawBlades {
+    pub saws: Vec<Saw>,
+    radius: f32,
+}
+
+impl SawBlades {
+    pub fn roll(seed: u64, config: &GameConfig) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed.wrapping_add(0x73617773));
+        let saws = (0..config.profile().hazards(config.saw_count))
+            .map(|_| {
+                let x = rng.gen_range(PATROL_WIDTH / 2.0..config.screen_width - PATROL_WIDTH / 2.0);
+                let y = rng.gen_range(config.mine_depth * 0.3..config.mine_depth * 0.9);
+                let speed = config.saw_speed * rng.gen_range(0.7..1.3);
+                Saw {
+                    center: na::Point2::new(x, y),
+                    velocity: if rng.gen_bool(0.5) { speed } else { -speed },
+                    min_x: x - PATROL_WIDTH / 2.0,
+                    max_x: x + PATROL_WIDTH / 2.0,
+                    spin: 0.0,
+                }
+            })
+            .collect();
+        SawBlades {
+            saws,
+            radius: config.saw_radius,
+        }
+    }
+
+    // 每帧在巡逻范围内来回移动
+    pub fn update(&mut self) {
+        for saw in self.saws.iter_mut() {
+            saw.center.x += saw.velocity;
+            if (saw.center.x < saw.min_x && saw.velocity < 0.0) || (saw.center.x > saw.max_x && saw.velocity > 0.0) {
+                saw.velocity = -saw.velocity;
+            }
+            saw.spin += 0.3;
+        }
+    }
+
+    // 一段绳子（折线）是否碰到某个锯片，返回碰到的锯片位置
+    pub fn cuts(&self, strand: &[na::Point2<f32>]) -> Option<na::Point2<f32>> {
+        self.saws
+            .iter()
+            .find(|saw| strand.windows(2).any(|pair| distance_to_segment(saw.center, pair[0], pair[1]) < self.radius))
+            .map(|saw| saw.center)
+    }
+
+    pub fn draw(&self, ctx: &mut Context) -> GameResult {
+        for saw in &self.saws {
+            let r = self.radius;
+            let body = Rect::new(saw.center.x - r * 0.7, saw.center.y - r * 0.7, r * 1.4, r * 1.4);
+            graphics::rectangle(ctx, graphics::DrawParam::default().dest(body.point()), &body, Color::new(0.6, 0.6, 0.65, 1.0))?;
+            // 两道锯齿随转动旋转
+            for k in 0..2 {
+                let angle = saw.spin + k as f32 * std::f32::consts::FRAC_PI_2;
+                let (dx, dy) = (angle.cos() * r, angle.sin() * r);
+                let tips = [
+                    na::Point2::new(saw.center.x - dx, saw.center.y - dy),
+                    na::Point2::new(saw.center.x + dx, saw.center.y + dy),
+                ];
+                graphics::line(ctx, graphics::DrawParam::default(), &tips, 3.0, Color::new(0.85, 0.85, 0.9, 1.0))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// 点到线段的距离
+fn distance_to_segment(point: na::Point2<f32>, start: na::Point2<f32>, end: na::Point2<f32>) -> f32 {
+    let segment = end - start;
+    let length_sq = segment.norm_squared();
+    if length_sq == 0.0 {
+        return na::distance(&point, &start);
+    }
+    let t = ((point - start).dot(&segment) / length_sq).clamp(0.0, 1.0);
+    na::distance(&point, &(start + segment * t))
+}
@@ -0,0 +1,48 @@
+// 音效：订阅玩法事件，把要播放的音效记下来，等到有 Context 的帧回调里统一播放。
+// 音效文件放在 sounds/ 目录，缺失时静默跳过
+use ggez::{
+    audio::{self, SoundSource},
+    Context,
+};
+
+use crate::events::{EventSubscriber, GameEvent};
+
+const EXPLOSION: &str = "/sounds/explosion.ogg";
+const MAX_PLAYING: usize = 8;
+
+#[derive(Default)]
+pub struct SoundEffects {
+    pending: Vec<&'static str>,
+    playing: Vec<audio::Source>,
+}
+
+impl SoundEffects {
+    pub fn new() -> Self {
+        SoundEffects::default()
+    }
+
+    // 播放本帧排队的音效，同一帧里重复的只播一次
+    pub fn play_pending(&mut self, ctx: &mut Context) {
+        self.playing.retain(|source| source.playing());
+        let mut pending = std::mem::take(&mut self.pending);
+        pending.dedup();
+        for path in pending {
+            if self.playing.len() >= MAX_PLAYING {
+                break;
+            }
+            if let Ok(mut source) = audio::Source::new(ctx, path) {
+                if source.play(ctx).is_ok() {
+                    self.playing.push(source);
+                }
+            }
+        }
+    }
+}
+
+impl EventSubscriber for SoundEffects {
+    fn on_event(&mut self, event: &GameEvent) {
+        if let GameEvent::ExplosionTriggered { .. } = event {
+            self.pending.push(EXPLOSION);
+        }
+    }
+}
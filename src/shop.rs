@@ -0,0 +1,258 @@
+// 关卡之间的商店：店主报价，可以讨价还价——指针在价格条上来回摆动，
+// 按空格停在越靠近中间的位置越便宜，最多便宜两成，停在两端反而涨价两成
+use ggez::{
+    graphics::{self, Color, Rect},
+    Context, GameResult,
+};
+
+use crate::draw_centered_text;
+
+const HAGGLE_RANGE: f32 = 0.2; // 讨价还价的价格浮动范围
+const NEEDLE_PERIOD_TICKS: u64 = 90; // 指针来回一次的帧数
+const BAR_WIDTH: f32 = 300.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ware {
+    Dynamite,
+    Appraiser,
+    StrengthDrink, // 下一关拖物品更快
+    LuckyClover,   // 下一关不会拉到成色差的物品，神秘袋也更丰厚
+    RockBook,      // 本局剩下的关卡石头价值翻三倍
+    GemPolish,     // 本局剩下的关卡钻石等宝石价值提高五成
+    Magnet,        // 下一关开头一段时间钩子吸引附近的物品
+    HookSpeed(u8), // 本局剩下的关卡钩子伸出和收回都更快，分 I/II/III 三档依次购买
+    BigClaw,       // 本局剩下的关卡钩爪更大，更容易抓到小东西
+    DualHook,      // 本局剩下的关卡多一个钩子，第一个在收时可以再出第二个
+    FireproofRope, // 本局剩下的关卡拖金水不会烧断绳子
+    RicochetHook,  // 本局剩下的关卡钩子碰到侧墙反弹一次
+    Insurance,     // 抵掉下一次倒扣钱或断绳，用掉为止
+}
+
+impl Ware {
+    pub fn name(self) -> &'static str {
+        match self {
+            Ware::Dynamite => "Dynamite",
+            Ware::Appraiser => "Appraiser's Loupe",
+            Ware::StrengthDrink => "Strength Drink",
+            Ware::LuckyClover => "Lucky Clover",
+            Ware::RockBook => "Rock Collector's Book",
+            Ware::GemPolish => "Gem Polish",
+            Ware::Magnet => "Magnet",
+            Ware::HookSpeed(1) => "Hook Speed I",
+            Ware::HookSpeed(2) => "Hook Speed II",
+            Ware::HookSpeed(_) => "Hook Speed III",
+            Ware::BigClaw => "Big Claw",
+            Ware::DualHook => "Dual Hook",
+            Ware::FireproofRope => "Fireproof Rope",
+            Ware::RicochetHook => "Ricochet Hook",
+            Ware::Insurance => "Insurance Policy",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Ware::Dynamite => "One more stick for the next level. Up: blow up a bad catch.",
+            Ware::Appraiser => "Hover over an item to see its exact value.",
+            Ware::StrengthDrink => "Reel in heavy catches much faster next level.",
+            Ware::LuckyClover => "No poor-quality finds and better mystery bags next level.",
+            Ware::RockBook => "Rocks are worth triple for the rest of the run.",
+            Ware::GemPolish => "Diamonds and other gems are worth 50% more for the rest of the run.",
+            Ware::Magnet => "For the first 30s of the next level, the hook pulls nearby items in.",
+            Ware::HookSpeed(_) => "The hook shoots out and reels in faster for the rest of the run.",
+            Ware::BigClaw => "A wider claw that snags small items more easily for the rest of the run.",
+            Ware::DualHook => "Fire a second hook while the first is still out, for the rest of the run.",
+            Ware::FireproofRope => "Molten gold no longer burns through the rope, for the rest of the run.",
+            Ware::RicochetHook => "The hook bounces off the side walls once, for the rest of the run.",
+            Ware::Insurance => "Covers the next skull or cut rope, then it's used up.",
+        }
+    }
+
+    pub fn base_price(self) -> i64 {
+        match self {
+            Ware::Dynamite => 150,
+            Ware::Appraiser => 500,
+            Ware::StrengthDrink => 200,
+            Ware::LuckyClover => 250,
+            Ware::RockBook => 300,
+            Ware::GemPolish => 400,
+            Ware::Magnet => 300,
+            Ware::HookSpeed(1) => 300,
+            Ware::HookSpeed(2) => 600,
+            Ware::HookSpeed(_) => 1000,
+            Ware::BigClaw => 350,
+            Ware::DualHook => 1200,
+            Ware::FireproofRope => 450,
+            Ware::RicochetHook => 400,
+            Ware::Insurance => 250,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Offer {
+    pub ware: Ware,
+    pub price: i64,
+    pub haggled: bool, // 每件商品只能还一次价
+    pub sold: bool,
+}
+
+// 讨价还价小游戏
+#[derive(Debug, Clone)]
+pub struct Haggle {
+    offer: usize,
+    tick: u64,
+}
+
+impl Haggle {
+    // 指针位置，-1 ~ 1，0 为正中
+    pub fn needle(&self) -> f32 {
+        let phase = (self.tick % NEEDLE_PERIOD_TICKS) as f32 / NEEDLE_PERIOD_TICKS as f32;
+        (phase * std::f32::consts::TAU).sin()
+    }
+
+    // 停在当前位置对应的价格倍率
+    pub fn price_factor(&self) -> f32 {
+        1.0 - HAGGLE_RANGE + 2.0 * HAGGLE_RANGE * self.needle().abs()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Shop {
+    pub offers: Vec<Offer>,
+    pub selected: usize,
+    pub haggle: Option<Haggle>,
+    pub message: Option<String>,
+}
+
+impl Shop {
+    // discount 为整体价格倍率（合约奖励的折扣等）
+    pub fn new(wares: &[Ware], discount: f32) -> Self {
+        Shop {
+            offers: wares
+                .iter()
+                .map(|&ware| Offer {
+                    ware,
+                    price: (ware.base_price() as f32 * discount).round() as i64,
+                    haggled: false,
+                    sold: false,
+                })
+                .collect(),
+            selected: 0,
+            haggle: None,
+            message: None,
+        }
+    }
+
+    pub fn update(&mut self) {
+        if let Some(haggle) = self.haggle.as_mut() {
+            haggle.tick += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if self.haggle.is_none() {
+            self.selected = self.selected.saturating_sub(1);
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if self.haggle.is_none() {
+            self.selected = (self.selected + 1).min(self.offers.len().saturating_sub(1));
+        }
+    }
+
+    // 开始还价，或在还价中按下时停住指针定下价格
+    pub fn haggle(&mut self) {
+        match self.haggle.take() {
+            Some(haggle) => {
+                let factor = haggle.price_factor();
+                let offer = &mut self.offers[haggle.offer];
+                offer.price = (offer.price as f32 * factor).round() as i64;
+                let change = ((factor - 1.0) * 100.0).round() as i32;
+                self.message = Some(if change <= 0 {
+                    format!("\"Fine, fine... {} for {}.\" ({:+}%)", offer.ware.name(), offer.price, change)
+                } else {
+                    format!("\"You insult me! Now it's {}.\" ({:+}%)", offer.price, change)
+                });
+            }
+            None => match self.offers.get_mut(self.selected) {
+                Some(offer) if !offer.haggled && !offer.sold => {
+                    offer.haggled = true;
+                    self.haggle = Some(Haggle {
+                        offer: self.selected,
+                        tick: 0,
+                    });
+                    self.message = None;
+                }
+                Some(_) => self.message = Some("\"My final offer stands.\"".to_string()),
+                None => (),
+            },
+        }
+    }
+
+    // 用 money 买下选中的商品，成功时返回商品
+    pub fn buy(&mut self, money: &mut i64) -> Option<Ware> {
+        if self.haggle.is_some() {
+            return None;
+        }
+        let offer = self.offers.get_mut(self.selected)?;
+        if offer.sold {
+            return None;
+        }
+        if *money < offer.price {
+            self.message = Some("\"Come back when you can afford it.\"".to_string());
+            return None;
+        }
+        *money -= offer.price;
+        offer.sold = true;
+        self.message = Some(format!("\"Pleasure doing business.\" Bought {}.", offer.ware.name()));
+        Some(offer.ware)
+    }
+
+    pub fn draw(&self, ctx: &mut Context, money: i64, screen_w: f32, screen_h: f32) -> GameResult {
+        let gold = Color::new(1.0, 0.84, 0.0, 1.0);
+        draw_centered_text(ctx, screen_w, "Shop", 40.0, gold, 40.0)?;
+        draw_centered_text(ctx, screen_w, &format!("Money: ${}", money), 22.0, Color::WHITE, 95.0)?;
+
+        for (i, offer) in self.offers.iter().enumerate() {
+            let color = if offer.sold {
+                Color::new(0.5, 0.5, 0.5, 1.0)
+            } else if i == self.selected {
+                gold
+            } else {
+                Color::WHITE
+            };
+            let status = if offer.sold { "  (sold)" } else { "" };
+            let line = format!("{}  ${}{}", offer.ware.name(), offer.price, status);
+            draw_centered_text(ctx, screen_w, &line, 26.0, color, 140.0 + i as f32 * 32.0)?;
+        }
+        if let Some(offer) = self.offers.get(self.selected) {
+            let y = 150.0 + self.offers.len() as f32 * 32.0;
+            draw_centered_text(ctx, screen_w, offer.ware.description(), 16.0, Color::new(0.8, 0.8, 0.8, 1.0), y)?;
+        }
+
+        // 还价的价格条：中间绿色最便宜，两端红色涨价
+        if let Some(haggle) = &self.haggle {
+            let bar = Rect::new((screen_w - BAR_WIDTH) / 2.0, screen_h / 2.0 + 40.0, BAR_WIDTH, 20.0);
+            graphics::rectangle(ctx, graphics::DrawParam::default().dest(bar.point()), &bar, Color::new(0.7, 0.2, 0.2, 1.0))?;
+            let sweet = Rect::new(bar.x + BAR_WIDTH * 0.4, bar.y, BAR_WIDTH * 0.2, bar.h);
+            graphics::rectangle(ctx, graphics::DrawParam::default().dest(sweet.point()), &sweet, Color::new(0.2, 0.7, 0.3, 1.0))?;
+            let x = bar.x + BAR_WIDTH * (haggle.needle() + 1.0) / 2.0;
+            let needle = Rect::new(x - 2.0, bar.y - 6.0, 4.0, bar.h + 12.0);
+            graphics::rectangle(ctx, graphics::DrawParam::default().dest(needle.point()), &needle, Color::WHITE)?;
+            draw_centered_text(ctx, screen_w, "Space: stop the needle", 16.0, Color::WHITE, bar.y + 35.0)?;
+        }
+
+        if let Some(message) = &self.message {
+            draw_centered_text(ctx, screen_w, message, 18.0, Color::WHITE, screen_h - 110.0)?;
+        }
+        draw_centered_text(
+            ctx,
+            screen_w,
+            "Up/Down: choose   H: haggle   Enter: buy   Esc: next level",
+            16.0,
+            Color::new(0.7, 0.7, 0.7, 1.0),
+            screen_h - 40.0,
+        )
+    }
+}
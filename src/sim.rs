@@ -0,0 +1,159 @@
+// 无窗口的模拟接口（类似 Gym）：reset(seed) / step(action)，供强化学习和写机器人使用
+use crate::config::GameConfig;
+use crate::input::{InputAction, InputSource};
+use crate::replay::Replay;
+use crate::{GameState, HookState, ItemType, Scene, TICKS_PER_SECOND};
+
+// 钩子阶段
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HookPhase {
+    Idle,
+    Thrown,
+    Retracting,
+}
+
+// 单个物品的观测
+#[derive(Debug, Clone)]
+pub struct ItemObservation {
+    pub item_type: ItemType,
+    pub x: f32,
+    pub y: f32,
+    pub value: i32,
+}
+
+// 一帧的观测
+#[derive(Debug, Clone)]
+pub struct Observation {
+    pub tick: u64,
+    pub ticks_left: u64,
+    pub score: i32,
+    pub miner_x: f32,
+    pub miner_y: f32,
+    pub hook_phase: HookPhase,
+    pub hook_angle: f32,
+    pub hook_length: f32,
+    pub items: Vec<ItemObservation>, // 只包含还留在矿里的物品
+}
+
+// step 的返回值
+#[derive(Debug, Clone)]
+pub struct StepResult {
+    pub observation: Observation,
+    pub reward: f32,
+    pub done: bool,
+}
+
+// 智能体输入：step 时放入一条命令，下一帧执行
+#[derive(Debug, Default)]
+struct AgentInput {
+    next: Option<InputAction>,
+}
+
+impl InputSource for AgentInput {
+    fn poll(&mut self, _tick: u64) -> Vec<InputAction> {
+        self.next.take().into_iter().collect()
+    }
+
+    fn push_local(&mut self, action: InputAction) {
+        self.next = Some(action);
+    }
+}
+
+pub struct Simulation {
+    state: GameState,
+}
+
+impl Simulation {
+    pub fn new(config: GameConfig) -> Self {
+        let mut sim = Simulation {
+            state: GameState::headless(config),
+        };
+        sim.reset(0);
+        sim
+    }
+
+    // 用给定种子重新开始，返回初始观测
+    pub fn reset(&mut self, seed: u64) -> Observation {
+        self.state.start_round(seed, Box::new(AgentInput::default()));
+        self.state.scene = Scene::Playing;
+        self.observe()
+    }
+
+    // 执行一个动作（None 表示本帧不操作）并推进一帧
+    pub fn step(&mut self, action: Option<InputAction>) -> StepResult {
+        if let Some(action) = action {
+            self.state.input.push_local(action);
+        }
+        let score_before = self.state.level_score;
+        self.state.update(1.0 / TICKS_PER_SECOND as f32);
+
+        StepResult {
+            observation: self.observe(),
+            reward: (self.state.level_score - score_before) as f32,
+            done: self.state.game_over,
+        }
+    }
+
+    pub fn observe(&self) -> Observation {
+        let state = &self.state;
+        let hook = &state.hooks[0]; // 观测只包含主钩
+        Observation {
+            tick: state.tick,
+            ticks_left: state.ticks_left(),
+            score: state.level_score,
+            miner_x: state.miner.position.x,
+            miner_y: state.miner.position.y,
+            hook_phase: match hook.state {
+                HookState::Idle => HookPhase::Idle,
+                HookState::Thrown => HookPhase::Thrown,
+                HookState::Retracting => HookPhase::Retracting,
+            },
+            hook_angle: hook.angle,
+            hook_length: hook.length,
+            items: state
+                .items
+                .iter()
+                .filter(|item| !item.collected)
+                .map(|item| ItemObservation {
+                    item_type: item.item_type,
+                    x: item.position.x,
+                    y: item.position.y,
+                    value: item.value(&state.value_context()),
+                })
+                .collect(),
+        }
+    }
+
+    // 本局的回放，可导出后在游戏里观看智能体的表现
+    pub fn replay(&self) -> &Replay {
+        &self.state.recording
+    }
+}
+
+// 命令行入口：用简单的贪心机器人跑若干局，打印得分
+pub fn run_bot_episodes(config: GameConfig, episodes: u64) {
+    let mut sim = Simulation::new(config);
+    for seed in 0..episodes {
+        let mut obs = sim.reset(seed);
+        loop {
+            // 空闲时瞄准价值最高的物品
+            let action = if obs.hook_phase == HookPhase::Idle {
+                obs.items
+                    .iter()
+                    .max_by_key(|item| item.value)
+                    .map(|item| InputAction::Throw {
+                        angle: (item.y - obs.miner_y).atan2(item.x - obs.miner_x),
+                        power: 1.0,
+                    })
+            } else {
+                None
+            };
+            let result = sim.step(action);
+            obs = result.observation;
+            if result.done {
+                break;
+            }
+        }
+        println!("seed {}: score {}", seed, obs.score);
+    }
+}
@@ -0,0 +1,189 @@
+// 关卡之间的老虎机：结算画面按 G 进来，从钱包里押一部分钱转三个转轮。
+// 三个一样按图案赔大钱，两个一样（骷髅除外）赔押注的 1.5 倍，其他情况押注输光；长期玩下来庄家略占便宜。
+// 转轮停在哪里开转时就定好了，之后只是动画：三个转轮先后停下
+use ggez::{
+    graphics::{self, Color, Rect, Text, TextFragment},
+    nalgebra as na, Context, GameResult,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{draw_centered_text, BagSize, ItemType};
+
+const WAGER_STEP: i64 = 50;
+const MAX_WAGER_SHARE: i64 = 50; // 一次最多押钱包的百分之几
+const SPIN_TICKS: u64 = 60; // 第一个转轮转多少帧停下
+const STOP_STAGGER: u64 = 25; // 后面的转轮各晚停多少帧
+const REEL_SIZE: f32 = 90.0;
+const REEL_GAP: f32 = 20.0;
+
+// 转轮上的条带，石头多钻石少
+const STRIP: [ItemType; 12] = [
+    ItemType::Rock,
+    ItemType::Silver,
+    ItemType::Rock,
+    ItemType::Gold(BagSize::Medium),
+    ItemType::Skull,
+    ItemType::Rock,
+    ItemType::Silver,
+    ItemType::Diamond,
+    ItemType::Rock,
+    ItemType::Silver,
+    ItemType::Gold(BagSize::Medium),
+    ItemType::Skull,
+];
+
+// 三个一样时的赔率
+fn jackpot(symbol: ItemType) -> i64 {
+    match symbol {
+        ItemType::Rock => 3,
+        ItemType::Silver => 6,
+        ItemType::Gold(_) => 15,
+        ItemType::Diamond => 50,
+        _ => 0,
+    }
+}
+
+// 押 wager 转出 symbols 时赔回的钱（含本金）
+fn payout(symbols: [ItemType; 3], wager: i64) -> i64 {
+    let [a, b, c] = symbols;
+    if a == b && b == c {
+        wager * jackpot(a)
+    } else if (a == b && a != ItemType::Skull) || (b == c && b != ItemType::Skull) || (a == c && a != ItemType::Skull) {
+        wager * 3 / 2
+    } else {
+        0
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SlotMachine {
+    pub wager: i64,
+    reels: [usize; 3],   // 各转轮当前显示的条带位置
+    targets: [usize; 3], // 这一转最后停下的位置
+    tick: u64,
+    pub spinning: bool,
+    message: Option<String>,
+    rng: StdRng,
+}
+
+impl SlotMachine {
+    pub fn new(wallet: i64, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let reels = [rng.gen_range(0..STRIP.len()), rng.gen_range(0..STRIP.len()), rng.gen_range(0..STRIP.len())];
+        SlotMachine {
+            wager: WAGER_STEP.min(max_wager(wallet)),
+            reels,
+            targets: reels,
+            tick: 0,
+            spinning: false,
+            message: None,
+            rng,
+        }
+    }
+
+    // 加减押注，转动中不能改
+    pub fn raise(&mut self, wallet: i64) {
+        if !self.spinning {
+            self.wager = (self.wager + WAGER_STEP).min(max_wager(wallet));
+        }
+    }
+
+    pub fn lower(&mut self, wallet: i64) {
+        if !self.spinning {
+            self.wager = (self.wager - WAGER_STEP).max(WAGER_STEP.min(max_wager(wallet)));
+        }
+    }
+
+    // 扣下押注开转
+    pub fn spin(&mut self, wallet: &mut i64) {
+        if self.spinning {
+            return;
+        }
+        self.wager = self.wager.min(max_wager(*wallet));
+        if self.wager <= 0 {
+            self.message = Some("Your wallet is empty".to_string());
+            return;
+        }
+        *wallet -= self.wager;
+        for target in self.targets.iter_mut() {
+            *target = self.rng.gen_range(0..STRIP.len());
+        }
+        self.tick = 0;
+        self.spinning = true;
+        self.message = None;
+    }
+
+    // 每帧转动转轮，最后一个停下时把赔的钱放回钱包
+    pub fn update(&mut self, wallet: &mut i64) {
+        if !self.spinning {
+            return;
+        }
+        self.tick += 1;
+        for reel in 0..3 {
+            self.reels[reel] = if self.tick < SPIN_TICKS + STOP_STAGGER * reel as u64 {
+                (self.reels[reel] + 1) % STRIP.len()
+            } else {
+                self.targets[reel]
+            };
+        }
+        if self.tick >= SPIN_TICKS + STOP_STAGGER * 2 {
+            self.spinning = false;
+            let won = payout(self.symbols(), self.wager);
+            *wallet += won;
+            self.message = Some(if won > 0 { format!("You win ${}!", won) } else { "No luck this time".to_string() });
+        }
+    }
+
+    fn symbols(&self) -> [ItemType; 3] {
+        [STRIP[self.reels[0]], STRIP[self.reels[1]], STRIP[self.reels[2]]]
+    }
+
+    pub fn draw(&self, ctx: &mut Context, wallet: i64, screen_w: f32, screen_h: f32) -> GameResult {
+        let gold = Color::new(1.0, 0.84, 0.0, 1.0);
+        draw_centered_text(ctx, screen_w, "Slot Machine", 40.0, gold, 40.0)?;
+        draw_centered_text(ctx, screen_w, &format!("Wallet: ${}", wallet), 22.0, Color::WHITE, 95.0)?;
+
+        let left = (screen_w - REEL_SIZE * 3.0 - REEL_GAP * 2.0) / 2.0;
+        let top = screen_h / 2.0 - REEL_SIZE;
+        for (reel, symbol) in self.symbols().iter().enumerate() {
+            let x = left + reel as f32 * (REEL_SIZE + REEL_GAP);
+            let frame = Rect::new(x, top, REEL_SIZE, REEL_SIZE);
+            graphics::rectangle(ctx, graphics::DrawParam::default().dest(frame.point()), &frame, Color::new(0.15, 0.1, 0.05, 1.0))?;
+            let face = Rect::new(x + 20.0, top + 20.0, REEL_SIZE - 40.0, REEL_SIZE - 40.0);
+            graphics::rectangle(ctx, graphics::DrawParam::default().dest(face.point()), &face, symbol.color())?;
+            let label = Text::new(TextFragment::new(symbol.name())
+                .color(Color::WHITE)
+                .font_size(14));
+            let size = label.dimensions(ctx)?;
+            let below = na::Point2::new(x + (REEL_SIZE - size.w) / 2.0, top + REEL_SIZE + 4.0);
+            graphics::draw(ctx, &label, graphics::DrawParam::default().dest(below))?;
+        }
+
+        let wager = format!("<  Wager: ${}  >   (up to {}% of your wallet)", self.wager, MAX_WAGER_SHARE);
+        draw_centered_text(ctx, screen_w, &wager, 22.0, gold, top + REEL_SIZE + 35.0)?;
+        draw_centered_text(
+            ctx,
+            screen_w,
+            "Three alike: Rock x3  Silver x6  Gold x15  Diamond x50   Pair (no skulls): x1.5",
+            16.0,
+            Color::new(0.8, 0.8, 0.8, 1.0),
+            top + REEL_SIZE + 70.0,
+        )?;
+        if let Some(message) = &self.message {
+            draw_centered_text(ctx, screen_w, message, 24.0, Color::WHITE, screen_h - 110.0)?;
+        }
+        draw_centered_text(
+            ctx,
+            screen_w,
+            "Left/Right: wager   Space: spin   Esc: back",
+            16.0,
+            Color::new(0.7, 0.7, 0.7, 1.0),
+            screen_h - 40.0,
+        )
+    }
+}
+
+// 钱包允许的最大押注
+fn max_wager(wallet: i64) -> i64 {
+    (wallet * MAX_WAGER_SHARE / 100).max(0)
+}
@@ -0,0 +1,254 @@
+// 游戏统计：订阅游戏事件累计每局和历史数据，可导出为 CSV/JSON
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::events::{EventSubscriber, GameEvent};
+use crate::storage;
+use crate::TICKS_PER_SECOND;
+
+const LIFETIME_FILE: &str = "lifetime_stats.txt";
+
+// 某类物品的统计
+#[derive(Debug, Clone, Default)]
+pub struct ItemStats {
+    pub count: u32,
+    pub value: i64,
+}
+
+// 一局的统计
+#[derive(Debug, Clone, Default)]
+pub struct RunStats {
+    pub seed: u64,
+    pub level: u32,
+    pub throws: u32,
+    pub hits: u32,
+    pub score: i64,
+    pub ticks: u64,
+    pub items: BTreeMap<String, ItemStats>,
+}
+
+impl RunStats {
+    // 命中率（抓到东西的出钩占比）
+    pub fn accuracy(&self) -> f32 {
+        if self.throws == 0 {
+            0.0
+        } else {
+            self.hits as f32 / self.throws as f32
+        }
+    }
+
+    pub fn seconds(&self) -> f32 {
+        self.ticks as f32 / TICKS_PER_SECOND as f32
+    }
+}
+
+// 历史累计统计，保存在用户数据目录
+#[derive(Debug, Clone, Default)]
+pub struct LifetimeStats {
+    pub runs: u32,
+    pub totals: RunStats,
+}
+
+impl LifetimeStats {
+    fn add(&mut self, run: &RunStats) {
+        self.runs += 1;
+        self.totals.throws += run.throws;
+        self.totals.hits += run.hits;
+        self.totals.score += run.score;
+        self.totals.ticks += run.ticks;
+        for (name, item) in &run.items {
+            let total = self.totals.items.entry(name.clone()).or_default();
+            total.count += item.count;
+            total.value += item.value;
+        }
+    }
+
+    fn path() -> PathBuf {
+        storage::user_data_dir().join(LIFETIME_FILE)
+    }
+
+    // 读取历史统计，文件不存在或损坏时从零开始
+    pub fn load() -> Self {
+        let text = match fs::read_to_string(Self::path()) {
+            Ok(text) => text,
+            Err(_) => return LifetimeStats::default(),
+        };
+        let mut stats = LifetimeStats::default();
+        for line in text.lines() {
+            let (key, value) = match line.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => continue,
+            };
+            match key {
+                "runs" => stats.runs = value.parse().unwrap_or(0),
+                "throws" => stats.totals.throws = value.parse().unwrap_or(0),
+                "hits" => stats.totals.hits = value.parse().unwrap_or(0),
+                "score" => stats.totals.score = value.parse().unwrap_or(0),
+                "ticks" => stats.totals.ticks = value.parse().unwrap_or(0),
+                _ => {
+                    // item.<类型>.count / item.<类型>.value
+                    let mut parts = key.splitn(3, '.');
+                    if let (Some("item"), Some(name), Some(field)) = (parts.next(), parts.next(), parts.next()) {
+                        let item = stats.totals.items.entry(name.to_string()).or_default();
+                        match field {
+                            "count" => item.count = value.parse().unwrap_or(0),
+                            "value" => item.value = value.parse().unwrap_or(0),
+                            _ => (),
+                        }
+                    }
+                }
+            }
+        }
+        stats
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        fs::create_dir_all(storage::user_data_dir())?;
+        let mut out = fs::File::create(Self::path())?;
+        writeln!(out, "runs = {}", self.runs)?;
+        writeln!(out, "throws = {}", self.totals.throws)?;
+        writeln!(out, "hits = {}", self.totals.hits)?;
+        writeln!(out, "score = {}", self.totals.score)?;
+        writeln!(out, "ticks = {}", self.totals.ticks)?;
+        for (name, item) in &self.totals.items {
+            writeln!(out, "item.{}.count = {}", name, item.count)?;
+            writeln!(out, "item.{}.value = {}", name, item.value)?;
+        }
+        Ok(())
+    }
+}
+
+// 统计订阅者
+#[derive(Debug, Default)]
+pub struct StatsTracker {
+    pub current: RunStats,
+    pub runs: Vec<RunStats>, // 本次启动以来结束的各局
+    pub lifetime: LifetimeStats,
+}
+
+impl StatsTracker {
+    pub fn new() -> Self {
+        StatsTracker::default()
+    }
+
+    // 读取历史统计（只有窗口游戏需要，离屏模拟不读写磁盘）
+    pub fn with_lifetime() -> Self {
+        StatsTracker {
+            lifetime: LifetimeStats::load(),
+            ..StatsTracker::default()
+        }
+    }
+
+    pub fn start_run(&mut self, seed: u64, level: u32) {
+        self.current = RunStats {
+            seed,
+            level,
+            ..RunStats::default()
+        };
+    }
+
+    // 导出到用户数据目录下的 stats/，返回目录路径
+    pub fn export(&self) -> io::Result<PathBuf> {
+        let dir = storage::data_subdir("stats")?;
+        self.write_runs_csv(&dir.join("runs.csv"))?;
+        self.write_items_csv(&dir.join("items.csv"))?;
+        self.write_json(&dir.join("stats.json"))?;
+        Ok(dir)
+    }
+
+    fn write_runs_csv(&self, path: &Path) -> io::Result<()> {
+        let mut out = fs::File::create(path)?;
+        writeln!(out, "scope,seed,level,throws,hits,accuracy,seconds,score")?;
+        for run in &self.runs {
+            writeln!(
+                out,
+                "run,{},{},{},{},{:.3},{:.2},{}",
+                run.seed, run.level, run.throws, run.hits, run.accuracy(), run.seconds(), run.score
+            )?;
+        }
+        let total = &self.lifetime.totals;
+        writeln!(
+            out,
+            "lifetime,,,{},{},{:.3},{:.2},{}",
+            total.throws, total.hits, total.accuracy(), total.seconds(), total.score
+        )
+    }
+
+    fn write_items_csv(&self, path: &Path) -> io::Result<()> {
+        let mut out = fs::File::create(path)?;
+        writeln!(out, "scope,run,item_type,count,value")?;
+        for (index, run) in self.runs.iter().enumerate() {
+            for (name, item) in &run.items {
+                writeln!(out, "run,{},{},{},{}", index + 1, name, item.count, item.value)?;
+            }
+        }
+        for (name, item) in &self.lifetime.totals.items {
+            writeln!(out, "lifetime,,{},{},{}", name, item.count, item.value)?;
+        }
+        Ok(())
+    }
+
+    fn write_json(&self, path: &Path) -> io::Result<()> {
+        let runs: Vec<String> = self.runs.iter().map(run_json).collect();
+        let json = format!(
+            "{{\n  \"runs\": [{}],\n  \"lifetime\": {{\"runs\": {}, \"totals\": {}}}\n}}\n",
+            runs.join(", "),
+            self.lifetime.runs,
+            run_json(&self.lifetime.totals)
+        );
+        fs::write(path, json)
+    }
+}
+
+impl EventSubscriber for StatsTracker {
+    fn on_event(&mut self, event: &GameEvent) {
+        match event {
+            // 只统计玩家一自己的出钩和收获
+            GameEvent::HookThrown { player: 0, .. } => self.current.throws += 1,
+            GameEvent::ItemGrabbed { player: 0, .. } => self.current.hits += 1,
+            GameEvent::ItemBanked { player: 0, item_type, value, .. } => {
+                let item = self.current.items.entry(item_type.name().to_string()).or_default();
+                item.count += 1;
+                item.value += *value as i64;
+            }
+            GameEvent::LevelCompleted { score, ticks, .. } => {
+                self.current.score = *score as i64;
+                self.current.ticks = *ticks;
+                let run = self.current.clone();
+                self.lifetime.add(&run);
+                self.runs.push(run);
+            }
+            GameEvent::HookThrown { .. }
+            | GameEvent::ItemGrabbed { .. }
+            | GameEvent::ItemDrilled { .. }
+            | GameEvent::ItemBanked { .. }
+            | GameEvent::MysteryBagOpened { .. }
+            | GameEvent::BossHit { .. }
+            | GameEvent::RockShattered { .. }
+            | GameEvent::RopeCut { .. }
+            | GameEvent::MinerHit { .. }
+            | GameEvent::ExplosionTriggered { .. } => (),
+        }
+    }
+}
+
+fn run_json(run: &RunStats) -> String {
+    let items: Vec<String> = run
+        .items
+        .iter()
+        .map(|(name, item)| format!("\"{}\": {{\"count\": {}, \"value\": {}}}", name, item.count, item.value))
+        .collect();
+    format!(
+        "{{\"seed\": {}, \"level\": {}, \"throws\": {}, \"hits\": {}, \"accuracy\": {:.3}, \"seconds\": {:.2}, \"score\": {}, \"items\": {{{}}}}}",
+        run.seed,
+        run.level,
+        run.throws,
+        run.hits,
+        run.accuracy(),
+        run.seconds(),
+        run.score,
+        items.join(", ")
+    )
+}
@@ -0,0 +1,28 @@
+// 用户数据目录：统计、崩溃报告、存档等文件都放在这里
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+const APP_DIR: &str = "gold_miner";
+
+// 按平台约定定位用户数据目录，找不到时退回当前目录
+pub fn user_data_dir() -> PathBuf {
+    let base = if cfg!(target_os = "windows") {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+    };
+    base.unwrap_or_else(|| PathBuf::from(".")).join(APP_DIR)
+}
+
+// 数据目录下的子目录，不存在时创建
+pub fn data_subdir(name: &str) -> io::Result<PathBuf> {
+    let dir = user_data_dir().join(name);
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
@@ -0,0 +1,261 @@
+// 剧情战役：按清单文件编排的一串关卡，分章节，每章开头有一段旁白；
+// 达到目标分才解锁下一关，部分关卡使用手工摆放的固定布局
+//
+// 清单文件为纯文本，与自由模式互不相干：
+//   ending <过场脚本>           通关后播放的过场动画
+//   chapter <章节标题>
+//   cutscene <过场脚本>         进入本章时播放的过场动画
+//   dialogue <对话脚本>         进入本章时的角色对话
+//   text <旁白>                 可写多行
+//   level <种子> <目标分>
+//   item <类型> <x> <y>         写在 level 之后，则该关使用固定布局
+//   objective <目标> <奖励>     该关的附加目标，见 objectives.rs
+//   dynamite <数量>             该关开局额外发放的炸药
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::objectives::Objective;
+use crate::{storage, Item, ItemType};
+
+pub const STORY_FILE: &str = "story.txt";
+const PROGRESS_FILE: &str = "story_progress.txt";
+
+#[derive(Debug, Clone)]
+pub struct Chapter {
+    pub title: String,
+    pub lines: Vec<String>,
+    pub cutscene: Option<String>,
+    pub dialogue: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct StoryLevel {
+    pub chapter: usize,
+    pub seed: u64,
+    pub target: i32,
+    pub layout: Option<Vec<(ItemType, f32, f32)>>,
+    pub objectives: Vec<Objective>,
+    pub dynamite: u32,
+}
+
+impl StoryLevel {
+    // 固定布局的物品
+    pub fn layout_items(&self, item_size: f32) -> Option<Vec<Item>> {
+        let layout = self.layout.as_ref()?;
+        Some(
+            layout
+                .iter()
+                .map(|&(item_type, x, y)| Item::new(item_type, x, y, item_size))
+                .collect(),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StoryManifest {
+    pub chapters: Vec<Chapter>,
+    pub levels: Vec<StoryLevel>,
+    pub ending: Option<String>,
+}
+
+impl StoryManifest {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Self::parse(&text)
+    }
+
+    pub fn parse(text: &str) -> io::Result<Self> {
+        let mut chapters: Vec<Chapter> = Vec::new();
+        let mut levels: Vec<StoryLevel> = Vec::new();
+        let mut ending = None;
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, rest) = line.split_once(' ').unwrap_or((line, ""));
+            let rest = rest.trim();
+            match key {
+                "chapter" => chapters.push(Chapter {
+                    title: rest.to_string(),
+                    lines: Vec::new(),
+                    cutscene: None,
+                    dialogue: None,
+                }),
+                "cutscene" => {
+                    chapters
+                        .last_mut()
+                        .ok_or_else(|| invalid(number, "过场动画必须写在章节之后"))?
+                        .cutscene = Some(rest.to_string())
+                }
+                "dialogue" => {
+                    chapters
+                        .last_mut()
+                        .ok_or_else(|| invalid(number, "对话必须写在章节之后"))?
+                        .dialogue = Some(rest.to_string())
+                }
+                "ending" => ending = Some(rest.to_string()),
+                "text" => chapters
+                    .last_mut()
+                    .ok_or_else(|| invalid(number, "旁白必须写在章节之后"))?
+                    .lines
+                    .push(rest.to_string()),
+                "level" => {
+                    if chapters.is_empty() {
+                        return Err(invalid(number, "关卡必须写在章节之后"));
+                    }
+                    let mut parts = rest.split_whitespace();
+                    levels.push(StoryLevel {
+                        chapter: chapters.len() - 1,
+                        seed: parse_field(number, parts.next())?,
+                        target: parse_field(number, parts.next())?,
+                        layout: None,
+                        objectives: Vec::new(),
+                        dynamite: 0,
+                    });
+                }
+                "item" => {
+                    let level = levels.last_mut().ok_or_else(|| invalid(number, "物品必须写在关卡之后"))?;
+                    let mut parts = rest.split_whitespace();
+                    let item_type = parts
+                        .next()
+                        .and_then(ItemType::from_name)
+                        .ok_or_else(|| invalid(number, "未知的物品类型"))?;
+                    let x = parse_field(number, parts.next())?;
+                    let y = parse_field(number, parts.next())?;
+                    level.layout.get_or_insert_with(Vec::new).push((item_type, x, y));
+                }
+                "objective" => {
+                    let level = levels.last_mut().ok_or_else(|| invalid(number, "目标必须写在关卡之后"))?;
+                    level.objectives.push(Objective::parse(rest).map_err(|msg| invalid(number, msg))?);
+                }
+                "dynamite" => {
+                    let level = levels.last_mut().ok_or_else(|| invalid(number, "炸药必须写在关卡之后"))?;
+                    level.dynamite = parse_field(number, Some(rest))?;
+                }
+                _ => return Err(invalid(number, "未知的指令")),
+            }
+        }
+        if levels.is_empty() {
+            return Err(invalid(0, "清单中没有关卡"));
+        }
+        Ok(StoryManifest { chapters, levels, ending })
+    }
+
+    // 该关是否是所在章节的第一关
+    pub fn opens_chapter(&self, index: usize) -> bool {
+        index == 0 || self.levels[index - 1].chapter != self.levels[index].chapter
+    }
+}
+
+// 剧情进度：已解锁到第几关、是否通关过，保存在用户数据目录
+#[derive(Debug, Clone, Default)]
+pub struct StoryProgress {
+    pub unlocked: usize,
+    pub completed: bool,
+}
+
+impl StoryProgress {
+    fn path() -> PathBuf {
+        storage::user_data_dir().join(PROGRESS_FILE)
+    }
+
+    // 读取进度，文件不存在或损坏时从第一关开始
+    pub fn load() -> Self {
+        let mut progress = StoryProgress::default();
+        let text = fs::read_to_string(Self::path()).unwrap_or_default();
+        for line in text.lines() {
+            match line.split_once('=').map(|(key, value)| (key.trim(), value.trim())) {
+                Some(("unlocked", value)) => progress.unlocked = value.parse().unwrap_or(0),
+                Some(("completed", value)) => progress.completed = value == "true",
+                _ => (),
+            }
+        }
+        progress
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        fs::create_dir_all(storage::user_data_dir())?;
+        let mut out = fs::File::create(Self::path())?;
+        writeln!(out, "unlocked = {}", self.unlocked)?;
+        writeln!(out, "completed = {}", self.completed)
+    }
+}
+
+// 进行中的剧情战役
+#[derive(Debug, Clone)]
+pub struct StoryRun {
+    pub manifest: StoryManifest,
+    pub progress: StoryProgress,
+    pub index: usize,
+    pub ended: bool, // 最后一关已达标
+}
+
+impl StoryRun {
+    // 从已解锁的最远一关继续
+    pub fn resume(manifest: StoryManifest) -> Self {
+        let progress = StoryProgress::load();
+        let index = progress.unlocked.min(manifest.levels.len() - 1);
+        StoryRun {
+            manifest,
+            progress,
+            index,
+            ended: false,
+        }
+    }
+
+    pub fn current(&self) -> &StoryLevel {
+        &self.manifest.levels[self.index]
+    }
+
+    // 当前章节的旁白（仅在章节第一关显示）
+    pub fn narration(&self) -> Option<String> {
+        if !self.manifest.opens_chapter(self.index) {
+            return None;
+        }
+        let chapter = &self.manifest.chapters[self.current().chapter];
+        Some(format!("{} - {}", chapter.title, chapter.lines.join(" ")))
+    }
+
+    // 进入章节时播放的过场动画
+    pub fn chapter_cutscene(&self) -> Option<String> {
+        if !self.manifest.opens_chapter(self.index) {
+            return None;
+        }
+        self.manifest.chapters[self.current().chapter].cutscene.clone()
+    }
+
+    // 进入章节时的角色对话
+    pub fn chapter_dialogue(&self) -> Option<String> {
+        if !self.manifest.opens_chapter(self.index) {
+            return None;
+        }
+        self.manifest.chapters[self.current().chapter].dialogue.clone()
+    }
+
+    // 本关达标，解锁下一关；返回是否还有下一关
+    pub fn complete(&mut self) -> bool {
+        self.progress.unlocked = self.progress.unlocked.max(self.index + 1);
+        self.ended = self.index + 1 >= self.manifest.levels.len();
+        self.progress.completed |= self.ended;
+        !self.ended
+    }
+
+    // 当前关已解锁下一关时前进，否则停在原关重试
+    pub fn advance(&mut self) {
+        if self.progress.unlocked > self.index && self.index + 1 < self.manifest.levels.len() {
+            self.index += 1;
+        }
+    }
+}
+
+fn parse_field<T: std::str::FromStr>(line: usize, value: Option<&str>) -> io::Result<T> {
+    value
+        .and_then(|v| v.parse().ok())
+        .ok_or_else(|| invalid(line, "字段格式错误"))
+}
+
+fn invalid(line: usize, msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("第 {} 行: {}", line + 1, msg))
+}
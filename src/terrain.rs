@@ -0,0 +1,104 @@
+// 泥土层：矿洞里铺着一格一格的泥土，钩子碰到泥土要一下下凿穿，每次出钩削掉一层，
+// 凿不穿就弹回来。有些物品埋在泥土里，要先把那一格挖开才能钩到。布局按种子生成，回放时完全一致
+use ggez::{
+    graphics::{self, Color, Rect},
+    nalgebra as na, Context, GameResult,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::config::GameConfig;
+use crate::Item;
+
+const TILE_SIZE: f32 = 40.0;
+const TOP: f32 = 120.0; // 泥土层从这个深度开始，地面附近不铺
+
+// 钩子凿泥土的结果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dig {
+    Eroded, // 削掉一层，还没凿穿，钩子弹回
+    Cleared,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Terrain {
+    tiles: Vec<u8>, // 每格剩余的层数，0 为已挖空
+    cols: usize,
+    rows: usize,
+    hardness: u8,
+}
+
+impl Terrain {
+    pub fn roll(seed: u64, config: &GameConfig, items: &[Item]) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed.wrapping_add(0x64697274));
+        let cols = (config.screen_width / TILE_SIZE).ceil() as usize;
+        let rows = ((config.mine_depth - TOP).max(0.0) / TILE_SIZE).ceil() as usize;
+        let hardness = config.dirt_hardness.max(1);
+        let tiles = (0..cols * rows)
+            .map(|_| if rng.gen::<f32>() < config.dirt_density { rng.gen_range(1..=hardness) } else { 0 })
+            .collect();
+        let mut terrain = Terrain {
+            tiles,
+            cols,
+            rows,
+            hardness,
+        };
+        // 埋起来的物品：所在的格子和上面一格都填满最硬的泥土
+        for item in items {
+            if rng.gen::<f32>() < config.buried_chance {
+                for y in [item.position.y, item.position.y - TILE_SIZE] {
+                    if let Some(index) = terrain.index(na::Point2::new(item.position.x, y)) {
+                        terrain.tiles[index] = hardness;
+                    }
+                }
+            }
+        }
+        terrain
+    }
+
+    fn index(&self, point: na::Point2<f32>) -> Option<usize> {
+        if point.x < 0.0 || point.y < TOP {
+            return None;
+        }
+        let col = (point.x / TILE_SIZE) as usize;
+        let row = ((point.y - TOP) / TILE_SIZE) as usize;
+        if col < self.cols && row < self.rows {
+            Some(row * self.cols + col)
+        } else {
+            None
+        }
+    }
+
+    // 该位置是否埋在泥土里
+    pub fn buried(&self, point: na::Point2<f32>) -> bool {
+        self.index(point).map_or(false, |index| self.tiles[index] > 0)
+    }
+
+    // 钩子凿到该位置的泥土，削掉一层；没有泥土时返回 None
+    pub fn dig(&mut self, point: na::Point2<f32>) -> Option<Dig> {
+        let index = self.index(point)?;
+        let tile = &mut self.tiles[index];
+        if *tile == 0 {
+            return None;
+        }
+        *tile -= 1;
+        Some(if *tile == 0 { Dig::Cleared } else { Dig::Eroded })
+    }
+
+    // 只画镜头范围内的格子，越硬颜色越深
+    pub fn draw(&self, ctx: &mut Context, view_top: f32, view_height: f32) -> GameResult {
+        let first = ((view_top - TOP).max(0.0) / TILE_SIZE) as usize;
+        let last = (((view_top + view_height - TOP).max(0.0) / TILE_SIZE).ceil() as usize).min(self.rows);
+        for row in first..last {
+            for col in 0..self.cols {
+                let layers = self.tiles[row * self.cols + col];
+                if layers == 0 {
+                    continue;
+                }
+                let shade = 0.45 - 0.2 * layers as f32 / self.hardness as f32;
+                let tile = Rect::new(col as f32 * TILE_SIZE, TOP + row as f32 * TILE_SIZE, TILE_SIZE, TILE_SIZE);
+                graphics::rectangle(ctx, graphics::DrawParam::default().dest(tile.point()), &tile, Color::new(shade + 0.1, shade, shade * 0.6, 1.0))?;
+            }
+        }
+        Ok(())
+    }
+}
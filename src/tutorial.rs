@@ -0,0 +1,114 @@
+// 教学关：第一次从选关地图进入第一关时先打这一关。布局固定、没有危险物、不计时，
+// 按顺序教开矿车、出钩、瞄准和重量，每一步完成后才出现下一步的提示，没学到的操作暂时不起作用。
+// 教完后结算，回车进入真正的第一关；纪录文件里记下已经教过，之后在选关地图上按 T 可以重来
+use crate::config::GameConfig;
+use crate::events::{EventSubscriber, GameEvent};
+use crate::input::InputAction;
+use crate::{BagSize, Item, ItemType};
+
+pub const TUTORIAL_RECORD: &str = "tutorial_done";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Step {
+    Move,   // 左右各开一次矿车
+    Throw,  // 出一次钩
+    Aim,    // 瞄准并钩住值钱的东西
+    Weight, // 钩一块大石头，感受重物收得慢
+    Done,
+}
+
+#[derive(Debug, Clone)]
+pub struct Tutorial {
+    pub step: Step,
+    moved_left: bool,
+    moved_right: bool,
+    rock_banked: bool, // 已经拉回过石头，教到重量这一步时直接算学会
+}
+
+impl Tutorial {
+    pub fn new() -> Self {
+        Tutorial {
+            step: Step::Move,
+            moved_left: false,
+            moved_right: false,
+            rock_banked: false,
+        }
+    }
+
+    // 当前这一步的提示，pendulum 为摆钩模式
+    pub fn prompt(&self, pendulum: bool) -> &'static str {
+        match self.step {
+            Step::Move => "Use Left and Right to drive the cart both ways",
+            Step::Throw => "Press Space to throw the hook",
+            Step::Aim if pendulum => "Wait until the swinging hook points at the gold, then throw",
+            Step::Aim => "Point the mouse at the gold and throw to hook it",
+            Step::Weight => "Now hook a rock - heavy things reel in slowly and are worth little",
+            Step::Done => "Well done! You are ready to dig",
+        }
+    }
+
+    // 第几步，从 1 开始，教完为 None
+    pub fn progress(&self) -> Option<(usize, usize)> {
+        let index = [Step::Move, Step::Throw, Step::Aim, Step::Weight].iter().position(|&step| step == self.step)?;
+        Some((index + 1, 4))
+    }
+
+    pub fn finished(&self) -> bool {
+        self.step == Step::Done
+    }
+
+    // 还没教到出钩时不能出钩，炸药和钻头等教完再用
+    pub fn allows(&self, action: &InputAction) -> bool {
+        match action {
+            InputAction::MoveLeft | InputAction::MoveRight | InputAction::Coast => true,
+            InputAction::Throw { .. } => self.step != Step::Move,
+            _ => self.finished(),
+        }
+    }
+
+    pub fn on_action(&mut self, action: &InputAction) {
+        if self.step != Step::Move {
+            return;
+        }
+        match action {
+            InputAction::MoveLeft => self.moved_left = true,
+            InputAction::MoveRight => self.moved_right = true,
+            _ => (),
+        }
+        if self.moved_left && self.moved_right {
+            self.step = Step::Throw;
+        }
+    }
+}
+
+impl EventSubscriber for Tutorial {
+    fn on_event(&mut self, event: &GameEvent) {
+        // 布局里只有两块石头，瞄准那一步就可能都拉回来了，之后不会再有石头可钩
+        if let GameEvent::ItemBanked { item_type: ItemType::Rock, .. } = event {
+            self.rock_banked = true;
+        }
+        self.step = match (self.step, event) {
+            (Step::Throw, GameEvent::HookThrown { .. }) => Step::Aim,
+            (Step::Aim, GameEvent::ItemGrabbed { item_type, .. }) if *item_type != ItemType::Rock => Step::Weight,
+            (Step::Weight, _) if self.rock_banked => Step::Done,
+            (step, _) => step,
+        };
+    }
+}
+
+// 固定布局：正下方一小块金子，两侧是大金块、钻石和两块石头，都在开局的钩子够得着的地方
+pub fn layout(config: &GameConfig) -> Vec<Item> {
+    let center = config.screen_width / 2.0;
+    let size = config.item_size;
+    [
+        (ItemType::Gold(BagSize::Small), 0.0, 200.0),
+        (ItemType::Gold(BagSize::Large), -140.0, 300.0),
+        (ItemType::Gold(BagSize::Medium), 160.0, 260.0),
+        (ItemType::Diamond, 60.0, 340.0),
+        (ItemType::Rock, -70.0, 250.0),
+        (ItemType::Rock, 100.0, 380.0),
+    ]
+    .iter()
+    .map(|&(item_type, dx, y)| Item::new(item_type, center + dx, y, size))
+    .collect()
+}
@@ -0,0 +1,49 @@
+// 本地双人对战：两名矿工在同一片矿场里抢东西，玩家一用方向键和空格，玩家二用 A/D 移动、S 出钩。
+// 玩家二有自己的矿车、钩子和输入源，钩子和玩家一的放在同一个列表里，按 player 区分归属；
+// 两人的得分分开计算，时间到后得分高的获胜。对战不录回放。
+// 双人合作沿用同一套玩家二（矿车、钩子、按键），只是得分记到共同的关卡得分上
+use ggez::input::keyboard::KeyCode;
+
+use crate::input::{InputAction, InputSource, KeyboardInput, Steering};
+use crate::Miner;
+
+pub const RIVAL: usize = 1; // 玩家二的编号，对应钩子的 player
+
+pub struct Rival {
+    pub miner: Miner,
+    pub score: i32,
+    pub input: Box<dyn InputSource>,
+    pub steering: Steering, // 玩家二按住的方向键
+}
+
+impl Rival {
+    pub fn new(miner: Miner) -> Self {
+        Rival {
+            miner,
+            score: 0,
+            input: Box::new(KeyboardInput::new()),
+            steering: Steering::default(),
+        }
+    }
+}
+
+// 玩家二的按键，angle 为玩家二钩子当前的摆动角度
+pub fn key_action(keycode: KeyCode, angle: f32) -> Option<InputAction> {
+    match keycode {
+        KeyCode::A => Some(InputAction::MoveLeft),
+        KeyCode::D => Some(InputAction::MoveRight),
+        KeyCode::S => Some(InputAction::Throw { angle, power: 1.0 }),
+        _ => None,
+    }
+}
+
+// 对战结果的标题，cpu 为对手是电脑
+pub fn verdict(score: i32, rival_score: i32, cpu: bool) -> &'static str {
+    match (score.cmp(&rival_score), cpu) {
+        (std::cmp::Ordering::Greater, false) => "Player 1 Wins!",
+        (std::cmp::Ordering::Less, false) => "Player 2 Wins!",
+        (std::cmp::Ordering::Greater, true) => "You Win!",
+        (std::cmp::Ordering::Less, true) => "CPU Wins!",
+        (std::cmp::Ordering::Equal, _) => "Draw!",
+    }
+}
@@ -0,0 +1,112 @@
+// 水下区域：水下主题的关卡在水面以下有水的阻力，钩子伸缩变慢，轻的物品随水流左右漂动，
+// 水底不断冒出气泡。气泡的位置用按种子初始化的随机数，回放时完全一致
+use ggez::{
+    graphics::{self, Color, Rect},
+    nalgebra as na, Context, GameResult,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::config::GameConfig;
+use crate::parallel;
+use crate::worlds::Theme;
+use crate::Item;
+
+const LIGHT_WEIGHT: f32 = 1.0; // 不超过这个重量的物品会漂动
+const DRIFT_SPEED: f32 = 0.3; // 漂动每帧最多移动的像素
+const DRIFT_PERIOD: f32 = 240.0; // 来回漂一趟的帧数
+const BUBBLE_EVERY: u64 = 20; // 每隔几帧冒一个气泡
+const BUBBLE_SPEED: f32 = 1.2;
+const BUBBLE_SIZE: f32 = 5.0;
+
+#[derive(Debug, Clone)]
+pub struct Water {
+    line: Option<f32>, // 水面的纵坐标，None 为本关没有水
+    drag: f32,
+    bottom: f32,
+    width: f32,
+    bubbles: Vec<na::Point2<f32>>,
+    rng: StdRng,
+}
+
+impl Default for Water {
+    fn default() -> Self {
+        Water {
+            line: None,
+            drag: 1.0,
+            bottom: 0.0,
+            width: 0.0,
+            bubbles: Vec::new(),
+            rng: StdRng::seed_from_u64(0),
+        }
+    }
+}
+
+impl Water {
+    pub fn new(seed: u64, config: &GameConfig, theme: &Theme) -> Self {
+        Water {
+            line: theme.water_line.map(|depth| config.mine_depth * depth),
+            drag: config.water_drag,
+            bottom: config.mine_depth,
+            width: config.screen_width,
+            bubbles: Vec::new(),
+            rng: StdRng::seed_from_u64(seed.wrapping_add(0x77617465)),
+        }
+    }
+
+    fn submerged(&self, y: f32) -> bool {
+        self.line.map_or(false, |line| y > line)
+    }
+
+    // 该深度钩子的速度倍率：水面以下受阻力变慢
+    pub fn drag_at(&self, y: f32) -> f32 {
+        if self.submerged(y) {
+            self.drag
+        } else {
+            1.0
+        }
+    }
+
+    // 每帧：气泡上浮，水下轻的物品随水流左右漂动，漂动的物品标记为 drifting
+    pub fn update(&mut self, tick: u64, items: &mut [Item]) {
+        let line = match self.line {
+            Some(line) => line,
+            None => return,
+        };
+
+        if tick % BUBBLE_EVERY == 0 {
+            let x = self.rng.gen_range(0.0..self.width);
+            self.bubbles.push(na::Point2::new(x, self.bottom));
+        }
+        for bubble in self.bubbles.iter_mut() {
+            bubble.y -= BUBBLE_SPEED;
+        }
+        self.bubbles.retain(|bubble| bubble.y > line);
+
+        let water = &*self;
+        parallel::for_each_mut(items, |i, item| {
+            if item.collected || item.falling || item.weight() > LIGHT_WEIGHT || !water.submerged(item.position.y) {
+                return;
+            }
+            // 每件物品的相位不同，不会整齐划一地漂
+            let phase = (tick as f32 / DRIFT_PERIOD + i as f32 * 0.37) * std::f32::consts::TAU;
+            let half = item.size() / 2.0;
+            item.position.x = (item.position.x + phase.sin() * DRIFT_SPEED).clamp(half, water.width - half);
+            item.drifting = true;
+        });
+    }
+
+    // 水面和气泡
+    pub fn draw(&self, ctx: &mut Context) -> GameResult {
+        let line = match self.line {
+            Some(line) => line,
+            None => return Ok(()),
+        };
+        let surface = Rect::new(0.0, line - 2.0, self.width, 4.0);
+        graphics::rectangle(ctx, graphics::DrawParam::default().dest(surface.point()), &surface, Color::new(0.7, 0.9, 1.0, 0.6))?;
+        for bubble in &self.bubbles {
+            let rect = Rect::new(bubble.x - BUBBLE_SIZE / 2.0, bubble.y - BUBBLE_SIZE / 2.0, BUBBLE_SIZE, BUBBLE_SIZE);
+            graphics::rectangle(ctx, graphics::DrawParam::default().dest(rect.point()), &rect, Color::new(0.85, 0.95, 1.0, 0.7))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+// 世界主题：经典矿洞、水下、熔岩洞穴。主题决定背景配色、生成哪些物品以及危险物的多少，
+// 自由模式每 3 关换一个世界，也可以在配置里用 world 固定一个
+use ggez::graphics::Color;
+use rand::Rng;
+
+use crate::{BagSize, ItemType};
+
+const LEVELS_PER_WORLD: u32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum World {
+    #[default]
+    Mine,
+    Underwater,
+    Lava,
+}
+
+// 主题数据
+pub struct Theme {
+    pub title: &'static str,
+    pub sky: Color,
+    pub surface: Color,                          // 地表那条线
+    pub bands: [Color; 4],                       // 由浅到深的地层
+    pub pebble: Color,                           // 地层里的碎石
+    pub spawn_table: &'static [Spawn], // 可能生成的物品
+    pub tnt_factor: usize,             // 炸药桶数量是配置值的几倍
+    pub moles: bool,                   // 有没有地鼠出没
+    pub water_line: Option<f32>,       // 水面所在的深度（占矿洞深度的比例），水下钩子变慢、轻的物品漂动
+}
+
+// 生成表的一项：物品、权重和最浅出现深度（占矿洞深度的比例，0 为地表，1 为矿底）
+pub struct Spawn {
+    pub item_type: ItemType,
+    pub weight: u32,
+    pub min_depth: f32,
+}
+
+const fn spawn(item_type: ItemType, weight: u32, min_depth: f32) -> Spawn {
+    Spawn {
+        item_type,
+        weight,
+        min_depth,
+    }
+}
+
+const MINE: Theme = Theme {
+    title: "Classic Mine",
+    sky: Color::new(0.0, 0.2, 0.4, 1.0),
+    surface: Color::new(0.2, 0.6, 0.2, 1.0),
+    bands: [
+        Color::new(0.55, 0.4, 0.2, 1.0),
+        Color::new(0.47, 0.33, 0.16, 1.0),
+        Color::new(0.38, 0.26, 0.12, 1.0),
+        Color::new(0.3, 0.2, 0.1, 1.0),
+    ],
+    pebble: Color::new(0.25, 0.18, 0.1, 1.0),
+    spawn_table: &[
+        spawn(ItemType::Gold(BagSize::Small), 2, 0.0),
+        spawn(ItemType::Gold(BagSize::Medium), 2, 0.0),
+        spawn(ItemType::Gold(BagSize::Large), 1, 0.5),
+        spawn(ItemType::Silver, 3, 0.0),
+        spawn(ItemType::Diamond, 1, 0.5),
+        spawn(ItemType::Rock, 1, 0.0),
+        spawn(ItemType::Bone, 1, 0.0),
+        spawn(ItemType::Skull, 1, 0.3),
+    ],
+    tnt_factor: 1,
+    moles: true,
+    water_line: None,
+};
+
+// 水下：沉船里的银器多，黄金少，没有地鼠
+const UNDERWATER: Theme = Theme {
+    title: "Sunken Reef",
+    sky: Color::new(0.3, 0.6, 0.85, 1.0),
+    surface: Color::new(0.85, 0.8, 0.55, 1.0),
+    bands: [
+        Color::new(0.1, 0.45, 0.6, 1.0),
+        Color::new(0.08, 0.36, 0.52, 1.0),
+        Color::new(0.06, 0.27, 0.42, 1.0),
+        Color::new(0.04, 0.18, 0.32, 1.0),
+    ],
+    pebble: Color::new(0.7, 0.85, 0.9, 1.0),
+    spawn_table: &[
+        spawn(ItemType::Gold(BagSize::Small), 1, 0.2),
+        spawn(ItemType::Gold(BagSize::Medium), 1, 0.2),
+        spawn(ItemType::Gold(BagSize::Large), 1, 0.6),
+        spawn(ItemType::Silver, 5, 0.0),
+        spawn(ItemType::Diamond, 1, 0.6),
+        spawn(ItemType::Rock, 1, 0.0),
+        spawn(ItemType::Bone, 2, 0.0), // 沉船水手的遗骨
+        spawn(ItemType::Skull, 1, 0.3),
+    ],
+    tnt_factor: 1,
+    moles: false,
+    water_line: Some(0.15),
+};
+
+// 熔岩洞穴：石头和钻石多，炸药桶加倍，没有地鼠
+const LAVA: Theme = Theme {
+    title: "Lava Caverns",
+    sky: Color::new(0.25, 0.05, 0.05, 1.0),
+    surface: Color::new(0.95, 0.35, 0.05, 1.0),
+    bands: [
+        Color::new(0.35, 0.15, 0.1, 1.0),
+        Color::new(0.3, 0.12, 0.08, 1.0),
+        Color::new(0.25, 0.08, 0.06, 1.0),
+        Color::new(0.4, 0.1, 0.02, 1.0),
+    ],
+    pebble: Color::new(1.0, 0.5, 0.1, 1.0),
+    spawn_table: &[
+        spawn(ItemType::Gold(BagSize::Small), 1, 0.0),
+        spawn(ItemType::Gold(BagSize::Medium), 2, 0.0),
+        spawn(ItemType::Gold(BagSize::Large), 1, 0.5),
+        spawn(ItemType::Diamond, 2, 0.4),
+        spawn(ItemType::MoltenGold, 1, 0.5),
+        spawn(ItemType::Rock, 4, 0.0),
+        spawn(ItemType::Bone, 1, 0.0),
+        spawn(ItemType::Skull, 2, 0.0),
+    ],
+    tnt_factor: 2,
+    moles: false,
+    water_line: None,
+};
+
+impl World {
+    const ALL: [World; 3] = [World::Mine, World::Underwater, World::Lava];
+
+    // 自由模式第 number 关所在的世界
+    pub fn for_level(number: u32) -> World {
+        let index = (number.max(1) - 1) / LEVELS_PER_WORLD;
+        World::ALL[index as usize % World::ALL.len()]
+    }
+
+    pub fn from_name(name: &str) -> Option<World> {
+        match name {
+            "mine" => Some(World::Mine),
+            "underwater" => Some(World::Underwater),
+            "lava" => Some(World::Lava),
+            _ => None,
+        }
+    }
+
+    pub fn theme(self) -> &'static Theme {
+        match self {
+            World::Mine => &MINE,
+            World::Underwater => &UNDERWATER,
+            World::Lava => &LAVA,
+        }
+    }
+}
+
+impl Theme {
+    // 在给定深度（占矿洞深度的比例）按权重抽一种物品，太浅的地方不出稀有物品；
+    // 石头的权重再乘上难度给的倍数
+    pub fn roll_item(&self, rng: &mut impl Rng, depth: f32, rock_weight: f32) -> ItemType {
+        let weight = move |spawn: &Spawn| match spawn.item_type {
+            ItemType::Rock => (spawn.weight as f32 * rock_weight).round() as u32,
+            _ => spawn.weight,
+        };
+        let available = || self.spawn_table.iter().filter(move |spawn| depth >= spawn.min_depth);
+        let total: u32 = available().map(weight).sum();
+        let mut pick = rng.gen_range(0..total);
+        for spawn in available() {
+            if pick < weight(spawn) {
+                return spawn.item_type;
+            }
+            pick -= weight(spawn);
+        }
+        unreachable!()
+    }
+}